@@ -0,0 +1,47 @@
+//! Cost of mnemonic mode per address for several `--mnemonic-indices`.
+//!
+//! Run with `cargo bench --bench mnemonic`. Each line draws keys the way a
+//! worker does (key source, then `Keypair`), so it includes the address.
+//! With one index every address pays for a whole phrase (PBKDF2 seed and
+//! the path to the account node); larger values spread that cost over more
+//! children. Raw keys are the baseline.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use eth_vanity::crypto::{KeySource, Keypair, MnemonicLength, MnemonicSpec, OsSource};
+
+/// Minimum measuring time per line.
+const MEASURE: Duration = Duration::from_secs(3);
+
+fn main() {
+    let raw = nanos_per_key(&mut OsSource);
+    println!("{:<22} {:>10.1} us/address", "raw keys", raw / 1000.0);
+
+    let mut per_phrase = None;
+    for indices in [1, 5, 20, 100, 1000] {
+        let spec = MnemonicSpec::new(MnemonicLength::Words12, indices);
+        let ns = nanos_per_key(&mut *spec.source(Box::new(OsSource)));
+        let phrase = *per_phrase.get_or_insert(ns);
+        println!(
+            "{:<22} {:>10.1} us/address  {:>6.1}x raw  {:>5.1}x faster than 1 index",
+            format!("mnemonic, {} indices", indices),
+            ns / 1000.0,
+            ns / raw,
+            phrase / ns
+        );
+    }
+}
+
+/// Draws keys from `source` and derives their addresses for at least [`MEASURE`].
+fn nanos_per_key(source: &mut dyn KeySource) -> f64 {
+    let mut keys = 0u64;
+    let start = Instant::now();
+    while start.elapsed() < MEASURE {
+        for _ in 0..100 {
+            black_box(Keypair::from_source(source));
+        }
+        keys += 100;
+    }
+    start.elapsed().as_nanos() as f64 / keys as f64
+}
@@ -12,6 +12,9 @@ PATTERN="${PATTERN:-c0ffee}"
 SUFFIX="${SUFFIX:-93}"
 WORKERS="${WORKERS:-$(nproc)}"
 COUNT="${COUNT:-1}"
+# Recipient public key from `eth_vanity keygen` on an offline machine; when
+# set, the result file only holds sealed results
+ENCRYPT_TO="${ENCRYPT_TO:-}"
 
 WORKDIR="/home/ubuntu/eth-vanity"
 RESULT_FILE="/home/ubuntu/vanity-result.txt"
@@ -35,7 +38,8 @@ echo "Starting search..."
     -p "${PATTERN}" \
     -s "${SUFFIX}" \
     -w "${WORKERS}" \
-    -n "${COUNT}" | tee "${RESULT_FILE}"
+    -n "${COUNT}" \
+    ${ENCRYPT_TO:+--encrypt-to "${ENCRYPT_TO}"} | tee "${RESULT_FILE}"
 
 echo ""
 echo "Results saved to ${RESULT_FILE}"
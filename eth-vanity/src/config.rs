@@ -1,15 +1,27 @@
 //! Runtime configuration for the vanity address generator.
 
-use crate::matcher::PatternType;
-use clap::Parser;
+use std::path::PathBuf;
+
+use crate::crypto::{
+    AddressFormat, ExtendedPublicKey, KeystoreKdf, MnemonicLength, MnemonicSpec, Recipient,
+    SafeDeployment, ShareSpec, XpubSpec, HARDENED,
+};
+use crate::matcher::{Pattern, PatternType, Target};
+use clap::{Parser, Subcommand};
 
 /// Ethereum Vanity Address Generator
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 pub struct Config {
-    /// Pattern to search for (hex characters only: 0-9, a-f)
-    #[arg(short, long)]
-    pub pattern: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Pattern to search for (hex characters only: 0-9, a-f; Base58 with --network tron;
+    /// base32 with --network filecoin; base36 with --network icap; bech32 data characters
+    /// with --bech32-hrp)
+    #[arg(short, long, required = true)]
+    pub pattern: Option<String>,
 
     /// Suffix pattern (when used, --pattern becomes the prefix and matching uses both)
     #[arg(short = 's', long)]
@@ -19,6 +31,40 @@ pub struct Config {
     #[arg(short = 't', long, default_value = "prefix")]
     pub pattern_type: PatternType,
 
+    /// Address format to match: ethereum (hex), tron (Base58Check T-address),
+    /// filecoin (f410f delegated address) or icap (XE IBAN, after the check digits)
+    #[arg(long, default_value = "ethereum")]
+    pub network: AddressFormat,
+
+    /// Match the bech32 form with this human-readable part (e.g. evmos, one)
+    #[arg(long, conflicts_with = "network")]
+    pub bech32_hrp: Option<String>,
+
+    /// Match the contract deployed with CREATE at this account nonce instead of the EOA
+    #[arg(long)]
+    pub contract_nonce: Option<u64>,
+
+    /// Match the 1/1 Safe owned by the EOA, deployed by this SafeProxyFactory
+    #[arg(long, conflicts_with = "contract_nonce", requires = "safe_init_code_hash")]
+    pub safe_factory: Option<String>,
+
+    /// keccak256(proxyCreationCode || singleton) of the Safe proxy (with --safe-factory)
+    #[arg(long, requires = "safe_factory")]
+    pub safe_init_code_hash: Option<String>,
+
+    /// Fallback handler passed to Safe.setup (with --safe-factory; default: none)
+    #[arg(long, requires = "safe_factory")]
+    pub safe_fallback_handler: Option<String>,
+
+    /// saltNonce of createProxyWithNonce, decimal or 0x hex (with --safe-factory)
+    #[arg(long, default_value = "0")]
+    pub safe_salt_nonce: String,
+
+    /// Also require the EOA address to start with this pattern
+    /// (with --contract-nonce or --safe-factory)
+    #[arg(long)]
+    pub eoa_pattern: Option<String>,
+
     /// Number of worker threads (default: number of CPU cores)
     #[arg(short = 'w', long)]
     pub workers: Option<usize>,
@@ -31,6 +77,94 @@ pub struct Config {
     #[arg(short = 'n', long, default_value = "1")]
     pub count: usize,
 
+    /// Derive keys from random BIP39 phrases of this many words (12 or 24) at
+    /// m/44'/60'/0'/0/i, so a standard wallet can restore them from the phrase
+    #[arg(long, value_name = "WORDS", conflicts_with_all = ["keystore_dir", "shares"])]
+    pub mnemonic: Option<MnemonicLength>,
+
+    /// Child indices i searched per phrase (with --mnemonic); more indices
+    /// spread each phrase's PBKDF2 seed over more addresses. The default is
+    /// BIP44's gap limit (see `cargo bench --bench mnemonic` for the cost)
+    #[arg(long, default_value = "20", requires = "mnemonic")]
+    pub mnemonic_indices: u32,
+
+    /// Search the normal child indices i of this extended public key instead
+    /// of random keys; only the path is reported, no private key exists here
+    #[arg(
+        long,
+        value_name = "XPUB",
+        conflicts_with_all = ["mnemonic", "keystore_dir", "shares", "entropy_file", "mlock"]
+    )]
+    pub xpub: Option<ExtendedPublicKey>,
+
+    /// Levels of child indices below --xpub: 1 (i) or 2 (i/j)
+    #[arg(long, default_value = "1", requires = "xpub")]
+    pub xpub_depth: u8,
+
+    /// Child indices j searched below each i (with --xpub-depth 2), so
+    /// every worker moves on to the next i instead of walking all 2^31 j
+    #[arg(long, default_value = "65536", requires = "xpub")]
+    pub xpub_indices: u32,
+
+    /// Write each found key as a keystore v3 JSON file in this directory
+    /// (mode 0600) instead of printing the private key
+    #[arg(long)]
+    pub keystore_dir: Option<PathBuf>,
+
+    /// Keystore key derivation function: scrypt or pbkdf2
+    #[arg(long, default_value = "scrypt", requires = "keystore_dir")]
+    pub keystore_kdf: KeystoreKdf,
+
+    /// Read the keystore passphrase from this file descriptor (first line)
+    /// instead of prompting (Unix only)
+    #[arg(long, requires = "keystore_dir")]
+    pub password_fd: Option<u32>,
+
+    /// Seal each result (address and key) to this secp256k1 public key;
+    /// open them with `decrypt` on the machine holding the identity
+    #[arg(long, conflicts_with = "keystore_dir")]
+    pub encrypt_to: Option<Recipient>,
+
+    /// Split each found key into K-of-N Shamir shares (e.g. 2-of-3), one file
+    /// per share in --share-dir, instead of printing the private key. Share
+    /// files are plaintext, so --encrypt-to cannot protect them
+    #[arg(
+        long,
+        value_name = "K-of-N",
+        requires = "share_dir",
+        conflicts_with_all = ["keystore_dir", "encrypt_to"]
+    )]
+    pub shares: Option<ShareSpec>,
+
+    /// Directory for the share files (mode 0600; with --shares)
+    #[arg(long, requires = "shares")]
+    pub share_dir: Option<PathBuf>,
+
+    /// Write each share as 24 BIP39 words instead of hex (with --shares)
+    #[arg(long, requires = "shares")]
+    pub share_mnemonic: bool,
+
+    /// Lock found private keys and their result output in memory (mlock)
+    /// so they are never swapped
+    #[arg(long)]
+    pub mlock: bool,
+
+    /// Mix extra entropy (dice rolls, a hardware RNG device) from this file
+    /// into the OS RNG; `-` reads stdin
+    #[arg(long, value_name = "PATH")]
+    pub entropy_file: Option<PathBuf>,
+
+    /// Derive every key from this seed, for reproducible test fixtures.
+    /// Keys are not secret; they are withheld unless --print-deterministic-keys
+    #[cfg(feature = "deterministic")]
+    #[arg(long, conflicts_with = "entropy_file")]
+    pub deterministic_seed: Option<String>,
+
+    /// Output keys derived from --deterministic-seed anyway
+    #[cfg(feature = "deterministic")]
+    #[arg(long, requires = "deterministic_seed")]
+    pub print_deterministic_keys: bool,
+
     /// Progress report interval in seconds
     #[arg(short = 'r', long, default_value = "5")]
     pub report_interval: u64,
@@ -51,6 +185,36 @@ pub struct Config {
     pub gpu_work_size: usize,
 }
 
+/// Tools for the offline machine that holds the `--encrypt-to` identity,
+/// and for checking or recovering keys.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Create an identity file (mode 0600) and print its recipient public key
+    Keygen {
+        /// Where to write the identity (hex secret key)
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// Decrypt results sealed with --encrypt-to
+    Decrypt {
+        /// Identity file created by `keygen`
+        #[arg(short, long)]
+        identity: PathBuf,
+
+        /// File with the search output or sealed hex lines (default: stdin)
+        input: Option<PathBuf>,
+    },
+    /// Run the known-answer self-test of every backend (and GPU device)
+    SelfTest,
+    /// Rebuild a key from --shares files; prints only the private key, and
+    /// only if it derives the address recorded in the shares
+    Recover {
+        /// Share files (at least the threshold, all for the same address)
+        #[arg(required = true)]
+        shares: Vec<PathBuf>,
+    },
+}
+
 impl Config {
     /// Returns the number of workers, defaulting to CPU count
     pub fn worker_count(&self) -> usize {
@@ -59,51 +223,145 @@ impl Config {
 
     /// Validates the configuration
     pub fn validate(&self) -> Result<(), ConfigError> {
-        // Check pattern contains only valid hex characters
-        let pattern = if self.case_sensitive {
-            self.pattern.clone()
-        } else {
-            self.pattern.to_lowercase()
-        };
+        let format = &self.address_format()?;
+        let body_len = format.body_len();
 
-        if !pattern.chars().all(|c| c.is_ascii_hexdigit()) {
-            return Err(ConfigError::InvalidPattern(
-                "Pattern must contain only hex characters (0-9, a-f)".into(),
-            ));
-        }
+        let pattern = self.normalized_pattern();
+        format
+            .validate_pattern(&pattern, self.case_sensitive)
+            .map_err(ConfigError::InvalidPattern)?;
 
         if pattern.is_empty() {
             return Err(ConfigError::InvalidPattern("Pattern cannot be empty".into()));
         }
 
-        if pattern.len() > 40 {
-            return Err(ConfigError::InvalidPattern(
-                "Pattern cannot be longer than 40 characters (full address)".into(),
-            ));
+        if pattern.len() > body_len {
+            return Err(ConfigError::InvalidPattern(format!(
+                "Pattern cannot be longer than {} characters (full address)",
+                body_len
+            )));
+        }
+
+        if matches!(self.effective_pattern_type(), PatternType::Prefix | PatternType::PrefixAndSuffix) {
+            format
+                .validate_prefix(&pattern, self.case_sensitive)
+                .map_err(ConfigError::InvalidPattern)?;
         }
 
         // Validate suffix if provided
-        if let Some(ref suffix) = self.suffix {
-            let suffix_norm = if self.case_sensitive {
-                suffix.clone()
+        if let Some(suffix_norm) = self.normalized_suffix() {
+            format
+                .validate_pattern(&suffix_norm, self.case_sensitive)
+                .map_err(|e| ConfigError::InvalidPattern(format!("suffix: {}", e)))?;
+
+            if suffix_norm.is_empty() {
+                return Err(ConfigError::InvalidPattern("Suffix cannot be empty".into()));
+            }
+
+            let total_len = pattern.len() + suffix_norm.len();
+            if total_len > body_len {
+                return Err(ConfigError::InvalidPattern(format!(
+                    "Combined prefix + suffix cannot be longer than {} characters",
+                    body_len
+                )));
+            }
+        }
+
+        // Tron derives contract addresses differently; only its accounts share
+        // Ethereum's key-to-address mapping. ICAP identifies accounts.
+        if matches!(self.network, AddressFormat::Tron | AddressFormat::Icap)
+            && (self.contract_nonce.is_some() || self.safe_factory.is_some())
+        {
+            return Err(ConfigError::InvalidConfig(format!(
+                "--network {} matches account addresses only",
+                self.network
+            )));
+        }
+
+        if let Some(ref dir) = self.keystore_dir {
+            if !dir.is_dir() {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "Keystore directory {} does not exist",
+                    dir.display()
+                )));
+            }
+        }
+
+        if let Some(ref dir) = self.share_dir {
+            if !dir.is_dir() {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "Share directory {} does not exist",
+                    dir.display()
+                )));
+            }
+        }
+
+        if self.mnemonic_indices == 0 || self.mnemonic_indices > HARDENED {
+            return Err(ConfigError::InvalidConfig(format!(
+                "--mnemonic-indices must be between 1 and {}",
+                HARDENED
+            )));
+        }
+
+        if !(1..=2).contains(&self.xpub_depth) {
+            return Err(ConfigError::InvalidConfig("--xpub-depth must be 1 or 2".into()));
+        }
+
+        if self.xpub_indices == 0 || self.xpub_indices > HARDENED {
+            return Err(ConfigError::InvalidConfig(format!(
+                "--xpub-indices must be between 1 and {}",
+                HARDENED
+            )));
+        }
+
+        if self.eoa_pattern.is_some() && self.contract_nonce.is_none() && self.safe_factory.is_none() {
+            return Err(ConfigError::InvalidConfig(
+                "--eoa-pattern requires --contract-nonce or --safe-factory".into(),
+            ));
+        }
+
+        if let Some(ref factory) = self.safe_factory {
+            if parse_hex::<20>(factory).is_none() {
+                return Err(ConfigError::InvalidConfig(
+                    "Safe factory must be 20 bytes (40 hex chars)".into(),
+                ));
+            }
+            let init_code_hash = self.safe_init_code_hash.as_deref().unwrap_or_default();
+            if parse_hex::<32>(init_code_hash).is_none() {
+                return Err(ConfigError::InvalidConfig(
+                    "Safe init code hash must be 32 bytes (64 hex chars)".into(),
+                ));
+            }
+            if let Some(ref handler) = self.safe_fallback_handler {
+                if parse_hex::<20>(handler).is_none() {
+                    return Err(ConfigError::InvalidConfig(
+                        "Safe fallback handler must be 20 bytes (40 hex chars)".into(),
+                    ));
+                }
+            }
+            if parse_uint256(&self.safe_salt_nonce).is_none() {
+                return Err(ConfigError::InvalidConfig(
+                    "Safe salt nonce must be a uint256 (decimal or 0x hex)".into(),
+                ));
+            }
+        }
+
+        if let Some(ref eoa_pattern) = self.eoa_pattern {
+            let eoa_norm = if self.case_sensitive {
+                eoa_pattern.clone()
             } else {
-                suffix.to_lowercase()
+                eoa_pattern.to_lowercase()
             };
 
-            if !suffix_norm.chars().all(|c| c.is_ascii_hexdigit()) {
+            if !eoa_norm.chars().all(|c| c.is_ascii_hexdigit()) {
                 return Err(ConfigError::InvalidPattern(
-                    "Suffix must contain only hex characters (0-9, a-f)".into(),
+                    "EOA pattern must contain only hex characters (0-9, a-f)".into(),
                 ));
             }
 
-            if suffix_norm.is_empty() {
-                return Err(ConfigError::InvalidPattern("Suffix cannot be empty".into()));
-            }
-
-            let total_len = pattern.len() + suffix_norm.len();
-            if total_len > 40 {
+            if eoa_norm.is_empty() || eoa_norm.len() > 40 {
                 return Err(ConfigError::InvalidPattern(
-                    "Combined prefix + suffix cannot be longer than 40 characters".into(),
+                    "EOA pattern must be 1 to 40 characters".into(),
                 ));
             }
         }
@@ -114,9 +372,9 @@ impl Config {
     /// Returns the normalized pattern (lowercase if case insensitive)
     pub fn normalized_pattern(&self) -> String {
         if self.case_sensitive {
-            self.pattern.clone()
+            self.pattern.clone().unwrap_or_default()
         } else {
-            self.pattern.to_lowercase()
+            self.pattern.as_deref().unwrap_or_default().to_lowercase()
         }
     }
 
@@ -131,6 +389,76 @@ impl Config {
         })
     }
 
+    /// Returns the address format patterns are matched against
+    pub fn address_format(&self) -> Result<AddressFormat, ConfigError> {
+        match self.bech32_hrp {
+            Some(ref hrp) => AddressFormat::bech32(hrp).map_err(ConfigError::InvalidConfig),
+            None => Ok(self.network.clone()),
+        }
+    }
+
+    /// Returns which derived address the pattern is matched against
+    pub fn target(&self) -> Target {
+        let eoa_pattern = self
+            .eoa_pattern
+            .as_ref()
+            .map(|p| Pattern::new(p.clone(), PatternType::Prefix, self.case_sensitive));
+        if let Some(deployment) = self.safe_deployment() {
+            return Target::Safe {
+                deployment: Box::new(deployment),
+                eoa_pattern,
+            };
+        }
+        match self.contract_nonce {
+            None => Target::Eoa,
+            Some(nonce) => Target::Contract { nonce, eoa_pattern },
+        }
+    }
+
+    /// Returns the Safe deployment when matching Safe addresses (after validation)
+    pub fn safe_deployment(&self) -> Option<SafeDeployment> {
+        let factory = parse_hex(self.safe_factory.as_deref()?).expect("validated hex");
+        let init_code_hash =
+            parse_hex(self.safe_init_code_hash.as_deref().expect("validated")).expect("validated hex");
+        let fallback_handler = self
+            .safe_fallback_handler
+            .as_deref()
+            .map_or([0u8; 20], |h| parse_hex(h).expect("validated hex"));
+        let salt_nonce = parse_uint256(&self.safe_salt_nonce).expect("validated");
+        Some(SafeDeployment::new(
+            factory,
+            init_code_hash,
+            fallback_handler,
+            salt_nonce,
+        ))
+    }
+
+    /// Returns what mnemonic mode generates, when enabled
+    pub fn mnemonic_spec(&self) -> Option<MnemonicSpec> {
+        self.mnemonic
+            .map(|length| MnemonicSpec::new(length, self.mnemonic_indices))
+    }
+
+    /// Returns what xpub mode searches, when enabled
+    pub fn xpub_spec(&self) -> Option<XpubSpec> {
+        self.xpub
+            .clone()
+            .map(|xpub| XpubSpec::new(xpub, self.xpub_depth, self.xpub_indices))
+    }
+
+    /// Returns whether found keys must not be output: they come from a
+    /// deterministic seed and `--print-deterministic-keys` was not given.
+    pub fn withhold_keys(&self) -> bool {
+        #[cfg(feature = "deterministic")]
+        {
+            self.deterministic_seed.is_some() && !self.print_deterministic_keys
+        }
+        #[cfg(not(feature = "deterministic"))]
+        {
+            false
+        }
+    }
+
     /// Returns whether GPU acceleration is enabled.
     pub fn gpu_enabled(&self) -> bool {
         #[cfg(feature = "gpu")]
@@ -143,6 +471,14 @@ impl Config {
         }
     }
 
+    /// Turns GPU acceleration off, e.g. after the device failed its self-test.
+    pub fn disable_gpu(&mut self) {
+        #[cfg(feature = "gpu")]
+        {
+            self.gpu = false;
+        }
+    }
+
     /// Returns the GPU device index.
     pub fn gpu_device_index(&self) -> usize {
         #[cfg(feature = "gpu")]
@@ -177,10 +513,49 @@ impl Config {
     }
 }
 
+/// Parses exactly N bytes of hex (with or without 0x).
+fn parse_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    let h = s.strip_prefix("0x").unwrap_or(s);
+    if h.len() != N * 2 {
+        return None;
+    }
+    hex::decode(h).ok()?.try_into().ok()
+}
+
+/// Parses a uint256 given in decimal or as 0x-prefixed hex, as a big-endian word.
+fn parse_uint256(s: &str) -> Option<[u8; 32]> {
+    let mut word = [0u8; 32];
+    if let Some(h) = s.strip_prefix("0x") {
+        if h.is_empty() || h.len() > 64 {
+            return None;
+        }
+        let bytes = hex::decode(format!("{:0>64}", h)).ok()?;
+        word.copy_from_slice(&bytes);
+        return Some(word);
+    }
+    if s.is_empty() {
+        return None;
+    }
+    for c in s.chars() {
+        let mut carry = c.to_digit(10)?;
+        for byte in word.iter_mut().rev() {
+            let v = *byte as u32 * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(word)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
     #[error("Invalid pattern: {0}")]
     InvalidPattern(String),
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
 }
 
 #[cfg(test)]
@@ -189,12 +564,39 @@ mod tests {
 
     fn make_test_config(pattern: &str) -> Config {
         Config {
-            pattern: pattern.into(),
+            command: None,
+            pattern: Some(pattern.into()),
             suffix: None,
             pattern_type: PatternType::Prefix,
+            network: AddressFormat::Hex,
+            bech32_hrp: None,
+            contract_nonce: None,
+            safe_factory: None,
+            safe_init_code_hash: None,
+            safe_fallback_handler: None,
+            safe_salt_nonce: "0".into(),
+            eoa_pattern: None,
             workers: None,
             case_sensitive: false,
             count: 1,
+            mnemonic: None,
+            mnemonic_indices: 20,
+            xpub: None,
+            xpub_depth: 1,
+            xpub_indices: 65536,
+            keystore_dir: None,
+            keystore_kdf: KeystoreKdf::Scrypt,
+            password_fd: None,
+            encrypt_to: None,
+            shares: None,
+            share_dir: None,
+            share_mnemonic: false,
+            mlock: false,
+            entropy_file: None,
+            #[cfg(feature = "deterministic")]
+            deterministic_seed: None,
+            #[cfg(feature = "deterministic")]
+            print_deterministic_keys: false,
             report_interval: 5,
             #[cfg(feature = "gpu")]
             gpu: false,
@@ -216,4 +618,179 @@ mod tests {
         let config = make_test_config("xyz");
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_tron_network() {
+        let mut config = make_test_config("Doge");
+        assert!(config.validate().is_err()); // 'o' is not hex
+
+        config.network = AddressFormat::Tron;
+        assert!(config.validate().is_ok()); // 'D' can follow 'T'
+        config.pattern = Some("1xy".into());
+        assert!(config.validate().is_err()); // Tron addresses never start with "T1"
+        config.pattern_type = PatternType::Suffix;
+        assert!(config.validate().is_ok());
+        config.pattern = Some("O0".into());
+        assert!(config.validate().is_err());
+
+        config.pattern = Some("abc".into());
+        config.contract_nonce = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_filecoin_network() {
+        let mut config = make_test_config("F00");
+        config.network = AddressFormat::Filecoin;
+        assert!(config.validate().is_err()); // '0' is not base32
+        config.pattern = Some("FEE7".into());
+        assert!(config.validate().is_ok());
+        // FEVM contracts keep their Ethereum CREATE address.
+        config.contract_nonce = Some(1);
+        assert!(config.validate().is_ok());
+        config.pattern = Some("a".repeat(40));
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_icap_network() {
+        let mut config = make_test_config("KITTY");
+        config.network = AddressFormat::Icap;
+        assert!(config.validate().is_ok());
+        config.pattern = Some("k-tty".into());
+        assert!(config.validate().is_err());
+        config.pattern = Some("0".repeat(31));
+        assert!(config.validate().is_err());
+
+        config.pattern = Some("kitty".into());
+        config.contract_nonce = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_bech32_hrp() {
+        let mut config = make_test_config("Q0");
+        config.bech32_hrp = Some("evmos".into());
+        assert!(config.validate().is_ok()); // lowercased to "q0"
+        config.case_sensitive = true;
+        assert!(config.validate().is_err());
+
+        config.case_sensitive = false;
+        config.pattern = Some("b".into());
+        assert!(config.validate().is_err());
+        config.pattern = Some("q".repeat(39));
+        assert!(config.validate().is_err());
+
+        // Contracts on Ethermint chains use the Ethereum derivation.
+        config.pattern = Some("c0de".into());
+        config.contract_nonce = Some(0);
+        assert!(config.validate().is_ok());
+        config.bech32_hrp = Some("".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_keystore_dir() {
+        let mut config = make_test_config("dead");
+        config.keystore_dir = Some(std::env::temp_dir());
+        assert!(config.validate().is_ok());
+        config.keystore_dir = Some(std::env::temp_dir().join("eth-vanity-missing-keystore-dir"));
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_spec() {
+        let mut config = make_test_config("dead");
+        assert_eq!(config.mnemonic_spec(), None);
+        config.mnemonic = Some(MnemonicLength::Words24);
+        config.mnemonic_indices = 5;
+        assert!(config.validate().is_ok());
+        assert_eq!(config.mnemonic_spec().unwrap().indices(), 5);
+        config.mnemonic_indices = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_xpub_spec() {
+        let mut config = make_test_config("dead");
+        assert_eq!(config.xpub_spec(), None);
+        config.xpub = Some(
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsef\
+             D265TMg7usUDFdp6W1EGMcet8"
+                .parse()
+                .unwrap(),
+        );
+        config.xpub_depth = 2;
+        assert!(config.validate().is_ok());
+        assert_eq!(config.xpub_spec().unwrap().depth(), 2);
+        config.xpub_indices = 100;
+        assert_eq!(config.xpub_spec().unwrap().indices(), 100);
+        config.xpub_indices = 0;
+        assert!(config.validate().is_err());
+        config.xpub_indices = 100;
+        config.xpub_depth = 3;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_share_dir() {
+        let mut config = make_test_config("dead");
+        config.shares = Some("2-of-3".parse().unwrap());
+        config.share_dir = Some(std::env::temp_dir());
+        assert!(config.validate().is_ok());
+        config.share_dir = Some(std::env::temp_dir().join("eth-vanity-missing-share-dir"));
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_shares_conflict_with_encrypt_to() {
+        let dir = std::env::temp_dir();
+        let args = ["eth_vanity", "-p", "dead", "--shares", "2-of-3", "--share-dir"];
+        let config = Config::try_parse_from(args.iter().copied().chain([dir.to_str().unwrap()]));
+        assert!(config.is_ok());
+
+        let recipient = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let config = Config::try_parse_from(
+            args.iter()
+                .copied()
+                .chain([dir.to_str().unwrap(), "--encrypt-to", recipient]),
+        );
+        assert_eq!(config.unwrap_err().kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_contract_target() {
+        let mut config = make_test_config("dead");
+        assert!(config.target().is_eoa());
+
+        config.contract_nonce = Some(0);
+        config.eoa_pattern = Some("BEEF".into());
+        assert!(config.validate().is_ok());
+        match config.target() {
+            Target::Contract { nonce, eoa_pattern } => {
+                assert_eq!(nonce, 0);
+                assert_eq!(eoa_pattern.unwrap().pattern(), "beef");
+            }
+            _ => panic!("expected contract target"),
+        }
+
+        config.eoa_pattern = Some("xyz".into());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_safe_target() {
+        let mut config = make_test_config("dead");
+        config.eoa_pattern = Some("00".into());
+        assert!(config.validate().is_err());
+
+        config.safe_factory = Some("0x4e1DCf7AD4e460CfD30791CCC4F9c8a4f820ec67".into());
+        config.safe_init_code_hash = Some("11".repeat(32));
+        config.safe_salt_nonce = "42".into();
+        assert!(config.validate().is_ok());
+        assert!(matches!(config.target(), Target::Safe { eoa_pattern: Some(_), .. }));
+
+        config.safe_salt_nonce = "0x".into();
+        assert!(config.validate().is_err());
+    }
 }
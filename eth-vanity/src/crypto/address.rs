@@ -2,6 +2,12 @@
 
 use std::fmt;
 
+/// Base-36 digits of ICAP (IBAN) strings.
+const ICAP_ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Length of a direct ICAP BBAN (30 base-36 digits, at most 155 bits).
+pub const ICAP_BBAN_LEN: usize = 30;
+
 /// An Ethereum address (20 bytes).
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Address([u8; 20]);
@@ -62,6 +68,107 @@ impl Address {
 
         checksum
     }
+
+    /// Returns true if the address fits the 155 bits of a direct ICAP
+    /// (the top five bits are zero).
+    #[inline]
+    pub fn is_icap_eligible(&self) -> bool {
+        self.0[0] < 0x08
+    }
+
+    /// Returns the direct ICAP encoding (`XE` + mod-97 check digits + 30
+    /// base-36 digits), or `None` if the address is 2^155 or above.
+    pub fn to_icap(&self) -> Option<String> {
+        if !self.is_icap_eligible() {
+            return None;
+        }
+        // Base-36 digits, least significant first.
+        let mut digits = [0u8; ICAP_BBAN_LEN];
+        for &byte in &self.0 {
+            let mut carry = byte as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                *digit = (carry % 36) as u8;
+                carry /= 36;
+            }
+        }
+        let bban: String = digits
+            .iter()
+            .rev()
+            .map(|&d| ICAP_ALPHABET[d as usize] as char)
+            .collect();
+        Some(format!("XE{:02}{}", icap_check_digits(&bban), bban))
+    }
+
+    /// Parses a direct ICAP (`XE` + check digits + 30-digit BBAN). Spaces
+    /// and an `iban:` prefix are ignored; letters may be in either case.
+    pub fn from_icap(icap: &str) -> Result<Self, IcapError> {
+        let icap = icap.strip_prefix("iban:").unwrap_or(icap);
+        let icap: String = icap
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        if icap.len() != 4 + ICAP_BBAN_LEN {
+            return Err(IcapError::InvalidLength(icap.len()));
+        }
+        if let Some(c) = icap.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(IcapError::InvalidCharacter(c));
+        }
+        if !icap.starts_with("XE") {
+            return Err(IcapError::InvalidCountry(icap[..2].to_string()));
+        }
+        let (check, bban) = (&icap[2..4], &icap[4..]);
+        if check.parse::<u32>().ok() != Some(icap_check_digits(bban)) {
+            return Err(IcapError::ChecksumMismatch);
+        }
+
+        // Big-endian base-256 digits of the BBAN value.
+        let mut bytes = [0u8; 21];
+        for c in bban.bytes() {
+            let mut carry = ICAP_ALPHABET.iter().position(|&a| a == c).expect("alphanumeric") as u32;
+            for byte in bytes.iter_mut().rev() {
+                carry += *byte as u32 * 36;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+        }
+        let address = Self(bytes[1..].try_into().expect("20 bytes"));
+        if bytes[0] != 0 || !address.is_icap_eligible() {
+            return Err(IcapError::OutOfRange);
+        }
+        Ok(address)
+    }
+}
+
+/// ISO 13616 check digits of an `XE` IBAN with the given BBAN.
+fn icap_check_digits(bban: &str) -> u32 {
+    // Move the country code and "00" to the end, read letters as 10..35,
+    // and reduce mod 97 digit by digit.
+    let rem = bban.bytes().chain(*b"XE00").fold(0u32, |rem, c| {
+        let value = ICAP_ALPHABET.iter().position(|&a| a == c).unwrap_or(0) as u32;
+        if value < 10 {
+            (rem * 10 + value) % 97
+        } else {
+            (rem * 100 + value) % 97
+        }
+    });
+    98 - rem
+}
+
+/// Errors decoding an ICAP address.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IcapError {
+    #[error("Direct ICAP must be 34 characters, got {0}")]
+    InvalidLength(usize),
+    #[error("ICAP country code must be XE, got {0}")]
+    InvalidCountry(String),
+    #[error("Invalid ICAP character: {0}")]
+    InvalidCharacter(char),
+    #[error("ICAP check digits do not match")]
+    ChecksumMismatch,
+    #[error("ICAP value does not fit in 155 bits")]
+    OutOfRange,
 }
 
 impl fmt::Debug for Address {
@@ -91,6 +198,26 @@ mod tests {
         assert_eq!(addr.to_checksum(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
     }
 
+    #[test]
+    fn test_icap_round_trip() {
+        // web3.js Iban example
+        let bytes = hex::decode("00c5496aee77c1ba1f0854206a26dda82a81d6d8")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let addr = Address::from_bytes(bytes);
+        assert_eq!(addr.to_icap().unwrap(), "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS");
+        assert_eq!(Address::from_icap("XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS"), Ok(addr));
+        assert_eq!(Address::from_icap("iban:xe73 38o0 73ky gtww zn0f 2wz0 r8px 5zpp zs"), Ok(addr));
+
+        assert_eq!(
+            Address::from_icap("XE7438O073KYGTWWZN0F2WZ0R8PX5ZPPZS"),
+            Err(IcapError::ChecksumMismatch)
+        );
+        assert!(matches!(Address::from_icap("DE7338O073"), Err(IcapError::InvalidLength(10))));
+        assert_eq!(Address::from_bytes([0xff; 20]).to_icap(), None);
+    }
+
     #[test]
     fn test_hex_output() {
         let bytes = [0u8; 20];
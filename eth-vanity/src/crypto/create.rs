@@ -0,0 +1,80 @@
+//! Contract address derivation (CREATE).
+
+use tiny_keccak::{Hasher, Keccak};
+
+use super::Address;
+
+/// Computes the address of the contract deployed with CREATE by `deployer`
+/// when its account nonce is `nonce`.
+///
+/// Address = keccak256(rlp([deployer, nonce]))[12..32].
+pub fn contract_address(deployer: &Address, nonce: u64) -> Address {
+    // rlp([deployer, nonce]): list header, 0x94 || deployer (20 bytes), then
+    // the nonce as 0x80 (zero), a single byte (< 0x80), or 0x80+len || big-endian.
+    let mut rlp = [0u8; 32];
+    rlp[1] = 0x94;
+    rlp[2..22].copy_from_slice(deployer.as_bytes());
+    let mut len = 22;
+    match nonce {
+        0 => {
+            rlp[len] = 0x80;
+            len += 1;
+        }
+        1..=0x7f => {
+            rlp[len] = nonce as u8;
+            len += 1;
+        }
+        _ => {
+            let bytes = nonce.to_be_bytes();
+            let skip = nonce.leading_zeros() as usize / 8;
+            let n = 8 - skip;
+            rlp[len] = 0x80 + n as u8;
+            rlp[len + 1..len + 1 + n].copy_from_slice(&bytes[skip..]);
+            len += 1 + n;
+        }
+    }
+    rlp[0] = 0xc0 + (len - 1) as u8;
+
+    let mut hasher = Keccak::v256();
+    hasher.update(&rlp[..len]);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+
+    let mut address_bytes = [0u8; 20];
+    address_bytes.copy_from_slice(&hash[12..]);
+    Address::from_bytes(address_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract_hex(deployer: &str, nonce: u64) -> String {
+        let bytes: [u8; 20] = hex::decode(deployer).unwrap().try_into().unwrap();
+        contract_address(&Address::from_bytes(bytes), nonce).to_hex()
+    }
+
+    #[test]
+    fn test_contract_address_vectors() {
+        let deployer = "6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+        assert_eq!(contract_hex(deployer, 0), "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d");
+        assert_eq!(contract_hex(deployer, 1), "343c43a37d37dff08ae8c4a11544c718abb4fcf8");
+        assert_eq!(contract_hex(deployer, 2), "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91");
+        assert_eq!(contract_hex(deployer, 3), "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c");
+    }
+
+    #[test]
+    fn test_contract_address_multibyte_nonce() {
+        let deployer = Address::from_bytes([0x11; 20]);
+        let mut rlp = vec![0xd8, 0x94];
+        rlp.extend_from_slice(deployer.as_bytes());
+        rlp.extend_from_slice(&[0x82, 0x01, 0x00]);
+
+        let mut hasher = Keccak::v256();
+        hasher.update(&rlp);
+        let mut hash = [0u8; 32];
+        hasher.finalize(&mut hash);
+
+        assert_eq!(contract_address(&deployer, 256).as_bytes()[..], hash[12..]);
+    }
+}
@@ -0,0 +1,197 @@
+//! ECIES over secp256k1 for sealing results to an offline recipient.
+//!
+//! sealed = 0x01 || ephemeral pubkey (33, compressed) || iv (16)
+//!          || AES-256-CTR(enc_key, iv, plaintext) || HMAC-SHA256(mac_key, ..)
+//! shared = sha256(compressed ECDH point)
+//! enc_key || mac_key = HKDF-SHA256(ikm = shared, salt = ephemeral pubkey)
+//! The MAC covers everything before it, so the version byte and the
+//! ephemeral key are authenticated too.
+
+use std::fmt;
+use std::str::FromStr;
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use secp256k1::ecdh::SharedSecret;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+type Aes256Ctr = ctr::Ctr128BE<aes::Aes256>;
+
+/// Format version of sealed messages.
+const VERSION: u8 = 1;
+
+/// HKDF info string, so the keys are bound to this scheme.
+const HKDF_INFO: &[u8] = b"eth-vanity ecies v1";
+
+const HEADER_LEN: usize = 1 + 33 + 16;
+const TAG_LEN: usize = 32;
+
+/// Public key results are sealed to (33- or 65-byte SEC1 hex).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recipient(PublicKey);
+
+impl Recipient {
+    /// Encrypts `plaintext` so that only the matching identity can read it.
+    pub fn seal(&self, plaintext: &[u8]) -> Vec<u8> {
+        let secp = Secp256k1::new();
+        let (ephemeral_secret, ephemeral_public) = secp.generate_keypair(&mut rand::thread_rng());
+        let mut iv = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut iv);
+
+        let ephemeral = ephemeral_public.serialize();
+        let shared = SharedSecret::new(&self.0, &ephemeral_secret);
+        let (enc_key, mac_key) = derive_keys(&shared, &ephemeral);
+
+        let mut sealed = Vec::with_capacity(HEADER_LEN + plaintext.len() + TAG_LEN);
+        sealed.push(VERSION);
+        sealed.extend_from_slice(&ephemeral);
+        sealed.extend_from_slice(&iv);
+        let start = sealed.len();
+        sealed.extend_from_slice(plaintext);
+        Aes256Ctr::new(enc_key.as_ref().into(), &iv.into()).apply_keystream(&mut sealed[start..]);
+
+        let tag = mac(&mac_key, &sealed);
+        sealed.extend_from_slice(&tag);
+        sealed
+    }
+}
+
+impl FromStr for Recipient {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))
+            .map_err(|_| "Recipient must be a hex secp256k1 public key".to_string())?;
+        PublicKey::from_slice(&bytes)
+            .map(Recipient)
+            .map_err(|_| "Recipient is not a valid secp256k1 public key".to_string())
+    }
+}
+
+impl fmt::Display for Recipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0.serialize()))
+    }
+}
+
+/// Secret key held by the recipient on the offline machine.
+pub struct Identity(SecretKey);
+
+impl Identity {
+    /// Generates a new random identity.
+    pub fn generate() -> Self {
+        Identity(SecretKey::new(&mut rand::thread_rng()))
+    }
+
+    /// Returns the public key to pass to `--encrypt-to`.
+    pub fn recipient(&self) -> Recipient {
+        Recipient(PublicKey::from_secret_key(&Secp256k1::new(), &self.0))
+    }
+
+    /// Returns the secret key as hex, for the identity file.
+    pub fn to_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.0.secret_bytes()))
+    }
+
+    /// Decrypts a message sealed to this identity's recipient.
+    pub fn open(&self, sealed: &[u8]) -> Result<Zeroizing<Vec<u8>>, EciesError> {
+        if sealed.len() < HEADER_LEN + TAG_LEN {
+            return Err(EciesError::Truncated);
+        }
+        if sealed[0] != VERSION {
+            return Err(EciesError::UnsupportedVersion(sealed[0]));
+        }
+        let ephemeral = PublicKey::from_slice(&sealed[1..34]).map_err(|_| EciesError::InvalidKey)?;
+        let shared = SharedSecret::new(&ephemeral, &self.0);
+        let (enc_key, mac_key) = derive_keys(&shared, &sealed[1..34]);
+
+        let (body, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+        let mut verifier =
+            <Hmac<Sha256> as Mac>::new_from_slice(mac_key.as_slice()).expect("any key length");
+        verifier.update(body);
+        verifier.verify_slice(tag).map_err(|_| EciesError::AuthenticationFailed)?;
+
+        let iv: [u8; 16] = body[34..HEADER_LEN].try_into().expect("16 bytes");
+        let mut plaintext = Zeroizing::new(body[HEADER_LEN..].to_vec());
+        Aes256Ctr::new(enc_key.as_ref().into(), &iv.into()).apply_keystream(&mut plaintext);
+        Ok(plaintext)
+    }
+}
+
+impl Drop for Identity {
+    fn drop(&mut self) {
+        self.0.non_secure_erase();
+    }
+}
+
+impl FromStr for Identity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))
+            .map_err(|_| "Identity must be a hex secp256k1 secret key".to_string())?;
+        SecretKey::from_slice(&bytes)
+            .map(Identity)
+            .map_err(|_| "Identity is not a valid secp256k1 secret key".to_string())
+    }
+}
+
+type Key = Zeroizing<[u8; 32]>;
+
+fn derive_keys(shared: &SharedSecret, ephemeral: &[u8]) -> (Key, Key) {
+    let hkdf = Hkdf::<Sha256>::new(Some(ephemeral), &shared.secret_bytes());
+    let mut okm = Zeroizing::new([0u8; 64]);
+    hkdf.expand(HKDF_INFO, &mut *okm).expect("64 bytes is a valid length");
+    let mut enc_key = Zeroizing::new([0u8; 32]);
+    let mut mac_key = Zeroizing::new([0u8; 32]);
+    enc_key.copy_from_slice(&okm[..32]);
+    mac_key.copy_from_slice(&okm[32..]);
+    (enc_key, mac_key)
+}
+
+fn mac(key: &[u8; 32], data: &[u8]) -> [u8; 32] {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// Errors opening a sealed message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EciesError {
+    #[error("Sealed message is truncated")]
+    Truncated,
+    #[error("Unsupported sealed message version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Sealed message has an invalid ephemeral key")]
+    InvalidKey,
+    #[error("Sealed message failed authentication (wrong identity or corrupted)")]
+    AuthenticationFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_round_trip() {
+        let identity = Identity::generate();
+        let recipient: Recipient = identity.recipient().to_string().parse().unwrap();
+        let sealed = recipient.seal(b"private key");
+        assert_eq!(sealed.len(), HEADER_LEN + 11 + TAG_LEN);
+        assert_eq!(identity.open(&sealed).unwrap().as_slice(), b"private key");
+
+        let mut tampered = sealed.clone();
+        tampered[HEADER_LEN] ^= 1;
+        assert_eq!(identity.open(&tampered), Err(EciesError::AuthenticationFailed));
+        assert_eq!(
+            Identity::generate().open(&sealed),
+            Err(EciesError::AuthenticationFailed)
+        );
+        assert_eq!(identity.open(&sealed[..40]), Err(EciesError::Truncated));
+    }
+}
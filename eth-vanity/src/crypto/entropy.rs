@@ -0,0 +1,238 @@
+//! Sources of candidate private keys.
+//!
+//! os:            secret = OS-seeded CSPRNG output (`rand::thread_rng`)
+//! mixed:         secret = sha256(DOMAIN || 32 CSPRNG bytes || sha256(extra entropy))
+//! deterministic: secret = sha256(DOMAIN || sha256(seed) || worker id || counter)
+//! Candidates that are not valid secp256k1 scalars (zero, or at least the
+//! curve order) are drawn again. The deterministic source exists only for
+//! test fixtures, behind the `deterministic` feature.
+
+use std::fmt;
+
+use rand::RngCore;
+use secp256k1::SecretKey;
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::MnemonicOrigin;
+
+const MIX_DOMAIN: &[u8] = b"eth-vanity entropy mix v1";
+#[cfg(feature = "deterministic")]
+const DETERMINISTIC_DOMAIN: &[u8] = b"eth-vanity deterministic v1";
+
+/// Produces the candidate private keys a worker tests.
+pub trait KeySource: Send {
+    /// Fills `secret` with the next 32 candidate bytes.
+    fn fill_secret(&mut self, secret: &mut [u8; 32]);
+
+    /// Draws candidates until one is a valid private key.
+    fn next_secret_key(&mut self) -> SecretKey {
+        let mut secret = Zeroizing::new([0u8; 32]);
+        loop {
+            self.fill_secret(&mut secret);
+            if let Ok(key) = SecretKey::from_slice(&*secret) {
+                return key;
+            }
+        }
+    }
+
+    /// The phrase and path of the last key, for sources that derive keys
+    /// from a BIP39 mnemonic.
+    fn mnemonic_origin(&self) -> Option<MnemonicOrigin> {
+        None
+    }
+}
+
+/// The operating system's RNG (the default).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsSource;
+
+impl KeySource for OsSource {
+    #[inline]
+    fn fill_secret(&mut self, secret: &mut [u8; 32]) {
+        rand::thread_rng().fill_bytes(secret);
+    }
+}
+
+/// OS randomness hashed together with operator-supplied entropy, so a key
+/// is unpredictable as long as either input is.
+pub struct MixedSource {
+    extra: Zeroizing<[u8; 32]>,
+}
+
+impl KeySource for MixedSource {
+    fn fill_secret(&mut self, secret: &mut [u8; 32]) {
+        let mut os = Zeroizing::new([0u8; 32]);
+        rand::thread_rng().fill_bytes(&mut *os);
+        let mut hasher = Sha256::new();
+        hasher.update(MIX_DOMAIN);
+        hasher.update(os.as_slice());
+        hasher.update(self.extra.as_slice());
+        secret.copy_from_slice(&hasher.finalize());
+    }
+}
+
+/// Reproducible keys from a seed. Anyone who knows the seed knows the keys.
+#[cfg(feature = "deterministic")]
+pub struct DeterministicSource {
+    seed: Zeroizing<[u8; 32]>,
+    worker_id: u64,
+    counter: u64,
+}
+
+#[cfg(feature = "deterministic")]
+impl KeySource for DeterministicSource {
+    fn fill_secret(&mut self, secret: &mut [u8; 32]) {
+        let mut hasher = Sha256::new();
+        hasher.update(DETERMINISTIC_DOMAIN);
+        hasher.update(self.seed.as_slice());
+        hasher.update(self.worker_id.to_le_bytes());
+        hasher.update(self.counter.to_le_bytes());
+        secret.copy_from_slice(&hasher.finalize());
+        self.counter += 1;
+    }
+}
+
+/// Where workers draw keys from. Each worker builds its own [`KeySource`]
+/// from this with [`Entropy::source`].
+#[derive(Clone, Default)]
+pub enum Entropy {
+    /// OS randomness only
+    #[default]
+    Os,
+    /// OS randomness mixed with the digest of extra entropy
+    Mixed(Zeroizing<[u8; 32]>),
+    /// Keys derived from the digest of a seed (test fixtures only)
+    #[cfg(feature = "deterministic")]
+    Deterministic(Zeroizing<[u8; 32]>),
+}
+
+impl Entropy {
+    /// Mixes `extra` (dice rolls, hardware RNG output, ...) into OS randomness.
+    pub fn mixed(extra: &[u8]) -> Self {
+        Entropy::Mixed(digest(extra))
+    }
+
+    /// Derives every key from `seed`. Keys are only reproducible per worker,
+    /// so fixtures should run a single worker.
+    #[cfg(feature = "deterministic")]
+    pub fn deterministic(seed: &[u8]) -> Self {
+        Entropy::Deterministic(digest(seed))
+    }
+
+    /// Returns true if keys are derived from a seed rather than randomness.
+    pub fn is_deterministic(&self) -> bool {
+        #[cfg(feature = "deterministic")]
+        if let Entropy::Deterministic(_) = self {
+            return true;
+        }
+        false
+    }
+
+    /// Builds the key source for worker `worker_id`.
+    #[cfg_attr(not(feature = "deterministic"), allow(unused_variables))]
+    pub fn source(&self, worker_id: usize) -> Box<dyn KeySource> {
+        match self {
+            Entropy::Os => Box::new(OsSource),
+            Entropy::Mixed(extra) => Box::new(MixedSource {
+                extra: extra.clone(),
+            }),
+            #[cfg(feature = "deterministic")]
+            Entropy::Deterministic(seed) => Box::new(DeterministicSource {
+                seed: seed.clone(),
+                worker_id: worker_id as u64,
+                counter: 0,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Entropy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entropy::Os => write!(f, "OS RNG"),
+            Entropy::Mixed(_) => write!(f, "OS RNG mixed with user entropy"),
+            #[cfg(feature = "deterministic")]
+            Entropy::Deterministic(_) => {
+                write!(f, "deterministic seed (TEST ONLY, keys are not secret)")
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Entropy {
+    /// Never shows the entropy digest or seed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entropy::Os => write!(f, "Entropy::Os"),
+            Entropy::Mixed(_) => write!(f, "Entropy::Mixed(<redacted>)"),
+            #[cfg(feature = "deterministic")]
+            Entropy::Deterministic(_) => write!(f, "Entropy::Deterministic(<redacted>)"),
+        }
+    }
+}
+
+fn digest(data: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut out = Zeroizing::new([0u8; 32]);
+    let mut hash = Sha256::digest(data);
+    out.copy_from_slice(&hash);
+    hash.as_mut_slice().zeroize();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Yields an invalid scalar (the curve order) first, then ones.
+    struct OrderThenOnes(bool);
+
+    impl KeySource for OrderThenOnes {
+        fn fill_secret(&mut self, secret: &mut [u8; 32]) {
+            if self.0 {
+                *secret = [1; 32];
+            } else {
+                secret.copy_from_slice(&secp256k1::constants::CURVE_ORDER);
+                self.0 = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_candidates_are_redrawn() {
+        let key = OrderThenOnes(false).next_secret_key();
+        assert_eq!(key.secret_bytes(), [1; 32]);
+    }
+
+    #[test]
+    fn test_mixed_source() {
+        let entropy = Entropy::mixed(b"3 1 4 1 5 9 2 6");
+        assert!(!entropy.is_deterministic());
+        assert_eq!(format!("{:?}", entropy), "Entropy::Mixed(<redacted>)");
+
+        let mut source = entropy.source(0);
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        source.fill_secret(&mut a);
+        source.fill_secret(&mut b);
+        assert_ne!(a, b);
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn test_deterministic_source() {
+        let entropy = Entropy::deterministic(b"fixture");
+        assert!(entropy.is_deterministic());
+
+        let draw = |worker| {
+            let mut source = entropy.source(worker);
+            let mut secrets = [[0u8; 32]; 2];
+            for secret in &mut secrets {
+                source.fill_secret(secret);
+            }
+            secrets
+        };
+        assert_eq!(draw(0), draw(0));
+        assert_ne!(draw(0)[0], draw(0)[1]);
+        assert_ne!(draw(0), draw(1));
+    }
+}
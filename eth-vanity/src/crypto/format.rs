@@ -0,0 +1,506 @@
+//! Display formats of the 20-byte address.
+//!
+//! Several chains reuse Ethereum's secp256k1 keys and Keccak-derived address
+//! bytes but show them differently. Patterns are matched against the
+//! variable part of the chosen representation (the characters after any
+//! fixed prefix such as `0x`, Tron's `T`, a bech32 `evmos1` or Filecoin's
+//! `f410f`). ICAP patterns skip the `XE` and the two check digits.
+
+use std::fmt;
+use std::str::FromStr;
+
+use blake2::digest::{Update, VariableOutput};
+use blake2::Blake2bVar;
+use sha2::{Digest, Sha256};
+
+use super::address::ICAP_BBAN_LEN;
+use super::Address;
+
+/// Bitcoin-style Base58 alphabet (no 0, O, I, l).
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Version byte of Tron mainnet addresses.
+const TRON_PREFIX: u8 = 0x41;
+
+/// Characters that can follow Tron's leading `T` (0x41 fixes the top digits).
+const TRON_FIRST_SYMBOLS: &str = "9ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// Bech32 data alphabet (BIP-173).
+const BECH32_ALPHABET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Bech32 data characters of a 20-byte address (160 bits / 5).
+const BECH32_ADDRESS_CHARS: usize = 32;
+
+/// Bech32 checksum length in characters.
+const BECH32_CHECKSUM_CHARS: usize = 6;
+
+/// RFC 4648 base32 alphabet, lowercase as Filecoin prints it.
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Filecoin delegated (f4) address prefix for the Ethereum address manager
+/// (actor ID 10): `f` + protocol `4` + `10` + `f`.
+const FILECOIN_PREFIX: &str = "f410f";
+
+/// Protocol byte and LEB128 actor ID covered by the f410 checksum.
+const FILECOIN_CHECKSUM_HEADER: [u8; 2] = [0x04, 0x0a];
+
+/// How an address is rendered and matched.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AddressFormat {
+    /// Ethereum hex (`0x` + 40 hex chars, EIP-55 casing)
+    #[default]
+    Hex,
+    /// Tron Base58Check of `0x41 || address` (`T` + 33 chars)
+    Tron,
+    /// Bech32 with the given human-readable part, as on Ethermint chains
+    /// (`evmos1` + 32 data chars + 6 checksum chars)
+    Bech32 { hrp: String },
+    /// Filecoin delegated f410 address: base32 of the address and its
+    /// 4-byte blake2b checksum (`f410f` + 39 chars)
+    Filecoin,
+    /// Direct ICAP (`XE` + check digits + 30 base-36 digits); only
+    /// addresses below 2^155 have one
+    Icap,
+}
+
+impl AddressFormat {
+    /// Returns the full address string in this format.
+    pub fn encode(&self, address: &Address) -> String {
+        match self {
+            AddressFormat::Hex => address.to_checksum(),
+            AddressFormat::Tron => {
+                let mut payload = [0u8; 25];
+                payload[0] = TRON_PREFIX;
+                payload[1..21].copy_from_slice(address.as_bytes());
+                let checksum = Sha256::digest(Sha256::digest(&payload[..21]));
+                payload[21..].copy_from_slice(&checksum[..4]);
+                base58_encode(&payload)
+            }
+            AddressFormat::Bech32 { hrp } => {
+                format!("{}1{}", hrp, bech32_data(hrp, address.as_bytes()))
+            }
+            AddressFormat::Filecoin => {
+                format!("{}{}", FILECOIN_PREFIX, filecoin_data(address.as_bytes()))
+            }
+            // Callers only encode addresses that matched, which are eligible.
+            AddressFormat::Icap => address.to_icap().unwrap_or_default(),
+        }
+    }
+
+    /// Returns the characters patterns are matched against: the encoded
+    /// address without its fixed prefix, lowercased unless case-sensitive.
+    /// `None` if the address has no representation in this format.
+    #[inline]
+    pub fn match_body(&self, address: &Address, case_sensitive: bool) -> Option<String> {
+        let body = match self {
+            // Hex bodies are lowercase, with or without -c (as on the GPU).
+            AddressFormat::Hex => address.to_hex(),
+            // Bech32 is lowercase only.
+            AddressFormat::Bech32 { hrp } => bech32_data(hrp, address.as_bytes()),
+            AddressFormat::Filecoin => filecoin_data(address.as_bytes()),
+            AddressFormat::Tron => self.encode(address).split_off(1),
+            // Skip "XE" and the check digits.
+            AddressFormat::Icap => address.to_icap()?.split_off(4),
+        };
+        // Base58 and base36 are mixed- and uppercase; fold them unless
+        // matching case-sensitively.
+        if case_sensitive || !matches!(self, AddressFormat::Tron | AddressFormat::Icap) {
+            return Some(body);
+        }
+        Some(body.to_ascii_lowercase())
+    }
+
+    /// Name of the network the format belongs to, for display.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AddressFormat::Hex => "Ethereum",
+            AddressFormat::Tron => "Tron",
+            AddressFormat::Bech32 { .. } => "Bech32",
+            AddressFormat::Filecoin => "Filecoin",
+            AddressFormat::Icap => "ICAP",
+        }
+    }
+
+    /// Characters every address starts with.
+    pub fn fixed_prefix(&self) -> String {
+        match self {
+            AddressFormat::Hex => "0x".into(),
+            AddressFormat::Tron => "T".into(),
+            AddressFormat::Bech32 { hrp } => format!("{}1", hrp),
+            AddressFormat::Filecoin => FILECOIN_PREFIX.into(),
+            AddressFormat::Icap => "XE".into(),
+        }
+    }
+
+    /// Number of characters after the fixed prefix.
+    pub fn body_len(&self) -> usize {
+        match self {
+            AddressFormat::Hex => 40,
+            AddressFormat::Tron => 33,
+            AddressFormat::Bech32 { .. } => BECH32_ADDRESS_CHARS + BECH32_CHECKSUM_CHARS,
+            // 24 bytes = 192 bits, unpadded
+            AddressFormat::Filecoin => 39,
+            AddressFormat::Icap => ICAP_BBAN_LEN,
+        }
+    }
+
+    /// Expected attempts for an address to be representable at all
+    /// (1 in 32 addresses is below 2^155).
+    pub fn eligibility_odds(&self) -> u64 {
+        match self {
+            AddressFormat::Icap => 32,
+            _ => 1,
+        }
+    }
+
+    /// Returns true for the plain hex format (the only one the GPU kernel matches).
+    pub fn is_hex(&self) -> bool {
+        matches!(self, AddressFormat::Hex)
+    }
+
+    /// Expected attempts for one pattern character to match.
+    pub fn symbol_odds(&self, c: char, case_sensitive: bool) -> u64 {
+        match self {
+            AddressFormat::Hex => 16,
+            AddressFormat::Tron => 58 / symbol_hits(BASE58_ALPHABET, c, case_sensitive).max(1),
+            AddressFormat::Bech32 { .. } | AddressFormat::Filecoin => 32,
+            AddressFormat::Icap => 36,
+        }
+    }
+
+    /// Expected attempts for the first character after the fixed prefix,
+    /// which is not uniformly distributed in Base58 formats.
+    pub fn first_symbol_odds(&self, c: char, case_sensitive: bool) -> u64 {
+        match self {
+            AddressFormat::Hex
+            | AddressFormat::Bech32 { .. }
+            | AddressFormat::Filecoin
+            | AddressFormat::Icap => self.symbol_odds(c, case_sensitive),
+            AddressFormat::Tron => {
+                let first = TRON_FIRST_SYMBOLS.as_bytes();
+                first.len() as u64 / symbol_hits(first, c, case_sensitive).max(1)
+            }
+        }
+    }
+
+    /// Checks that a prefix can start an address body in this format.
+    pub fn validate_prefix(&self, prefix: &str, case_sensitive: bool) -> Result<(), String> {
+        let first = match (self, prefix.chars().next()) {
+            (AddressFormat::Tron, Some(c)) => c,
+            _ => return Ok(()),
+        };
+        if symbol_hits(TRON_FIRST_SYMBOLS.as_bytes(), first, case_sensitive) == 0 {
+            return Err(format!(
+                "tron addresses start with T followed by one of {}",
+                TRON_FIRST_SYMBOLS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that `pattern` only uses characters this format can produce.
+    pub fn validate_pattern(&self, pattern: &str, case_sensitive: bool) -> Result<(), String> {
+        let valid = |c: char| match self {
+            AddressFormat::Hex => c.is_ascii_hexdigit(),
+            AddressFormat::Tron => symbol_hits(BASE58_ALPHABET, c, case_sensitive) > 0,
+            AddressFormat::Bech32 { .. } => BECH32_ALPHABET.contains(&(c as u8)) && c.is_ascii(),
+            AddressFormat::Filecoin => BASE32_ALPHABET.contains(&(c as u8)) && c.is_ascii(),
+            // ICAP is uppercase; case-insensitive patterns are lowercased.
+            AddressFormat::Icap if case_sensitive => c.is_ascii_digit() || c.is_ascii_uppercase(),
+            AddressFormat::Icap => c.is_ascii_alphanumeric(),
+        };
+        match pattern.chars().find(|&c| !valid(c)) {
+            None => Ok(()),
+            Some(c) => Err(format!("'{}' cannot appear in a {} address ({})", c, self, self.charset())),
+        }
+    }
+
+    /// Human-readable character set of the format.
+    fn charset(&self) -> &'static str {
+        match self {
+            AddressFormat::Hex => "hex characters only: 0-9, a-f",
+            AddressFormat::Tron => "Base58: no 0, O, I or l",
+            AddressFormat::Bech32 { .. } => "bech32: lowercase, no 1, b, i or o",
+            AddressFormat::Filecoin => "base32: lowercase a-z and 2-7",
+            AddressFormat::Icap => "base36: 0-9, A-Z",
+        }
+    }
+
+    /// Bech32 format with `hrp`, which must be 1 to 83 printable ASCII
+    /// characters in one case (stored lowercase).
+    pub fn bech32(hrp: &str) -> Result<Self, String> {
+        let valid = !hrp.is_empty()
+            && hrp.len() <= 83
+            && hrp.bytes().all(|b| (33..=126).contains(&b))
+            && (hrp == hrp.to_lowercase() || hrp == hrp.to_uppercase());
+        if !valid {
+            return Err(format!("Invalid bech32 human-readable part: {}", hrp));
+        }
+        Ok(AddressFormat::Bech32 {
+            hrp: hrp.to_lowercase(),
+        })
+    }
+}
+
+impl FromStr for AddressFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "eth" | "ethereum" | "evm" | "hex" => Ok(AddressFormat::Hex),
+            "tron" | "trx" => Ok(AddressFormat::Tron),
+            "filecoin" | "fil" | "fevm" => Ok(AddressFormat::Filecoin),
+            "icap" | "iban" => Ok(AddressFormat::Icap),
+            _ => Err(format!("Unknown network: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for AddressFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressFormat::Hex => write!(f, "ethereum"),
+            AddressFormat::Tron => write!(f, "tron"),
+            AddressFormat::Bech32 { hrp } => write!(f, "bech32 ({})", hrp),
+            AddressFormat::Filecoin => write!(f, "filecoin"),
+            AddressFormat::Icap => write!(f, "icap"),
+        }
+    }
+}
+
+/// Bech32 data part of `bytes`: the bytes regrouped into 5-bit values,
+/// followed by the BIP-173 checksum over `hrp` and the data.
+fn bech32_data(hrp: &str, bytes: &[u8; 20]) -> String {
+    let mut values = [0u8; BECH32_ADDRESS_CHARS + BECH32_CHECKSUM_CHARS];
+    // 160 bits split evenly into 32 groups; no padding.
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    let mut out = 0;
+    for &byte in bytes {
+        acc = (acc << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            values[out] = ((acc >> bits) & 31) as u8;
+            out += 1;
+        }
+    }
+
+    let mut checksum = bech32_hrp_polymod(hrp);
+    for &v in &values[..BECH32_ADDRESS_CHARS] {
+        checksum = bech32_polymod_step(checksum, v);
+    }
+    for _ in 0..BECH32_CHECKSUM_CHARS {
+        checksum = bech32_polymod_step(checksum, 0);
+    }
+    checksum ^= 1;
+    for (i, v) in values[BECH32_ADDRESS_CHARS..].iter_mut().enumerate() {
+        *v = ((checksum >> (5 * (5 - i))) & 31) as u8;
+    }
+
+    values.iter().map(|&v| BECH32_ALPHABET[v as usize] as char).collect()
+}
+
+/// Base32 part of an f410 address: the address followed by
+/// blake2b-32(protocol || actor ID || address), lowercase and unpadded.
+fn filecoin_data(bytes: &[u8; 20]) -> String {
+    let mut payload = [0u8; 24];
+    payload[..20].copy_from_slice(bytes);
+    let mut hasher = Blake2bVar::new(4).expect("valid blake2b output size");
+    hasher.update(&FILECOIN_CHECKSUM_HEADER);
+    hasher.update(bytes);
+    hasher
+        .finalize_variable(&mut payload[20..])
+        .expect("matching output size");
+
+    let mut out = String::with_capacity(39);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for &byte in &payload {
+        acc = (acc << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((acc >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((acc << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+/// Checksum state after the expanded human-readable part.
+fn bech32_hrp_polymod(hrp: &str) -> u32 {
+    let mut chk = 1;
+    for b in hrp.bytes() {
+        chk = bech32_polymod_step(chk, b >> 5);
+    }
+    chk = bech32_polymod_step(chk, 0);
+    for b in hrp.bytes() {
+        chk = bech32_polymod_step(chk, b & 31);
+    }
+    chk
+}
+
+fn bech32_polymod_step(chk: u32, value: u8) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let top = chk >> 25;
+    let mut chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+    for (i, g) in GEN.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            chk ^= g;
+        }
+    }
+    chk
+}
+
+/// Number of alphabet symbols `c` matches; a case-insensitive letter matches
+/// both of its cases when the alphabet has them.
+fn symbol_hits(alphabet: &[u8], c: char, case_sensitive: bool) -> u64 {
+    alphabet
+        .iter()
+        .filter(|&&b| {
+            if case_sensitive {
+                b as char == c
+            } else {
+                (b as char).eq_ignore_ascii_case(&c)
+            }
+        })
+        .count() as u64
+}
+
+/// Base58 encoding (leading zero bytes become '1').
+fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Base-58 digits, least significant first.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let mut out = "1".repeat(zeros);
+    out.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    out
+}
+
+/// Base58 decoding; `None` on characters outside the alphabet.
+pub(crate) fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+    // Bytes, least significant first.
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+    for c in s[zeros..].bytes() {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut out = vec![0u8; zeros];
+    out.extend(bytes.iter().rev());
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_address(hex_str: &str) -> Address {
+        let bytes: [u8; 20] = hex::decode(hex_str).unwrap().try_into().unwrap();
+        Address::from_bytes(bytes)
+    }
+
+    #[test]
+    fn test_tron_encoding() {
+        // USDT (TRC-20) contract
+        let usdt = make_address("a614f803b6fd780986a42c78ec9c7f77e6ded13c");
+        assert_eq!(AddressFormat::Tron.encode(&usdt), "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t");
+        assert_eq!(
+            AddressFormat::Tron.match_body(&usdt, false).unwrap(),
+            "r7nhqjekqxgtci8q8zy4pl8otszgjlj6t"
+        );
+    }
+
+    #[test]
+    fn test_bech32_encoding() {
+        let addr = make_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        let evmos = AddressFormat::bech32("evmos").unwrap();
+        assert_eq!(evmos.encode(&addr), "evmos1t2htvpfl862vnwdqnuekd9p4ulh3h6hd4k0fm4");
+        assert_eq!(
+            evmos.match_body(&addr, false).unwrap(),
+            "t2htvpfl862vnwdqnuekd9p4ulh3h6hd4k0fm4"
+        );
+        // Same data characters, different checksum.
+        let one = AddressFormat::bech32("ONE").unwrap();
+        assert_eq!(one.encode(&addr), "one1t2htvpfl862vnwdqnuekd9p4ulh3h6hdcksx2z");
+
+        assert!(AddressFormat::bech32("").is_err());
+        assert!(AddressFormat::bech32("Evmos").is_err());
+        assert!(evmos.validate_pattern("t2ht", false).is_ok());
+        assert!(evmos.validate_pattern("b1", false).is_err());
+        assert!(evmos.validate_pattern("T2HT", true).is_err());
+    }
+
+    #[test]
+    fn test_filecoin_encoding() {
+        let addr = make_address("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        let filecoin = AddressFormat::Filecoin;
+        assert_eq!(filecoin.encode(&addr), "f410flkxlmbj7h2kmtonat4zwnfbv47xrx2xn5yjm6ma");
+        assert_eq!(
+            filecoin.encode(&Address::from_bytes([0; 20])),
+            "f410faaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaonc6iji"
+        );
+        assert_eq!(filecoin.match_body(&addr, false).unwrap().len(), filecoin.body_len());
+        assert!(filecoin.validate_pattern("lkx7", false).is_ok());
+        assert!(filecoin.validate_pattern("l0", false).is_err());
+    }
+
+    #[test]
+    fn test_icap_body() {
+        let icap = AddressFormat::Icap;
+        let addr = make_address("00c5496aee77c1ba1f0854206a26dda82a81d6d8");
+        assert_eq!(icap.encode(&addr), "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS");
+        assert_eq!(icap.match_body(&addr, true).unwrap(), "38O073KYGTWWZN0F2WZ0R8PX5ZPPZS");
+        assert_eq!(icap.match_body(&addr, false).unwrap(), "38o073kygtwwzn0f2wz0r8px5zppzs");
+        assert_eq!(icap.match_body(&make_address(&"08".repeat(20)), false), None);
+        assert!(icap.validate_pattern("CAFE", true).is_ok());
+        assert!(icap.validate_pattern("cafe", true).is_err());
+    }
+
+    #[test]
+    fn test_base58_leading_zeros() {
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(base58_encode(&[58]), "21");
+        assert_eq!(base58_decode("112"), Some(vec![0, 0, 1]));
+        assert_eq!(base58_decode("21"), Some(vec![58]));
+        assert_eq!(base58_decode("0OIl"), None);
+    }
+
+    #[test]
+    fn test_tron_pattern_validation() {
+        let tron = AddressFormat::Tron;
+        assert!(tron.validate_pattern("Doge", true).is_ok());
+        assert!(tron.validate_pattern("l", true).is_err());
+        assert!(tron.validate_pattern("l", false).is_ok()); // matches 'L'
+        assert!(tron.validate_pattern("0", false).is_err());
+        assert_eq!(tron.symbol_odds('a', false), 29);
+        assert_eq!(tron.symbol_odds('i', false), 58);
+        assert!(tron.validate_prefix("R7N", true).is_ok());
+        assert!(tron.validate_prefix("r7n", false).is_ok());
+        assert!(tron.validate_prefix("a", true).is_err());
+        assert!(tron.validate_prefix("1", false).is_err());
+    }
+}
@@ -0,0 +1,376 @@
+//! BIP32 hierarchical deterministic keys.
+//!
+//! master:            I = HMAC-SHA512("Bitcoin seed", seed)
+//! hardened child i:  I = HMAC-SHA512(chain code, 0x00 || key || i)   (i >= 2^31)
+//! normal child i:    I = HMAC-SHA512(chain code, compressed pubkey || i)
+//! child key = I[..32] + key mod n, child chain code = I[32..]
+//! An index whose I[..32] is not below n, or whose child key is zero, is
+//! invalid (probability below 2^-127); derivation returns `None` and callers
+//! move on to the next index, as BIP32 prescribes.
+//! An extended public key (xpub) derives the public keys of its normal
+//! children as parent + I[..32]·G, without any private key.
+
+use std::cell::OnceCell;
+use std::fmt;
+use std::str::FromStr;
+
+use hmac::{Hmac, Mac};
+use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey, Verification};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+
+use super::format::base58_decode;
+
+/// Offset of hardened child indices.
+pub const HARDENED: u32 = 1 << 31;
+
+/// Version bytes of mainnet extended public and private keys.
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+
+/// A BIP32 derivation path such as `m/44'/60'/0'/0`; the default is `m`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// The child indices, hardened ones offset by [`HARDENED`].
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Returns this path extended by one child index.
+    pub fn child(&self, index: u32) -> Self {
+        let mut indices = self.0.clone();
+        indices.push(index);
+        DerivationPath(indices)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = String;
+
+    /// Parses `m/...`; hardened indices are marked with `'` or `h`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(format!("Derivation path must start with m/, got {}", s));
+        }
+        parts
+            .map(|part| {
+                let (digits, hardened) = match part.strip_suffix(['\'', 'h']) {
+                    Some(digits) => (digits, true),
+                    None => (part, false),
+                };
+                match digits.parse::<u32>() {
+                    Ok(index) if index < HARDENED && hardened => Ok(index | HARDENED),
+                    Ok(index) if index < HARDENED => Ok(index),
+                    _ => Err(format!("Invalid derivation path component: {}", part)),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(DerivationPath)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for &index in &self.0 {
+            if index >= HARDENED {
+                write!(f, "/{}'", index - HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A BIP32 extended private key (key and chain code).
+#[derive(Clone)]
+pub struct ExtendedPrivateKey {
+    key: SecretKey,
+    chain_code: Zeroizing<[u8; 32]>,
+    /// Compressed public key, computed once for the first normal child
+    public_key: OnceCell<[u8; 33]>,
+}
+
+impl ExtendedPrivateKey {
+    /// Derives the master key of a BIP32 seed (e.g. a BIP39 seed).
+    pub fn from_seed(seed: &[u8]) -> Option<Self> {
+        Self::from_hmac(b"Bitcoin seed", &[seed], None)
+    }
+
+    /// Derives child `index` (hardened if `index >= HARDENED`).
+    pub fn derive_child(&self, index: u32) -> Option<Self> {
+        let index_bytes = index.to_be_bytes();
+        if index >= HARDENED {
+            let key = Zeroizing::new(self.key.secret_bytes());
+            let data: [&[u8]; 3] = [&[0], key.as_slice(), &index_bytes];
+            Self::from_hmac(&*self.chain_code, &data, Some(&self.key))
+        } else {
+            let public_key = self.public_key.get_or_init(|| {
+                PublicKey::from_secret_key(&Secp256k1::new(), &self.key).serialize()
+            });
+            Self::from_hmac(&*self.chain_code, &[public_key, &index_bytes], Some(&self.key))
+        }
+    }
+
+    /// Derives every index of `path` in turn.
+    pub fn derive_path(&self, path: &DerivationPath) -> Option<Self> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    /// Returns the secp256k1 secret key.
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.key
+    }
+
+    /// Returns the extended public key of this node.
+    pub fn to_public(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            key: PublicKey::from_secret_key(&Secp256k1::new(), &self.key),
+            chain_code: *self.chain_code,
+        }
+    }
+
+    /// I = HMAC-SHA512(hmac_key, data); the new key is I[..32] (plus `parent`).
+    fn from_hmac(hmac_key: &[u8], data: &[&[u8]], parent: Option<&SecretKey>) -> Option<Self> {
+        let mut mac = <Hmac<Sha512> as Mac>::new_from_slice(hmac_key).expect("any key length");
+        for part in data {
+            mac.update(part);
+        }
+        let mut i = Zeroizing::new([0u8; 64]);
+        i.copy_from_slice(&mac.finalize().into_bytes());
+
+        let tweak: [u8; 32] = i[..32].try_into().expect("32 bytes");
+        let key = match parent {
+            None => SecretKey::from_slice(&tweak).ok()?,
+            Some(parent) => parent.add_tweak(&Scalar::from_be_bytes(tweak).ok()?).ok()?,
+        };
+        let mut chain_code = Zeroizing::new([0u8; 32]);
+        chain_code.copy_from_slice(&i[32..]);
+        Some(Self {
+            key,
+            chain_code,
+            public_key: OnceCell::new(),
+        })
+    }
+}
+
+/// A BIP32 extended public key (public key and chain code), parsed from
+/// its `xpub...` serialization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    key: PublicKey,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedPublicKey {
+    /// Derives normal child `index`; `None` for hardened or invalid indices.
+    /// Takes a context because it runs once per candidate in xpub searches.
+    pub fn derive_child<C: Verification>(&self, secp: &Secp256k1<C>, index: u32) -> Option<Self> {
+        if index >= HARDENED {
+            return None;
+        }
+        let mut mac =
+            <Hmac<Sha512> as Mac>::new_from_slice(&self.chain_code).expect("any key length");
+        mac.update(&self.key.serialize());
+        mac.update(&index.to_be_bytes());
+        let i = mac.finalize().into_bytes();
+
+        let tweak = Scalar::from_be_bytes(i[..32].try_into().expect("32 bytes")).ok()?;
+        let key = self.key.add_exp_tweak(secp, &tweak).ok()?;
+        Some(Self {
+            key,
+            chain_code: i[32..].try_into().expect("32 bytes"),
+        })
+    }
+
+    /// Derives each index of `path` in turn.
+    pub fn derive_path<C: Verification>(&self, secp: &Secp256k1<C>, path: &[u32]) -> Option<Self> {
+        path.iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(secp, index))
+    }
+
+    /// Returns the secp256k1 public key.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.key
+    }
+
+    /// Returns the chain code.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+}
+
+/// What xpub mode searches: the normal children of an xpub, one or two
+/// levels deep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XpubSpec {
+    xpub: ExtendedPublicKey,
+    depth: u8,
+    indices: u32,
+}
+
+impl XpubSpec {
+    /// Searches children `i` (depth 1) or `i/j` with `j` in `0..indices`
+    /// (depth 2) of `xpub`; other depths are clamped to that range, and
+    /// `indices` to `1..=HARDENED`.
+    pub fn new(xpub: ExtendedPublicKey, depth: u8, indices: u32) -> Self {
+        Self {
+            xpub,
+            depth: depth.clamp(1, 2),
+            indices: indices.clamp(1, HARDENED),
+        }
+    }
+
+    /// The extended public key searched under.
+    pub fn xpub(&self) -> &ExtendedPublicKey {
+        &self.xpub
+    }
+
+    /// Levels of child indices below the xpub.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Number of indices `j` searched below each `i` at depth 2.
+    pub fn indices(&self) -> u32 {
+        self.indices
+    }
+}
+
+impl fmt::Display for XpubSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = hex::encode(self.xpub.key.serialize());
+        if self.depth == 1 {
+            write!(f, "children i of public key {}", key)
+        } else {
+            write!(f, "children i/0..{} of public key {}", self.indices - 1, key)
+        }
+    }
+}
+
+impl FromStr for ExtendedPublicKey {
+    type Err = String;
+
+    /// Parses a Base58Check mainnet `xpub`; refuses an `xprv`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || "Extended public key must be a Base58Check xpub".to_string();
+        let bytes = base58_decode(s.trim()).ok_or_else(invalid)?;
+        if bytes.len() != 82 {
+            return Err(invalid());
+        }
+        let (payload, checksum) = bytes.split_at(78);
+        if Sha256::digest(Sha256::digest(payload))[..4] != *checksum {
+            return Err("Extended public key checksum mismatch".into());
+        }
+        match payload[..4].try_into().expect("4 bytes") {
+            XPUB_VERSION => {}
+            XPRV_VERSION => {
+                return Err("That is an extended private key (xprv); pass its xpub instead".into());
+            }
+            _ => return Err("Only mainnet xpub extended keys are supported".into()),
+        }
+        // version(4) depth(1) parent fingerprint(4) child number(4) chain code(32) key(33)
+        Ok(Self {
+            chain_code: payload[13..45].try_into().expect("32 bytes"),
+            key: PublicKey::from_slice(&payload[45..])
+                .map_err(|_| "Extended public key holds an invalid public key".to_string())?,
+        })
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    /// Never shows the key or chain code.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ExtendedPrivateKey(<redacted>)")
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.key.non_secure_erase();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BIP32 test vector 1.
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    fn secret_hex(key: &ExtendedPrivateKey) -> String {
+        hex::encode(key.secret_key().secret_bytes())
+    }
+
+    #[test]
+    fn test_bip32_vector_1() {
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(SEED).unwrap()).unwrap();
+        assert_eq!(
+            secret_hex(&master),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+        assert_eq!(
+            hex::encode(*master.chain_code),
+            "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508"
+        );
+
+        let path: DerivationPath = "m/0'/1/2h/2/1000000000".parse().unwrap();
+        let cases = [
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+        ];
+        for (depth, expected) in cases.iter().enumerate() {
+            let prefix = DerivationPath(path.indices()[..=depth].to_vec());
+            assert_eq!(&secret_hex(&master.derive_path(&prefix).unwrap()), expected);
+        }
+    }
+
+    #[test]
+    fn test_xpub() {
+        // Master xpub of BIP32 test vector 1
+        let xpub: ExtendedPublicKey = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8Nqtwyb\
+                                       GhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+            .parse()
+            .unwrap();
+        let master = ExtendedPrivateKey::from_seed(&hex::decode(SEED).unwrap()).unwrap();
+        assert_eq!(xpub, master.to_public());
+
+        // Public derivation of normal children agrees with private derivation
+        let secp = Secp256k1::verification_only();
+        let account = master.derive_path(&"m/0'".parse().unwrap()).unwrap();
+        let path = [1, 2, 7];
+        let by_public = account.to_public().derive_path(&secp, &path).unwrap();
+        let by_private = account.derive_path(&DerivationPath(path.to_vec())).unwrap();
+        assert_eq!(by_public, by_private.to_public());
+        assert_eq!(xpub.derive_child(&secp, HARDENED), None);
+
+        let mut corrupted = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29\
+                             ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"
+            .to_string();
+        corrupted.replace_range(20..21, "x");
+        assert!(corrupted.parse::<ExtendedPublicKey>().is_err());
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6\
+                    LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        assert!(xprv.parse::<ExtendedPublicKey>().unwrap_err().contains("xprv"));
+    }
+
+    #[test]
+    fn test_derivation_path() {
+        let path: DerivationPath = "m/44'/60'/0'/0".parse().unwrap();
+        assert_eq!(path.indices(), &[44 | HARDENED, 60 | HARDENED, HARDENED, 0]);
+        assert_eq!(path.child(7).to_string(), "m/44'/60'/0'/0/7");
+        assert_eq!("m".parse::<DerivationPath>().unwrap().indices(), &[] as &[u32]);
+        assert!("44'/60'".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+        assert!("m/x".parse::<DerivationPath>().is_err());
+    }
+}
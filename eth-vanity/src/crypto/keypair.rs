@@ -2,14 +2,17 @@
 
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 use tiny_keccak::{Hasher, Keccak};
+use zeroize::{Zeroize, Zeroizing};
 
-use super::Address;
+use super::{Address, KeySource, OsSource, PrivateKey};
 
 /// Represents an Ethereum keypair (private key + derived address).
+///
+/// The private key is zeroed on drop and redacted from Debug output.
 #[derive(Debug, Clone)]
 pub struct Keypair {
-    /// The private key bytes (32 bytes)
-    secret_key: [u8; 32],
+    /// The private key (32 bytes)
+    secret_key: PrivateKey,
     /// The derived Ethereum address
     address: Address,
 }
@@ -20,30 +23,39 @@ impl Keypair {
     /// Uses a cryptographically secure random number generator.
     #[inline]
     pub fn generate() -> Self {
-        let secp = Secp256k1::new();
-        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
-        let address = Self::derive_address(&public_key);
+        Self::from_source(&mut OsSource)
+    }
 
-        Self {
-            secret_key: secret_key.secret_bytes(),
-            address,
-        }
+    /// Generates a keypair from the next key of `source`.
+    #[inline]
+    pub fn from_source(source: &mut dyn KeySource) -> Self {
+        Self::from_secp(source.next_secret_key())
     }
 
-    /// Generates a keypair from an existing secret key.
+    /// Generates a keypair from an existing secret key. The bytes are
+    /// borrowed, so a key held in a zeroizing buffer is not copied out of it.
     ///
     /// # Panics
     /// Panics if the secret key is invalid.
-    pub fn from_secret_key(secret_bytes: [u8; 32]) -> Self {
+    pub fn from_secret_key(secret_bytes: &[u8; 32]) -> Self {
+        let secret_key = SecretKey::from_slice(secret_bytes).expect("Invalid secret key");
+        Self::from_secp(secret_key)
+    }
+
+    /// Builds the keypair and erases the secp256k1 copy of the key.
+    fn from_secp(mut secret_key: SecretKey) -> Self {
         let secp = Secp256k1::new();
-        let secret_key = SecretKey::from_slice(&secret_bytes).expect("Invalid secret key");
         let public_key = PublicKey::from_secret_key(&secp, &secret_key);
         let address = Self::derive_address(&public_key);
 
-        Self {
-            secret_key: secret_bytes,
+        let mut secret_bytes = secret_key.secret_bytes();
+        let keypair = Self {
+            secret_key: PrivateKey::new(&secret_bytes),
             address,
-        }
+        };
+        secret_bytes.zeroize();
+        secret_key.non_secure_erase();
+        keypair
     }
 
     /// Derives an Ethereum address from a secp256k1 public key.
@@ -54,7 +66,7 @@ impl Keypair {
     /// 3. Hash the remaining 64 bytes with Keccak-256
     /// 4. Take the last 20 bytes of the hash
     #[inline]
-    fn derive_address(public_key: &PublicKey) -> Address {
+    pub(crate) fn derive_address(public_key: &PublicKey) -> Address {
         let public_key_bytes = public_key.serialize_uncompressed();
 
         // Skip the first byte (0x04 prefix) and hash the remaining 64 bytes
@@ -72,12 +84,17 @@ impl Keypair {
     }
 
     /// Returns the private key as a hex string (without 0x prefix).
-    pub fn private_key_hex(&self) -> String {
-        hex::encode(self.secret_key)
+    pub fn private_key_hex(&self) -> Zeroizing<String> {
+        self.secret_key.to_hex()
     }
 
     /// Returns the private key bytes.
     pub fn private_key_bytes(&self) -> &[u8; 32] {
+        self.secret_key.as_bytes()
+    }
+
+    /// Returns the private key.
+    pub fn private_key(&self) -> &PrivateKey {
         &self.secret_key
     }
 
@@ -107,12 +124,13 @@ mod tests {
             0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
             0x00, 0x00, 0x00, 0x01,
         ];
-        let keypair = Keypair::from_secret_key(secret_bytes);
+        let keypair = Keypair::from_secret_key(&secret_bytes);
 
         // Address for private key = 1 is well-known
         assert_eq!(
             keypair.address().to_hex(),
             "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
         );
+        assert!(!format!("{:?}", keypair).contains(&*keypair.private_key_hex()));
     }
 }
@@ -0,0 +1,283 @@
+//! Web3 Secret Storage (keystore v3) encryption.
+//!
+//! derived    = KDF(passphrase, salt)             (scrypt or PBKDF2-HMAC-SHA256)
+//! ciphertext = AES-128-CTR(derived[0..16], iv, private key)
+//! mac        = keccak256(derived[16..32] || ciphertext)
+//! The JSON layout is the one geth writes, which Foundry and MetaMask read.
+
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::RngCore;
+use sha2::Sha256;
+use tiny_keccak::{Hasher, Keccak};
+use zeroize::Zeroizing;
+
+use super::Address;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// geth's standard scrypt cost (`n = 2^18, r = 8, p = 1`).
+const SCRYPT_LOG_N: u8 = 18;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// PBKDF2 iteration count geth uses.
+const PBKDF2_ROUNDS: u32 = 262_144;
+
+/// Key derivation function of the keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeystoreKdf {
+    /// scrypt (geth's default)
+    #[default]
+    Scrypt,
+    /// PBKDF2-HMAC-SHA256
+    Pbkdf2,
+}
+
+impl FromStr for KeystoreKdf {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "scrypt" => Ok(KeystoreKdf::Scrypt),
+            "pbkdf2" => Ok(KeystoreKdf::Pbkdf2),
+            _ => Err(format!("Unknown keystore KDF: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for KeystoreKdf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreKdf::Scrypt => write!(f, "scrypt"),
+            KeystoreKdf::Pbkdf2 => write!(f, "pbkdf2"),
+        }
+    }
+}
+
+/// Cost parameters of one KDF run.
+#[derive(Debug, Clone, Copy)]
+enum KdfParams {
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    Pbkdf2 { rounds: u32 },
+}
+
+impl KdfParams {
+    fn derive(&self, passphrase: &[u8], salt: &[u8; 32]) -> Zeroizing<[u8; 32]> {
+        let mut derived = Zeroizing::new([0u8; 32]);
+        match *self {
+            KdfParams::Scrypt { log_n, r, p } => {
+                let params = scrypt::Params::new(log_n, r, p, 32).expect("valid scrypt params");
+                scrypt::scrypt(passphrase, salt, &params, &mut *derived)
+                    .expect("32-byte output is valid");
+            }
+            KdfParams::Pbkdf2 { rounds } => {
+                pbkdf2::pbkdf2_hmac::<Sha256>(passphrase, salt, rounds, &mut *derived);
+            }
+        }
+        derived
+    }
+
+    fn to_json(self, salt: &[u8; 32]) -> String {
+        match self {
+            KdfParams::Scrypt { log_n, r, p } => format!(
+                r#""kdf":"scrypt","kdfparams":{{"dklen":32,"n":{},"p":{},"r":{},"salt":"{}"}}"#,
+                1u64 << log_n,
+                p,
+                r,
+                hex::encode(salt)
+            ),
+            KdfParams::Pbkdf2 { rounds } => format!(
+                r#""kdf":"pbkdf2","kdfparams":{{"c":{},"dklen":32,"prf":"hmac-sha256","salt":"{}"}}"#,
+                rounds,
+                hex::encode(salt)
+            ),
+        }
+    }
+}
+
+impl From<KeystoreKdf> for KdfParams {
+    fn from(kdf: KeystoreKdf) -> Self {
+        match kdf {
+            KeystoreKdf::Scrypt => KdfParams::Scrypt {
+                log_n: SCRYPT_LOG_N,
+                r: SCRYPT_R,
+                p: SCRYPT_P,
+            },
+            KeystoreKdf::Pbkdf2 => KdfParams::Pbkdf2 {
+                rounds: PBKDF2_ROUNDS,
+            },
+        }
+    }
+}
+
+/// Encrypts `secret_key` under `passphrase` and returns the keystore JSON.
+pub fn encrypt_keystore(
+    secret_key: &[u8; 32],
+    address: &Address,
+    passphrase: &str,
+    kdf: KeystoreKdf,
+) -> String {
+    let mut rng = rand::thread_rng();
+    let mut salt = [0u8; 32];
+    let mut iv = [0u8; 16];
+    let mut id = [0u8; 16];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut iv);
+    rng.fill_bytes(&mut id);
+    encrypt_with(
+        secret_key,
+        address,
+        passphrase.as_bytes(),
+        kdf.into(),
+        &salt,
+        &iv,
+        id,
+    )
+}
+
+/// Writes the keystore of `address` into `dir` as `<address>.json`, readable
+/// by the owner only. Fails if the file already exists.
+pub fn write_keystore_file(dir: &Path, address: &Address, json: &str) -> io::Result<PathBuf> {
+    let path = dir.join(format!("{}.json", address.to_checksum()));
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    file.write_all(json.as_bytes())?;
+    file.sync_all()?;
+    Ok(path)
+}
+
+fn encrypt_with(
+    secret_key: &[u8; 32],
+    address: &Address,
+    passphrase: &[u8],
+    kdf: KdfParams,
+    salt: &[u8; 32],
+    iv: &[u8; 16],
+    id: [u8; 16],
+) -> String {
+    let derived = kdf.derive(passphrase, salt);
+    seal(secret_key, address, &derived, &kdf.to_json(salt), iv, id)
+}
+
+/// Encrypts and MACs `secret_key` under the KDF output `derived`, described
+/// in the JSON by `kdf_json`.
+fn seal(
+    secret_key: &[u8; 32],
+    address: &Address,
+    derived: &[u8; 32],
+    kdf_json: &str,
+    iv: &[u8; 16],
+    id: [u8; 16],
+) -> String {
+    let mut ciphertext = *secret_key;
+    let mut cipher = Aes128Ctr::new(derived[..16].into(), iv.into());
+    cipher.apply_keystream(&mut ciphertext);
+
+    let mut hasher = Keccak::v256();
+    hasher.update(&derived[16..]);
+    hasher.update(&ciphertext);
+    let mut mac = [0u8; 32];
+    hasher.finalize(&mut mac);
+
+    format!(
+        r#"{{"address":"{}","crypto":{{"cipher":"aes-128-ctr","cipherparams":{{"iv":"{}"}},"ciphertext":"{}",{},"mac":"{}"}},"id":"{}","version":3}}"#,
+        address.to_hex(),
+        hex::encode(iv),
+        hex::encode(ciphertext),
+        kdf_json,
+        hex::encode(mac),
+        uuid_v4(id)
+    )
+}
+
+/// Formats 16 random bytes as a version 4 UUID.
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let h = hex::encode(bytes);
+    format!("{}-{}-{}-{}-{}", &h[..8], &h[8..12], &h[12..16], &h[16..20], &h[20..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode<const N: usize>(s: &str) -> [u8; N] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_pbkdf2_spec_vector() {
+        // Web3 Secret Storage Definition, PBKDF2-SHA-256 test vector.
+        let secret = decode("7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d");
+        let json = encrypt_with(
+            &secret,
+            &Address::from_bytes([0; 20]),
+            b"testpassword",
+            KdfParams::Pbkdf2 { rounds: 262_144 },
+            &decode("ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"),
+            &decode("6087dab2f9fdbbfaddc31a909735c1e6"),
+            [0; 16],
+        );
+        assert!(json.contains(
+            r#""ciphertext":"5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46""#
+        ));
+        assert!(json.contains(
+            r#""mac":"517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2""#
+        ));
+        assert!(json.contains(r#""kdfparams":{"c":262144,"dklen":32,"prf":"hmac-sha256""#));
+    }
+
+    #[test]
+    fn test_scrypt_spec_vector() {
+        // Web3 Secret Storage Definition, scrypt test vector. Its cost
+        // (n = 2^18, r = 1) breaks RFC 7914's n < 2^(16 r), which the scrypt
+        // crate enforces, so the spec's derived key is sealed directly.
+        let salt = decode("ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19");
+        let json = seal(
+            &decode("7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"),
+            &Address::from_bytes([0; 20]),
+            &decode("fac192ceb5fd772906bea3e118a69e8bbb5cc24229e20d8766fd298291bba6bd"),
+            &KdfParams::Scrypt { log_n: 18, r: 1, p: 8 }.to_json(&salt),
+            &decode("83dbcc02d8ccb40e466191a123791e0e"),
+            [0; 16],
+        );
+        assert!(json.contains(
+            r#""ciphertext":"d172bf743a674da9cdad04534d56926ef8358534d458fffccd4e6ad2fbde479c""#
+        ));
+        assert!(json.contains(
+            r#""mac":"2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097""#
+        ));
+        assert!(json.contains(r#""kdfparams":{"dklen":32,"n":262144,"p":8,"r":1"#));
+    }
+
+    #[test]
+    fn test_scrypt_params_order() {
+        // Cross-checked with OpenSSL (Python's hashlib.scrypt, n=1024, r=2, p=3).
+        let derived = KdfParams::Scrypt { log_n: 10, r: 2, p: 3 }.derive(
+            b"testpassword",
+            &decode("ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"),
+        );
+        assert_eq!(
+            hex::encode(*derived),
+            "139396216e041345e796f6046185ffc657d219c7c5193615a63d2385dffc511c"
+        );
+    }
+
+    #[test]
+    fn test_uuid_v4() {
+        assert_eq!(uuid_v4([0xff; 16]), "ffffffff-ffff-4fff-bfff-ffffffffffff");
+    }
+}
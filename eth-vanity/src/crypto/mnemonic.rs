@@ -0,0 +1,275 @@
+//! BIP39 mnemonic mode: candidate keys are accounts of random seed phrases.
+//!
+//! phrase  = BIP39 English words of 16 (12 words) or 32 (24 words) random bytes
+//! seed    = PBKDF2-HMAC-SHA512(phrase, "mnemonic", 2048 rounds), empty passphrase
+//! keys    = BIP32 children m/44'/60'/0'/0/i of the seed, i in 0..indices
+//! The seed and the four-level account path cost as much as dozens of child
+//! keys, so each phrase is searched over all `indices` children before the
+//! next phrase is drawn. More indices means more addresses per second, and a
+//! deeper account index for the user to reach in their wallet.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bip39::{Language, Mnemonic};
+use secp256k1::SecretKey;
+use zeroize::Zeroizing;
+
+use super::{DerivationPath, ExtendedPrivateKey, KeySource};
+
+/// The standard Ethereum account path; keys are its children.
+pub const ACCOUNT_PATH: &str = "m/44'/60'/0'/0";
+
+/// Number of words of generated phrases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MnemonicLength {
+    /// 12 words, 128 bits of entropy
+    Words12,
+    /// 24 words, 256 bits of entropy
+    Words24,
+}
+
+impl MnemonicLength {
+    /// Bytes of entropy encoded by the phrase.
+    pub fn entropy_len(self) -> usize {
+        match self {
+            MnemonicLength::Words12 => 16,
+            MnemonicLength::Words24 => 32,
+        }
+    }
+}
+
+impl FromStr for MnemonicLength {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "12" => Ok(MnemonicLength::Words12),
+            "24" => Ok(MnemonicLength::Words24),
+            _ => Err(format!("Mnemonic length must be 12 or 24 words, got {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for MnemonicLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MnemonicLength::Words12 => write!(f, "12"),
+            MnemonicLength::Words24 => write!(f, "24"),
+        }
+    }
+}
+
+/// What mnemonic mode generates: phrase length and children per phrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MnemonicSpec {
+    length: MnemonicLength,
+    indices: u32,
+}
+
+impl MnemonicSpec {
+    /// Searches children `0..indices` of each phrase (at least 1).
+    pub fn new(length: MnemonicLength, indices: u32) -> Self {
+        Self {
+            length,
+            indices: indices.max(1),
+        }
+    }
+
+    /// Number of words of generated phrases.
+    pub fn length(&self) -> MnemonicLength {
+        self.length
+    }
+
+    /// Number of child indices searched per phrase.
+    pub fn indices(&self) -> u32 {
+        self.indices
+    }
+
+    /// Builds a source of mnemonic-derived keys whose phrases come from `entropy`.
+    pub fn source(&self, entropy: Box<dyn KeySource>) -> Box<dyn KeySource> {
+        Box::new(MnemonicSource {
+            entropy,
+            spec: *self,
+            account_path: ACCOUNT_PATH.parse().expect("valid path"),
+            account: None,
+            next_index: 0,
+        })
+    }
+}
+
+impl fmt::Display for MnemonicSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} words, {}/0..{}",
+            self.length,
+            ACCOUNT_PATH,
+            self.indices - 1
+        )
+    }
+}
+
+/// The phrase and path a key was derived from.
+#[derive(Clone)]
+pub struct MnemonicOrigin {
+    phrase: Zeroizing<String>,
+    path: DerivationPath,
+}
+
+impl MnemonicOrigin {
+    /// Records that a key was derived from `phrase` at `path`.
+    pub fn new(phrase: Zeroizing<String>, path: DerivationPath) -> Self {
+        Self { phrase, path }
+    }
+
+    /// The BIP39 phrase (as secret as the key).
+    pub fn phrase(&self) -> &Zeroizing<String> {
+        &self.phrase
+    }
+
+    /// The full derivation path of the key.
+    pub fn path(&self) -> &DerivationPath {
+        &self.path
+    }
+}
+
+impl fmt::Debug for MnemonicOrigin {
+    /// Never shows the phrase.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MnemonicOrigin(<redacted>, {})", self.path)
+    }
+}
+
+/// Keys derived from phrases drawn from an inner entropy source.
+pub struct MnemonicSource {
+    entropy: Box<dyn KeySource>,
+    spec: MnemonicSpec,
+    account_path: DerivationPath,
+    /// The current phrase and its account node
+    account: Option<(Mnemonic, ExtendedPrivateKey)>,
+    next_index: u32,
+}
+
+impl MnemonicSource {
+    /// Draws a new phrase and derives its account node.
+    fn next_account(&mut self) -> (Mnemonic, ExtendedPrivateKey) {
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        loop {
+            self.entropy.fill_secret(&mut entropy);
+            let mnemonic = Mnemonic::from_entropy(&entropy[..self.spec.length.entropy_len()])
+                .expect("16 or 32 bytes of entropy");
+            if let Some(account) = derive_node(&mnemonic, &self.account_path) {
+                return (mnemonic, account);
+            }
+        }
+    }
+}
+
+impl KeySource for MnemonicSource {
+    /// Fills the next child key of the current phrase. An invalid child
+    /// index (never seen in practice) is skipped here, as BIP32 says, so
+    /// every call yields a valid key.
+    fn fill_secret(&mut self, secret: &mut [u8; 32]) {
+        loop {
+            if self.account.is_none() || self.next_index == self.spec.indices {
+                self.account = Some(self.next_account());
+                self.next_index = 0;
+            }
+            let (_, account) = self.account.as_ref().expect("drawn above");
+            let child = account.derive_child(self.next_index);
+            self.next_index += 1;
+            if let Some(child) = child {
+                secret.copy_from_slice(&child.secret_key().secret_bytes());
+                return;
+            }
+        }
+    }
+
+    fn mnemonic_origin(&self) -> Option<MnemonicOrigin> {
+        let (mnemonic, _) = self.account.as_ref()?;
+        Some(MnemonicOrigin::new(
+            Zeroizing::new(mnemonic.to_string()),
+            self.account_path.child(self.next_index - 1),
+        ))
+    }
+}
+
+/// Derives the key of an English `phrase` (empty passphrase) at `path`.
+pub fn mnemonic_key(phrase: &str, path: &DerivationPath) -> Result<SecretKey, String> {
+    let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase)
+        .map_err(|e| format!("Invalid mnemonic: {}", e))?;
+    derive_node(&mnemonic, path)
+        .map(|key| *key.secret_key())
+        .ok_or_else(|| format!("{} is not a valid key of this mnemonic", path))
+}
+
+fn derive_node(mnemonic: &Mnemonic, path: &DerivationPath) -> Option<ExtendedPrivateKey> {
+    let seed = Zeroizing::new(mnemonic.to_seed_normalized(""));
+    ExtendedPrivateKey::from_seed(seed.as_slice())?.derive_path(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::Keypair;
+
+    /// Counts up from 1, so phrases are predictable.
+    struct Counter(u8);
+
+    impl KeySource for Counter {
+        fn fill_secret(&mut self, secret: &mut [u8; 32]) {
+            self.0 += 1;
+            *secret = [self.0; 32];
+        }
+    }
+
+    #[test]
+    fn test_known_phrase() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                      abandon abandon about";
+        let key = mnemonic_key(phrase, &"m/44'/60'/0'/0/0".parse().unwrap()).unwrap();
+        let keypair = Keypair::from_secret_key(&key.secret_bytes());
+        assert_eq!(
+            keypair.address().to_checksum(),
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94"
+        );
+        assert!(mnemonic_key("abandon about", &ACCOUNT_PATH.parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_source_walks_indices_then_redraws() {
+        let spec = MnemonicSpec::new(MnemonicLength::Words12, 3);
+        let mut source = spec.source(Box::new(Counter(0)));
+        let mut origins = Vec::new();
+        for _ in 0..4 {
+            let key = source.next_secret_key();
+            let origin = source.mnemonic_origin().unwrap();
+            assert_eq!(origin.phrase().split_whitespace().count(), 12);
+            assert_eq!(mnemonic_key(origin.phrase(), origin.path()).unwrap(), key);
+            origins.push(origin);
+        }
+        let paths: Vec<_> = origins.iter().map(|o| o.path().to_string()).collect();
+        let expected = ["m/44'/60'/0'/0/0", "m/44'/60'/0'/0/1", "m/44'/60'/0'/0/2"];
+        assert_eq!(paths[..3], expected);
+        assert_eq!(paths[3], expected[0]);
+        assert_eq!(origins[0].phrase(), origins[2].phrase());
+        assert_ne!(origins[0].phrase(), origins[3].phrase());
+        let first_word = origins[0].phrase().split_whitespace().next().unwrap().to_string();
+        assert!(!format!("{:?}", origins[0]).contains(&first_word));
+
+        let spec = MnemonicSpec::new(MnemonicLength::Words24, 1);
+        let mut source = spec.source(Box::new(Counter(0)));
+        source.next_secret_key();
+        let origin = source.mnemonic_origin().unwrap();
+        assert_eq!(origin.phrase().split_whitespace().count(), 24);
+    }
+
+    #[test]
+    fn test_mnemonic_length() {
+        assert_eq!("24".parse::<MnemonicLength>(), Ok(MnemonicLength::Words24));
+        assert!("18".parse::<MnemonicLength>().is_err());
+        let spec = MnemonicSpec::new(MnemonicLength::Words12, 20);
+        assert_eq!(spec.to_string(), "12 words, m/44'/60'/0'/0/0..19");
+    }
+}
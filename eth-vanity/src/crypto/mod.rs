@@ -1,12 +1,49 @@
 //! Cryptographic operations for Ethereum key and address generation.
 //!
 //! This module provides:
-//! - Secure random key generation using secp256k1
+//! - Secure random key generation using secp256k1, from pluggable key sources
 //! - Ethereum address derivation using Keccak-256
-//! - Keypair management
+//! - Contract address derivation for CREATE deployments
+//! - Safe address derivation for a 1/1 Safe owned by the key
+//! - Non-hex address formats (Tron, bech32, Filecoin f410, ICAP)
+//! - Keypair management, with zeroized and optionally mlocked private keys
+//! - BIP32 derivation, BIP39 mnemonic-derived candidate keys and keyless
+//!   search under an extended public key (xpub)
+//! - Keystore v3 (Web3 Secret Storage) encryption
+//! - ECIES sealing of results to an offline recipient
+//! - k-of-n Shamir sharing of found keys
 
 mod address;
+mod create;
+mod ecies;
+mod entropy;
+mod format;
+mod hd;
 mod keypair;
+mod keystore;
+mod mnemonic;
+mod safe;
+mod scalar;
+mod secret;
+mod shamir;
 
-pub use address::Address;
+pub use address::{Address, IcapError};
+pub use create::contract_address;
+pub use ecies::{EciesError, Identity, Recipient};
+#[cfg(feature = "deterministic")]
+pub use entropy::DeterministicSource;
+pub use entropy::{Entropy, KeySource, MixedSource, OsSource};
+pub use format::AddressFormat;
+pub use hd::{DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, XpubSpec, HARDENED};
 pub use keypair::Keypair;
+pub use keystore::{encrypt_keystore, write_keystore_file, KeystoreKdf};
+pub use mnemonic::{
+    mnemonic_key, MnemonicLength, MnemonicOrigin, MnemonicSource, MnemonicSpec, ACCOUNT_PATH,
+};
+pub use safe::SafeDeployment;
+pub use scalar::add_scalar_mod_n;
+pub use secret::{PrivateKey, SecretText};
+pub use shamir::{
+    combine_shares, parse_share_file, share_file_contents, write_share_files, ShamirError, Share,
+    ShareSpec,
+};
@@ -0,0 +1,153 @@
+//! Address of a 1/1 Safe owned by a candidate EOA.
+//!
+//! SafeProxyFactory.createProxyWithNonce(singleton, initializer, saltNonce):
+//!   initializer = setup([owner], 1, 0, "", fallbackHandler, 0, 0, 0)
+//!   salt        = keccak256(keccak256(initializer) || saltNonce)
+//!   address     = keccak256(0xff || factory || salt || initCodeHash)[12..32]
+//! Only the owner word of the initializer changes between candidates.
+
+use tiny_keccak::{Hasher, Keccak};
+
+use super::Address;
+
+/// `Safe.setup(address[],uint256,address,bytes,address,address,uint256,address)`.
+const SETUP_SELECTOR: [u8; 4] = [0xb6, 0x3e, 0x80, 0x0d];
+
+/// Length of the ABI-encoded setup call with one owner and empty `data`.
+const SETUP_LEN: usize = 4 + 8 * 32 + 2 * 32 + 32;
+
+/// Offset of the owner address in the setup calldata (last 20 bytes of the
+/// first `_owners` element).
+const OWNER_OFFSET: usize = 4 + 8 * 32 + 32 + 12;
+
+/// Parameters of the Safe deployment, fixed for the whole search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafeDeployment {
+    factory: [u8; 20],
+    init_code_hash: [u8; 32],
+    fallback_handler: [u8; 20],
+    salt_nonce: [u8; 32],
+    /// Setup calldata with a zero owner.
+    setup: [u8; SETUP_LEN],
+}
+
+impl SafeDeployment {
+    /// `init_code_hash` is keccak256(proxyCreationCode || singleton), as in
+    /// safe-vanity; `salt_nonce` is the big-endian `saltNonce` word.
+    pub fn new(
+        factory: [u8; 20],
+        init_code_hash: [u8; 32],
+        fallback_handler: [u8; 20],
+        salt_nonce: [u8; 32],
+    ) -> Self {
+        let mut setup = [0u8; SETUP_LEN];
+        setup[..4].copy_from_slice(&SETUP_SELECTOR);
+        // Head: _owners offset, _threshold, to, data offset, fallbackHandler,
+        // paymentToken, payment, paymentReceiver.
+        set_word(&mut setup, 0, 0x100);
+        set_word(&mut setup, 1, 1);
+        set_word(&mut setup, 3, 0x140);
+        setup[4 + 4 * 32 + 12..4 + 5 * 32].copy_from_slice(&fallback_handler);
+        // Tail: _owners = [owner], data = "".
+        set_word(&mut setup, 8, 1);
+        Self {
+            factory,
+            init_code_hash,
+            fallback_handler,
+            salt_nonce,
+            setup,
+        }
+    }
+
+    /// The SafeProxyFactory address.
+    pub fn factory(&self) -> &[u8; 20] {
+        &self.factory
+    }
+
+    /// keccak256(proxyCreationCode || singleton).
+    pub fn init_code_hash(&self) -> &[u8; 32] {
+        &self.init_code_hash
+    }
+
+    /// The fallback handler passed to `setup`.
+    pub fn fallback_handler(&self) -> &[u8; 20] {
+        &self.fallback_handler
+    }
+
+    /// The big-endian `saltNonce` word.
+    pub fn salt_nonce(&self) -> &[u8; 32] {
+        &self.salt_nonce
+    }
+
+    /// keccak256 of the setup calldata with `owner` as the only owner.
+    pub fn initializer_hash(&self, owner: &Address) -> [u8; 32] {
+        let mut setup = self.setup;
+        setup[OWNER_OFFSET..OWNER_OFFSET + 20].copy_from_slice(owner.as_bytes());
+        keccak256(&setup)
+    }
+
+    /// Address of the 1/1 Safe owned by `owner`.
+    pub fn address(&self, owner: &Address) -> Address {
+        let mut salt_preimage = [0u8; 64];
+        salt_preimage[..32].copy_from_slice(&self.initializer_hash(owner));
+        salt_preimage[32..].copy_from_slice(&self.salt_nonce);
+        let salt = keccak256(&salt_preimage);
+
+        let mut preimage = [0u8; 85];
+        preimage[0] = 0xff;
+        preimage[1..21].copy_from_slice(&self.factory);
+        preimage[21..53].copy_from_slice(&salt);
+        preimage[53..85].copy_from_slice(&self.init_code_hash);
+        let hash = keccak256(&preimage);
+
+        let mut address_bytes = [0u8; 20];
+        address_bytes.copy_from_slice(&hash[12..]);
+        Address::from_bytes(address_bytes)
+    }
+}
+
+/// Writes a small integer into the `index`-th calldata word after the selector.
+fn set_word(calldata: &mut [u8], index: usize, value: u16) {
+    let end = 4 + (index + 1) * 32;
+    calldata[end - 2..end].copy_from_slice(&value.to_be_bytes());
+}
+
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_calldata_layout() {
+        let owner = Address::from_bytes([0x11; 20]);
+        let deployment = SafeDeployment::new([0; 20], [0; 32], [0x22; 20], [0; 32]);
+
+        // Hand-assembled abi.encodeWithSelector(setup, [owner], 1, 0, "", handler, 0, 0, 0).
+        let word = |hex_tail: &str| format!("{:0>64}", hex_tail);
+        let expected = [
+            "b63e800d".to_string(),
+            word("100"),
+            word("1"),
+            word("0"),
+            word("140"),
+            word(&"22".repeat(20)),
+            word("0"),
+            word("0"),
+            word("0"),
+            word("1"),
+            word(&"11".repeat(20)),
+            word("0"),
+        ]
+        .concat();
+        let expected = hex::decode(expected).unwrap();
+        assert_eq!(expected.len(), SETUP_LEN);
+        assert_eq!(deployment.initializer_hash(&owner), keccak256(&expected));
+    }
+}
@@ -0,0 +1,98 @@
+//! Scalar arithmetic modulo the secp256k1 curve order.
+//!
+//! The GPU worker tests `base + i` for a batch of offsets `i` and rebuilds
+//! the private key of a hit on the CPU with [`add_scalar_mod_n`].
+
+/// Adds a scalar offset to a base private key modulo the secp256k1 curve order.
+///
+/// Returns zero when the sum is exactly n (the offset reached the point at
+/// infinity, which is not a valid key).
+pub fn add_scalar_mod_n(base_key: &[u8; 32], offset: u32) -> [u8; 32] {
+    // secp256k1 curve order n
+    let n: [u64; 4] = [
+        0xBFD25E8CD0364141,
+        0xBAAEDCE6AF48A03B,
+        0xFFFFFFFFFFFFFFFE,
+        0xFFFFFFFFFFFFFFFF,
+    ];
+
+    // Convert base_key (big-endian) to u64 limbs (little-endian limb order)
+    let mut key = [0u64; 4];
+    for (i, limb) in key.iter_mut().enumerate() {
+        let off = (3 - i) * 8;
+        *limb = u64::from_be_bytes(base_key[off..off + 8].try_into().expect("8 bytes"));
+    }
+
+    // Add offset
+    let mut carry = offset as u128;
+    for limb in key.iter_mut() {
+        let sum = *limb as u128 + carry;
+        *limb = sum as u64;
+        carry = sum >> 64;
+    }
+
+    // Reduce mod n if needed
+    let mut gte_n = carry > 0;
+    if !gte_n {
+        // Compare key >= n; equal limbs all the way down means key == n
+        gte_n = true;
+        for i in (0..4).rev() {
+            if key[i] != n[i] {
+                gte_n = key[i] > n[i];
+                break;
+            }
+        }
+    }
+
+    if gte_n {
+        let mut borrow: u128 = 0;
+        for i in 0..4 {
+            let diff = key[i] as u128 + (1u128 << 64) - n[i] as u128 - borrow;
+            key[i] = diff as u64;
+            borrow = 1 - (diff >> 64);
+        }
+    }
+
+    // Convert back to big-endian bytes
+    let mut result = [0u8; 32];
+    for (i, limb) in key.iter().enumerate() {
+        let off = (3 - i) * 8;
+        result[off..off + 8].copy_from_slice(&limb.to_be_bytes());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_scalar_mod_n_basic() {
+        let mut base = [0u8; 32];
+        base[31] = 1; // key = 1
+        let result = add_scalar_mod_n(&base, 5);
+        assert_eq!(result[31], 6); // 1 + 5 = 6
+        // All other bytes should be 0
+        assert!(result[..31].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_add_scalar_mod_n_carry() {
+        let mut base = [0u8; 32];
+        base[31] = 0xFF;
+        let result = add_scalar_mod_n(&base, 1);
+        assert_eq!(result[31], 0);
+        assert_eq!(result[30], 1); // carry propagated
+    }
+
+    #[test]
+    fn test_add_scalar_mod_n_wraps_at_n() {
+        let mut n_minus_1 = secp256k1::constants::CURVE_ORDER;
+        n_minus_1[31] -= 1;
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(add_scalar_mod_n(&n_minus_1, 1), [0u8; 32]);
+        assert_eq!(add_scalar_mod_n(&n_minus_1, 2), one);
+    }
+}
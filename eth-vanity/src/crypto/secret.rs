@@ -0,0 +1,268 @@
+//! Secret key material that cleans up after itself.
+//!
+//! `PrivateKey` keeps its bytes on the heap so moving it (into a result,
+//! through the pool channel) copies only a pointer, and zeroes them on drop.
+//! Debug output is redacted. A key can also be locked in RAM (mlock) so it
+//! is never written to swap. `SecretText` does the same for text that
+//! holds a key, such as a result block before it is printed or sealed.
+//!
+//! mlock works on whole pages, and small secrets share pages with each
+//! other and with unrelated heap data. Locked pages are counted, so a page
+//! is unlocked only when the last locked secret on it is dropped.
+
+#[cfg(unix)]
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+#[cfg(unix)]
+use std::ops::Range;
+#[cfg(unix)]
+use std::sync::{Mutex, PoisonError};
+
+use zeroize::{Zeroize, Zeroizing};
+
+/// A 32-byte secp256k1 private key.
+pub struct PrivateKey {
+    bytes: Box<[u8; 32]>,
+    locked: bool,
+}
+
+impl PrivateKey {
+    /// Copies `bytes` into a new key; the caller should zeroize its copy.
+    pub fn new(bytes: &[u8; 32]) -> Self {
+        Self {
+            bytes: Box::new(*bytes),
+            locked: false,
+        }
+    }
+
+    /// Returns the key bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    /// Returns the key as hex (without 0x prefix), zeroed when dropped.
+    pub fn to_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(*self.bytes))
+    }
+
+    /// Returns true if the key is locked in memory.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Locks the key's memory so it cannot be swapped out. Unlocked again
+    /// when the key is dropped.
+    pub fn lock(&mut self) -> io::Result<()> {
+        if !self.locked {
+            mlock(self.bytes.as_ptr(), self.bytes.len())?;
+            self.locked = true;
+        }
+        Ok(())
+    }
+}
+
+impl Clone for PrivateKey {
+    /// The copy is not locked, even if the original is.
+    fn clone(&self) -> Self {
+        Self::new(&self.bytes)
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+        if self.locked {
+            munlock(self.bytes.as_ptr(), self.bytes.len());
+        }
+    }
+}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey(<redacted>)")
+    }
+}
+
+/// A text buffer for output that contains secrets: zeroed on drop and
+/// optionally locked in RAM. It never lets `String` reallocate (which would
+/// leave a stale copy behind); it moves to a larger buffer itself, locked
+/// before the text is copied over.
+pub struct SecretText {
+    text: String,
+    locked: bool,
+}
+
+impl SecretText {
+    /// Creates an empty buffer with room for `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            text: String::with_capacity(capacity),
+            locked: false,
+        }
+    }
+
+    /// Returns the text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns true if the whole buffer is locked in memory. Growing a
+    /// locked buffer past what mlock allows leaves it unlocked.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Locks the buffer so it cannot be swapped out. Unlocked again when
+    /// the buffer is dropped.
+    pub fn lock(&mut self) -> io::Result<()> {
+        if !self.locked {
+            mlock(self.text.as_ptr(), self.text.capacity())?;
+            self.locked = true;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for SecretText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let len = self.text.len() + s.len();
+        if len > self.text.capacity() {
+            let mut grown = SecretText::with_capacity(len.max(2 * self.text.capacity()));
+            if self.locked {
+                let _ = grown.lock();
+            }
+            grown.text.push_str(&self.text);
+            // The old buffer is zeroed and unlocked as it drops
+            *self = grown;
+        }
+        self.text.push_str(s);
+        Ok(())
+    }
+}
+
+impl Drop for SecretText {
+    fn drop(&mut self) {
+        self.text.zeroize();
+        if self.locked {
+            munlock(self.text.as_ptr(), self.text.capacity());
+        }
+    }
+}
+
+/// Number of locked secrets on each page, by page number.
+#[cfg(unix)]
+static LOCKED_PAGES: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+#[cfg(unix)]
+fn page_size() -> usize {
+    // SAFETY: sysconf has no preconditions.
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+/// Page numbers covering `ptr..ptr + len` (non-empty).
+#[cfg(unix)]
+fn pages(ptr: *const u8, len: usize, page_size: usize) -> Range<usize> {
+    let start = ptr as usize;
+    start / page_size..(start + len - 1) / page_size + 1
+}
+
+#[cfg(unix)]
+fn mlock(ptr: *const u8, len: usize) -> io::Result<()> {
+    if len == 0 {
+        return Ok(());
+    }
+    let page_size = page_size();
+    let pages = pages(ptr, len, page_size);
+    let mut locked = LOCKED_PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+    // Locking an already locked page again is harmless.
+    // SAFETY: the pages hold `ptr..ptr + len`, a live allocation owned by
+    // the caller.
+    let start = (pages.start * page_size) as *const libc::c_void;
+    if unsafe { libc::mlock(start, pages.len() * page_size) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    for page in pages {
+        *locked.entry(page).or_insert(0) += 1;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn munlock(ptr: *const u8, len: usize) {
+    if len == 0 {
+        return;
+    }
+    let page_size = page_size();
+    let mut locked = LOCKED_PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+    for page in pages(ptr, len, page_size) {
+        let count = locked.entry(page).or_insert(1);
+        *count -= 1;
+        if *count == 0 {
+            locked.remove(&page);
+            // SAFETY: as in `mlock`; no locked secret is left on the page.
+            unsafe {
+                libc::munlock((page * page_size) as *const libc::c_void, page_size);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn mlock(_ptr: *const u8, _len: usize) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "memory locking is only supported on Unix",
+    ))
+}
+
+#[cfg(not(unix))]
+fn munlock(_ptr: *const u8, _len: usize) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_private_key_redacted_and_lockable() {
+        let mut key = PrivateKey::new(&[0xab; 32]);
+        assert_eq!(format!("{:?}", key), "PrivateKey(<redacted>)");
+        assert_eq!(key.to_hex().as_str(), "ab".repeat(32));
+
+        // RLIMIT_MEMLOCK may be zero in sandboxes; only check the flag on success.
+        if key.lock().is_ok() {
+            assert!(key.is_locked());
+            assert!(!key.clone().is_locked());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shared_page_stays_locked() {
+        // Both ranges hold the last byte, so they share its page
+        let buf = Box::new([0u8; 64]);
+        if mlock(buf.as_ptr(), 64).is_err() {
+            return;
+        }
+        mlock(buf[32..].as_ptr(), 32).unwrap();
+        let page = buf[63..].as_ptr() as usize / page_size();
+        munlock(buf.as_ptr(), 64);
+        assert!(LOCKED_PAGES.lock().unwrap().contains_key(&page));
+        munlock(buf[32..].as_ptr(), 32);
+    }
+
+    #[test]
+    fn test_secret_text_grows_without_losing_text() {
+        use std::fmt::Write;
+
+        let mut text = SecretText::with_capacity(8);
+        let locked = text.lock().is_ok();
+        write!(text, "{}", "ab".repeat(3)).unwrap();
+        write!(text, "{}", "cd".repeat(10)).unwrap();
+        assert_eq!(text.as_str(), format!("{}{}", "ab".repeat(3), "cd".repeat(10)));
+        if locked {
+            assert!(text.is_locked());
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! k-of-n Shamir secret sharing of private keys.
+//!
+//! Each key byte is the constant term of a random polynomial of degree k - 1
+//! over GF(2^8) (the AES field, x^8 + x^4 + x^3 + x + 1); share x holds the
+//! 32 evaluations at x = 1..=n. Any k shares rebuild the key by Lagrange
+//! interpolation at 0, fewer reveal nothing about it. Field multiplication
+//! uses no lookup tables, so its timing does not depend on the key.
+//!
+//! A share is written `<k>-<x>-<64 hex chars>`, or as a mnemonic `<k>-<x>`
+//! followed by the 24 BIP39 English words of its 32 bytes.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use bip39::{Language, Mnemonic};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use super::Address;
+
+/// How many shares to write and how many rebuild the key, e.g. `2-of-3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareSpec {
+    threshold: u8,
+    count: u8,
+}
+
+impl ShareSpec {
+    /// Requires 2 <= threshold <= count <= 255.
+    pub fn new(threshold: u8, count: u8) -> Result<Self, String> {
+        if threshold < 2 || threshold > count {
+            return Err(format!(
+                "Share threshold must be between 2 and the share count, got {}-of-{}",
+                threshold, count
+            ));
+        }
+        Ok(Self { threshold, count })
+    }
+
+    /// Number of shares that rebuild the key.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Number of shares written.
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Splits `secret` into `count` shares with indices 1..=count.
+    pub fn split(&self, secret: &[u8; 32]) -> Vec<Share> {
+        // coefficients[d] holds the degree d + 1 coefficient of every byte's polynomial
+        let mut coefficients = Zeroizing::new(vec![[0u8; 32]; self.threshold as usize - 1]);
+        for coefficient in coefficients.iter_mut() {
+            rand::thread_rng().fill_bytes(coefficient);
+        }
+        (1..=self.count)
+            .map(|x| {
+                let mut data = Zeroizing::new([0u8; 32]);
+                for (i, y) in data.iter_mut().enumerate() {
+                    // Horner's rule, highest degree first
+                    let mut acc = 0;
+                    for coefficient in coefficients.iter().rev() {
+                        acc = gf_mul(acc, x) ^ coefficient[i];
+                    }
+                    *y = gf_mul(acc, x) ^ secret[i];
+                }
+                Share {
+                    threshold: self.threshold,
+                    index: x,
+                    data,
+                }
+            })
+            .collect()
+    }
+}
+
+impl FromStr for ShareSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Shares must be given as K-of-N (e.g. 2-of-3), got {}", s);
+        let lower = s.to_lowercase();
+        let (k, n) = lower.split_once("-of-").ok_or_else(invalid)?;
+        ShareSpec::new(k.parse().map_err(|_| invalid())?, n.parse().map_err(|_| invalid())?)
+    }
+}
+
+impl fmt::Display for ShareSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-of-{}", self.threshold, self.count)
+    }
+}
+
+/// One share of a private key.
+#[derive(Clone)]
+pub struct Share {
+    threshold: u8,
+    index: u8,
+    data: Zeroizing<[u8; 32]>,
+}
+
+impl Share {
+    /// Number of shares that rebuild the key.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// The share's x coordinate (1-based).
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the share as `<k>-<x>-<hex>`.
+    pub fn to_text(&self) -> Zeroizing<String> {
+        Zeroizing::new(format!(
+            "{}-{}-{}",
+            self.threshold,
+            self.index,
+            *Zeroizing::new(hex::encode(self.data.as_slice()))
+        ))
+    }
+
+    /// Returns the share as `<k>-<x>` followed by 24 BIP39 words.
+    pub fn to_mnemonic(&self) -> Zeroizing<String> {
+        let mnemonic = Mnemonic::from_entropy(self.data.as_slice()).expect("32 bytes of entropy");
+        let mut out = Zeroizing::new(format!("{}-{}", self.threshold, self.index));
+        for word in mnemonic.words() {
+            out.push(' ');
+            out.push_str(word);
+        }
+        out
+    }
+}
+
+impl FromStr for Share {
+    type Err = ShamirError;
+
+    /// Parses either form written by [`Share::to_text`] or [`Share::to_mnemonic`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ShamirError::InvalidShare(reason.to_string());
+        let s = s.trim();
+        let (threshold, rest) = s.split_once('-').ok_or_else(|| invalid("missing threshold"))?;
+        let (index, data) = match rest.split_once(char::is_whitespace) {
+            Some((index, words)) => {
+                let words = Zeroizing::new(words.to_lowercase());
+                let mnemonic = Mnemonic::parse_in_normalized(Language::English, &words)
+                    .map_err(|e| ShamirError::InvalidShare(format!("mnemonic: {}", e)))?;
+                let (entropy, len) = mnemonic.to_entropy_array();
+                let entropy = Zeroizing::new(entropy);
+                let data: [u8; 32] = entropy[..len]
+                    .try_into()
+                    .map_err(|_| invalid("mnemonic must have 24 words"))?;
+                (index, Zeroizing::new(data))
+            }
+            None => {
+                let (index, hex_data) = rest.split_once('-').ok_or_else(|| invalid("missing data"))?;
+                let mut data = Zeroizing::new([0u8; 32]);
+                hex::decode_to_slice(hex_data, data.as_mut_slice())
+                    .map_err(|_| invalid("data must be 64 hex characters"))?;
+                (index, data)
+            }
+        };
+        let threshold: u8 = threshold.parse().map_err(|_| invalid("bad threshold"))?;
+        let index: u8 = index.parse().map_err(|_| invalid("bad index"))?;
+        if threshold < 2 || index == 0 {
+            return Err(invalid("threshold must be at least 2 and index at least 1"));
+        }
+        Ok(Share {
+            threshold,
+            index,
+            data,
+        })
+    }
+}
+
+impl fmt::Debug for Share {
+    /// Never shows the share data.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Share({}-{}, <redacted>)", self.threshold, self.index)
+    }
+}
+
+/// Rebuilds a key from at least `threshold` distinct shares of it.
+pub fn combine_shares(shares: &[Share]) -> Result<Zeroizing<[u8; 32]>, ShamirError> {
+    let threshold = shares.first().map_or(2, |s| s.threshold);
+    if shares.iter().any(|s| s.threshold != threshold) {
+        return Err(ShamirError::ThresholdMismatch);
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|s| s.index == share.index) {
+            return Err(ShamirError::DuplicateShare(share.index));
+        }
+    }
+    if shares.len() < threshold as usize {
+        return Err(ShamirError::NotEnoughShares {
+            needed: threshold,
+            got: shares.len(),
+        });
+    }
+
+    let shares = &shares[..threshold as usize];
+    let mut secret = Zeroizing::new([0u8; 32]);
+    for share in shares {
+        // Lagrange basis polynomial of this share at x = 0 (subtraction is XOR)
+        let mut basis = 1;
+        for other in shares.iter().filter(|s| s.index != share.index) {
+            basis = gf_mul(basis, gf_mul(other.index, gf_inv(other.index ^ share.index)));
+        }
+        for (byte, y) in secret.iter_mut().zip(share.data.iter()) {
+            *byte ^= gf_mul(*y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Contents of a share file: comments, the address and the share.
+pub fn share_file_contents(
+    address: &Address,
+    share: &Share,
+    count: u8,
+    mnemonic: bool,
+) -> Zeroizing<String> {
+    let encoded = if mnemonic {
+        share.to_mnemonic()
+    } else {
+        share.to_text()
+    };
+    Zeroizing::new(format!(
+        "# eth-vanity Shamir share {} of {} (any {} rebuild the key)\n\
+         # Recover with: eth_vanity recover <share files>\n\
+         Address: {}\n\
+         Share:   {}\n",
+        share.index,
+        count,
+        share.threshold,
+        address.to_checksum(),
+        *encoded
+    ))
+}
+
+/// Parses a share file into its address (as written) and share.
+pub fn parse_share_file(contents: &str) -> Result<(String, Share), ShamirError> {
+    let field = |name: &str| {
+        contents
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(name))
+            .map(str::trim)
+            .ok_or_else(|| ShamirError::InvalidShare(format!("missing {} line", name)))
+    };
+    let address = field("Address:")?;
+    let share = field("Share:")?.parse()?;
+    Ok((address.to_string(), share))
+}
+
+/// Writes one file per share, named `<address>-share-<x>.txt` (mode 0600
+/// on Unix). Every share is written and synced to a temporary file first;
+/// the files are renamed into place only when all of them were written, so
+/// a failure leaves none of them behind. Fails if a share file already
+/// exists.
+pub fn write_share_files(
+    dir: &Path,
+    address: &Address,
+    shares: &[Share],
+    count: u8,
+    mnemonic: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let name = |share: &Share| format!("{}-share-{}.txt", address.to_checksum(), share.index);
+    let paths: Vec<PathBuf> = shares.iter().map(|share| dir.join(name(share))).collect();
+    if let Some(path) = paths.iter().find(|path| path.exists()) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+
+    let mut temporary = Vec::with_capacity(shares.len());
+    for share in shares {
+        let path = dir.join(format!(".{}.tmp", name(share)));
+        let written = write_new_file(&path, &share_file_contents(address, share, count, mnemonic));
+        temporary.push(path);
+        if let Err(e) = written {
+            remove_files(&temporary);
+            return Err(e);
+        }
+    }
+    for (done, (from, to)) in temporary.iter().zip(&paths).enumerate() {
+        if let Err(e) = fs::rename(from, to) {
+            remove_files(&paths[..done]);
+            remove_files(&temporary[done..]);
+            return Err(e);
+        }
+    }
+    Ok(paths)
+}
+
+/// Creates `path` (mode 0600 on Unix), writes `contents` and syncs it.
+fn write_new_file(path: &Path, contents: &str) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+/// Best-effort cleanup after a failed write.
+fn remove_files(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Errors rebuilding a key from shares.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ShamirError {
+    #[error("invalid share: {0}")]
+    InvalidShare(String),
+    #[error("shares come from splits with different thresholds")]
+    ThresholdMismatch,
+    #[error("share {0} given twice")]
+    DuplicateShare(u8),
+    #[error("need {needed} shares to rebuild the key, got {got}")]
+    NotEnoughShares { needed: u8, got: usize },
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1, branch-free.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        a = (a << 1) ^ (0x1b & (a >> 7).wrapping_neg());
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8): a^254. Only used on public share indices.
+fn gf_inv(a: u8) -> u8 {
+    let (mut result, mut base) = (1, a);
+    for bit in 0..8 {
+        if (254 >> bit) & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: [u8; 32] = [0xa5; 32];
+
+    #[test]
+    fn test_field() {
+        // FIPS-197 section 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        for a in 1..=255 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_any_threshold_subset_rebuilds() {
+        let shares = ShareSpec::new(3, 5).unwrap().split(&SECRET);
+        assert_eq!(shares.len(), 5);
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                    assert_eq!(*combine_shares(&subset).unwrap(), SECRET);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_combine_errors() {
+        let shares = ShareSpec::new(2, 3).unwrap().split(&SECRET);
+        assert_eq!(
+            combine_shares(&shares[..1]),
+            Err(ShamirError::NotEnoughShares { needed: 2, got: 1 })
+        );
+        assert_eq!(
+            combine_shares(&[shares[1].clone(), shares[1].clone()]),
+            Err(ShamirError::DuplicateShare(2))
+        );
+        let other = ShareSpec::new(3, 3).unwrap().split(&SECRET);
+        assert_eq!(
+            combine_shares(&[shares[0].clone(), other[1].clone()]),
+            Err(ShamirError::ThresholdMismatch)
+        );
+    }
+
+    #[test]
+    fn test_share_encodings_round_trip() {
+        let shares = ShareSpec::new(2, 3).unwrap().split(&SECRET);
+        let text: Share = shares[0].to_text().parse().unwrap();
+        let mnemonic = shares[2].to_mnemonic();
+        assert_eq!(mnemonic.split_whitespace().count(), 25);
+        assert!(mnemonic.starts_with("2-3 "));
+        let words: Share = mnemonic.parse().unwrap();
+        assert_eq!(*combine_shares(&[text, words]).unwrap(), SECRET);
+
+        assert!(format!("{:?}", shares[0]).contains("<redacted>"));
+        assert!("2-1-abcd".parse::<Share>().is_err());
+        assert!("1-1-".parse::<Share>().is_err());
+    }
+
+    #[test]
+    fn test_share_file_round_trip() {
+        let address = Address::from_bytes([0x11; 20]);
+        let share = &ShareSpec::new(2, 2).unwrap().split(&SECRET)[1];
+        let contents = share_file_contents(&address, share, 2, true);
+        let (parsed_address, parsed) = parse_share_file(&contents).unwrap();
+        assert_eq!(parsed_address, address.to_checksum());
+        assert_eq!((parsed.threshold(), parsed.index()), (2, 2));
+        assert_eq!(parsed.to_text(), share.to_text());
+    }
+
+    #[test]
+    fn test_write_share_files_all_or_nothing() {
+        let dir = std::env::temp_dir().join(format!("eth-vanity-shares-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let address = Address::from_bytes([0x22; 20]);
+        let shares = ShareSpec::new(2, 3).unwrap().split(&SECRET);
+
+        let paths = write_share_files(&dir, &address, &shares, 3, false).unwrap();
+        assert_eq!(paths.len(), 3);
+        let contents = fs::read_to_string(&paths[1]).unwrap();
+        assert_eq!(parse_share_file(&contents).unwrap().1.to_text(), shares[1].to_text());
+
+        // Share 2 exists now, so a second write must fail without adding files
+        fs::remove_file(&paths[0]).unwrap();
+        fs::remove_file(&paths[2]).unwrap();
+        assert!(write_share_files(&dir, &address, &shares, 3, false).is_err());
+        let left: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().path()).collect();
+        assert_eq!(left, vec![paths[1].clone()]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_share_spec() {
+        let spec: ShareSpec = "2-of-3".parse().unwrap();
+        assert_eq!((spec.threshold(), spec.count()), (2, 3));
+        assert_eq!(spec.to_string(), "2-of-3");
+        assert!("1-of-3".parse::<ShareSpec>().is_err());
+        assert!("4-of-3".parse::<ShareSpec>().is_err());
+        assert!("2/3".parse::<ShareSpec>().is_err());
+        assert!("2-of-256".parse::<ShareSpec>().is_err());
+    }
+}
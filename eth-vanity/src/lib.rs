@@ -8,15 +8,20 @@
 //! - `matcher`: Pattern matching strategies
 //! - `worker`: Parallel execution and worker pool management
 //! - `config`: Runtime configuration
+//! - `verify`: Independent re-derivation of results (k256 + sha3)
+//! - `selftest`: Known-answer checks of every backend
 
 pub mod config;
 pub mod crypto;
 pub mod matcher;
+pub mod selftest;
+pub mod verify;
 pub mod worker;
 
 pub use config::Config;
-pub use crypto::{Address, Keypair};
-pub use matcher::{MatchResult, Pattern, PatternType};
+pub use crypto::{contract_address, Address, AddressFormat, Entropy, Keypair};
+pub use matcher::{MatchResult, Pattern, PatternType, Target};
+pub use verify::{Verifier, VerifyError};
 pub use worker::{VanityResult, WorkerPool};
 
 #[cfg(feature = "gpu")]
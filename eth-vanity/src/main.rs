@@ -4,16 +4,50 @@
 //!   eth_vanity -p dead           # Find address starting with "dead"
 //!   eth_vanity -p beef -t suffix # Find address ending with "beef"
 //!   eth_vanity -p cafe -t contains -n 5 # Find 5 addresses containing "cafe"
+//!   eth_vanity -p c0de --contract-nonce 0 # First deployed contract starts with "c0de"
+//!   eth_vanity -p Kitty -c --network tron # Tron address starts with "TKitty"
+//!   eth_vanity -p qqq --bech32-hrp evmos  # Evmos address starts with "evmos1qqq"
+//!   eth_vanity -p cafe --network filecoin # FEVM address starts with "f410fcafe"
+//!   eth_vanity -p CAFE -t contains --network icap # ICAP (XE..) contains "CAFE"
+//!   eth_vanity -p 5afe --eoa-pattern 00 --safe-factory <addr> --safe-init-code-hash <hash>
+//!                                        # 1/1 Safe starts with "5afe", its owner with "00"
+//!   eth_vanity -p dead --keystore-dir ./keys # Save the key as an encrypted keystore
+//!   eth_vanity -p dead --encrypt-to <pubkey>  # Seal results to an offline recipient
+//!   eth_vanity decrypt -i identity.key out.txt # ...and open them on that machine
+//!   eth_vanity self-test                  # Check every backend against known answers
+//!   eth_vanity -p dead --entropy-file -  # Mix dice rolls typed on stdin into the RNG
+//!   eth_vanity -p dead --mnemonic 12 # Key restorable from a 12-word seed phrase
+//!   eth_vanity -p dead --shares 2-of-3 --share-dir ./shares # Split the key among custodians
+//!   eth_vanity recover a-share-1.txt a-share-3.txt # ...and rebuild it from any two
+//!   eth_vanity -p dead --xpub xpub6... # Child index of a watch-only xpub; no key here
 
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Read as _, Write as _};
 use std::process;
 use std::time::Duration;
 
 use clap::Parser;
 
-use eth_vanity::{Config, Pattern, WorkerPool};
+use eth_vanity::config::Command;
+use eth_vanity::selftest::{self, Check};
+use eth_vanity::crypto::{
+    combine_shares, encrypt_keystore, parse_share_file, write_keystore_file, write_share_files,
+    Identity, Keypair, PrivateKey, SecretText,
+};
+use eth_vanity::{Address, AddressFormat, Config, Entropy, Pattern, Verifier, WorkerPool};
+use zeroize::Zeroizing;
 
 fn main() {
-    let config = Config::parse();
+    let mut config = Config::parse();
+
+    if let Some(ref command) = config.command {
+        if let Err(e) = run_command(command) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
 
     // Validate configuration
     if let Err(e) = config.validate() {
@@ -21,6 +55,36 @@ fn main() {
         process::exit(1);
     }
 
+    // Known answers for every backend in use; a failing GPU is dropped,
+    // a failing CPU check stops the search
+    if let Err(e) = selftest::before_search(&mut config) {
+        eprintln!("Refusing to mine: {}", e);
+        process::exit(1);
+    }
+
+    let format = config.address_format().expect("validated");
+
+    // Ask for the keystore passphrase before spending time on the search
+    let passphrase = if config.keystore_dir.is_some() {
+        match read_passphrase(&config) {
+            Ok(passphrase) => Some(passphrase),
+            Err(e) => {
+                eprintln!("Keystore passphrase error: {}", e);
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let entropy = match read_entropy(&config) {
+        Ok(entropy) => entropy,
+        Err(e) => {
+            eprintln!("Entropy error: {}", e);
+            process::exit(1);
+        }
+    };
+
     // Create the pattern
     let pattern = if let Some(ref suffix) = config.normalized_suffix() {
         Pattern::new_prefix_and_suffix(
@@ -34,7 +98,8 @@ fn main() {
             config.pattern_type,
             config.case_sensitive,
         )
-    };
+    }
+    .with_format(format.clone());
 
     // Print startup info
     println!("Ethereum Vanity Address Generator");
@@ -44,7 +109,21 @@ fn main() {
     } else {
         format!("{} ({})", pattern.pattern(), pattern.pattern_type())
     };
+    let target = config.target();
     println!("Pattern:    {}", pattern_display);
+    println!("Match:      {}", target);
+    if !format.is_hex() {
+        println!("Network:    {} ({}...)", format.name(), format.fixed_prefix());
+    }
+    if !matches!(entropy, Entropy::Os) {
+        println!("Entropy:    {}", entropy);
+    }
+    if let Some(spec) = config.mnemonic_spec() {
+        println!("Mnemonic:   {}", spec);
+    }
+    if let Some(spec) = config.xpub_spec() {
+        println!("Xpub:       {}", spec);
+    }
     println!("Difficulty: {}", pattern.difficulty_description());
     println!("Workers:    {}", config.worker_count());
 
@@ -67,17 +146,43 @@ fn main() {
     println!();
 
     // Create worker pool
-    #[cfg(feature = "gpu")]
-    let pool = WorkerPool::new_with_gpu(
-        config.worker_count(),
-        pattern,
-        config.gpu_enabled(),
-        config.gpu_device_index(),
-        config.gpu_work_size(),
-    );
+    let pool = match config.xpub_spec() {
+        Some(spec) => {
+            #[cfg(feature = "gpu")]
+            if config.gpu_enabled() {
+                eprintln!("Warning: GPU matching does not support --xpub.");
+                eprintln!("Continuing with CPU-only workers.");
+            }
+            WorkerPool::new_xpub(config.worker_count(), pattern, target, spec)
+        }
+        #[cfg(feature = "gpu")]
+        None => WorkerPool::new_with_gpu(
+            config.worker_count(),
+            pattern,
+            target,
+            entropy,
+            config.mnemonic_spec(),
+            config.mlock,
+            config.gpu_enabled(),
+            config.gpu_device_index(),
+            config.gpu_work_size(),
+        ),
+        #[cfg(not(feature = "gpu"))]
+        None => WorkerPool::new(
+            config.worker_count(),
+            pattern,
+            target,
+            entropy,
+            config.mnemonic_spec(),
+            config.mlock,
+        ),
+    };
 
-    #[cfg(not(feature = "gpu"))]
-    let pool = WorkerPool::new(config.worker_count(), pattern);
+    // Every result is re-derived independently before it is reported
+    let mut verifier = Verifier::new(pool.pattern().clone(), pool.target().clone());
+    if let Some(ref xpub) = config.xpub {
+        verifier = verifier.with_xpub(xpub.clone());
+    }
 
     // Set up ctrl-c handler
     let stop_flag = pool.stop_flag_clone();
@@ -92,14 +197,28 @@ fn main() {
         // Wait for result or timeout for progress report
         match pool.wait_for_result(report_interval) {
             Some(result) => {
-                found += 1;
-                print_result(&result, found);
+                if let Err(e) = verifier.verify(&result) {
+                    eprintln!("FATAL: result failed independent verification, not reported");
+                    eprintln!("  {}", e);
+                    eprintln!("  This is a bug in key derivation or matching; please report it.");
+                    pool.stop();
+                    process::exit(2);
+                }
+                let passphrase = passphrase.as_deref().map(String::as_str);
+                // A key that could not be stored is printed instead and not counted
+                if print_result(&result, found + 1, &config, &format, passphrase) {
+                    found += 1;
+                }
 
                 if config.count > 0 && found >= config.count {
                     println!("\nTarget reached! Found {} address(es).", found);
                     break;
                 }
             }
+            None if pool.is_finished() => {
+                println!("\nSearched every child index below the xpub.");
+                break;
+            }
             None => {
                 // Timeout - print progress
                 print_progress(&pool);
@@ -126,12 +245,350 @@ fn main() {
     pool.join();
 }
 
-fn print_result(result: &eth_vanity::VanityResult, index: usize) {
-    println!("=== Match #{} ===", index);
-    println!("Address:     {}", result.address);
-    println!("Private Key: {}", result.private_key);
-    println!("Worker:      {}", result.worker_id);
+/// Prints a result. Returns false if its key could not be stored where it
+/// was asked to go; the key is printed instead, so it is not lost.
+fn print_result(
+    result: &eth_vanity::VanityResult,
+    index: usize,
+    config: &Config,
+    format: &AddressFormat,
+    passphrase: Option<&str>,
+) -> bool {
+    // Holds the private key unless it goes to a keystore; zeroed on drop
+    let mut out = SecretText::with_capacity(1024);
+    if config.mlock {
+        if let Err(e) = out.lock() {
+            eprintln!("Warning: could not lock result buffer in memory: {}", e);
+        }
+    }
+    writeln!(out, "=== Match #{} ===", index).unwrap();
+    writeln!(out, "Address:     {}", result.address).unwrap();
+    if let Some(ref icap) = result.icap {
+        writeln!(out, "ICAP:        {}", icap).unwrap();
+    }
+    if let (Some(contract), Some(nonce)) = (&result.contract_address, config.contract_nonce) {
+        writeln!(out, "Contract:    {} (CREATE at nonce {})", contract, nonce).unwrap();
+    }
+    if let Some(ref safe) = result.safe_address {
+        writeln!(out, "Safe:        {} (1/1, owner = Address)", safe).unwrap();
+        writeln!(out, "Salt nonce:  {}", config.safe_salt_nonce).unwrap();
+    }
+    // The matched address (EOA, contract or Safe) in the pattern's format
+    if let Some(ref formatted) = result.formatted_address {
+        writeln!(out, "{:<12} {}", format!("{}:", format.name()), formatted).unwrap();
+    }
+    if let Some(ref origin) = result.mnemonic {
+        if config.withhold_keys() {
+            writeln!(out, "Mnemonic:    <withheld>").unwrap();
+        } else {
+            writeln!(out, "Mnemonic:    {}", **origin.phrase()).unwrap();
+        }
+        writeln!(out, "Path:        {}", origin.path()).unwrap();
+    }
+    if let Some(ref path) = result.xpub_path {
+        let indices: Vec<String> = path.indices().iter().map(u32::to_string).collect();
+        writeln!(out, "Child path:  {} (below --xpub)", indices.join("/")).unwrap();
+    }
+    let mut stored = true;
+    match (&result.private_key, &config.keystore_dir, passphrase) {
+        // Xpub mode: the key is derived from the xprv, elsewhere
+        (None, ..) => {}
+        _ if config.withhold_keys() => writeln!(
+            out,
+            "Private Key: <withheld: deterministic test key, see --print-deterministic-keys>"
+        )
+        .unwrap(),
+        (Some(key), ..) if config.shares.is_some() => match save_shares(key, result, config) {
+            Ok(paths) => {
+                let spec = config.shares.expect("checked");
+                writeln!(out, "Shares:      {} (any {} rebuild the key)", spec, spec.threshold())
+                    .unwrap();
+                for path in paths {
+                    writeln!(out, "             {}", path.display()).unwrap();
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write shares (key printed, match not counted): {}", e);
+                write_private_key(&mut out, key);
+                stored = false;
+            }
+        },
+        (Some(key), Some(dir), Some(passphrase)) => {
+            match save_keystore(key, result, dir, passphrase, config) {
+                Ok(path) => writeln!(out, "Keystore:    {}", path.display()).unwrap(),
+                Err(e) => {
+                    eprintln!("Failed to write keystore (key printed, match not counted): {}", e);
+                    write_private_key(&mut out, key);
+                    stored = false;
+                }
+            }
+        }
+        (Some(key), ..) => write_private_key(&mut out, key),
+    }
+    writeln!(out, "Worker:      {}", result.worker_id).unwrap();
+
+    // Only the offline recipient can read a sealed result, address included
+    match config.encrypt_to {
+        Some(ref recipient) => {
+            println!("=== Match #{} ===", index);
+            println!("Sealed:      {}", hex::encode(recipient.seal(out.as_str().as_bytes())));
+        }
+        None => {
+            if let Err(e) = write_secret(out.as_str().as_bytes()) {
+                eprintln!("Failed to print result: {}", e);
+            }
+        }
+    }
     println!();
+    stored
+}
+
+/// Writes secret output to stdout as is. `print!` would format it into
+/// std's stdout buffer first, a copy that is never zeroed.
+fn write_secret(bytes: &[u8]) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout.flush()?;
+    stdout.write_all(bytes)?;
+    stdout.flush()
+}
+
+/// Writes the key's line to `out` byte by byte, so no unlocked hex copy of
+/// the key is made.
+fn write_private_key(out: &mut SecretText, key: &PrivateKey) {
+    write!(out, "Private Key: ").unwrap();
+    for byte in key.as_bytes() {
+        write!(out, "{:02x}", byte).unwrap();
+    }
+    writeln!(out).unwrap();
+}
+
+fn run_command(command: &Command) -> io::Result<()> {
+    match command {
+        Command::Keygen { output } => {
+            let identity = Identity::generate();
+            let mut options = fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            writeln!(options.open(output)?, "{}", *identity.to_hex())?;
+            println!("Identity:    {}", output.display());
+            println!("Recipient:   {}", identity.recipient());
+            println!("\nKeep the identity file offline; pass the recipient to --encrypt-to.");
+            Ok(())
+        }
+        Command::Decrypt { identity, input } => {
+            let identity: Identity = Zeroizing::new(fs::read_to_string(identity)?)
+                .parse()
+                .map_err(|e: String| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let text = Zeroizing::new(match input {
+                Some(path) => fs::read_to_string(path)?,
+                None => io::read_to_string(io::stdin())?,
+            });
+            let mut opened = 0;
+            // Accept the search output as-is or bare hex lines
+            for line in text.lines() {
+                let line = line.trim();
+                let sealed = line.strip_prefix("Sealed:").unwrap_or(line).trim();
+                let bytes = match hex::decode(sealed) {
+                    Ok(bytes) if !bytes.is_empty() => bytes,
+                    _ => continue,
+                };
+                match identity.open(&bytes) {
+                    Ok(plaintext) => {
+                        write_secret(&plaintext)?;
+                        println!();
+                        opened += 1;
+                    }
+                    Err(e) => eprintln!("Skipping sealed result: {}", e),
+                }
+            }
+            if opened == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "no sealed results found"));
+            }
+            Ok(())
+        }
+        Command::SelfTest => {
+            let mut failed = 0;
+            for check in Check::ALL {
+                match check.run() {
+                    Ok(()) => println!("ok    {}", check),
+                    Err(e) => {
+                        println!("FAIL  {}", e);
+                        failed += 1;
+                    }
+                }
+            }
+            #[cfg(feature = "gpu")]
+            for (index, name) in eth_vanity::worker::gpu::list_devices().iter().enumerate() {
+                match eth_vanity::worker::gpu::self_test_device(index, 65536) {
+                    Ok(()) => println!("ok    OpenCL kernel on device {} ({})", index, name),
+                    Err(e) => {
+                        println!("FAIL  device {} ({}): {}", index, name, e);
+                        failed += 1;
+                    }
+                }
+            }
+            if failed > 0 {
+                let message = format!("{} self-test check(s) failed", failed);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+            Ok(())
+        }
+        Command::Recover { shares: paths } => {
+            let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+            let mut address: Option<String> = None;
+            let mut shares = Vec::with_capacity(paths.len());
+            for path in paths {
+                let contents = Zeroizing::new(fs::read_to_string(path)?);
+                let (share_address, share) = parse_share_file(&contents)
+                    .map_err(|e| invalid(format!("{}: {}", path.display(), e)))?;
+                match address {
+                    Some(ref a) if !a.eq_ignore_ascii_case(&share_address) => {
+                        return Err(invalid(format!(
+                            "{} is a share of {}, not {}",
+                            path.display(),
+                            share_address,
+                            a
+                        )));
+                    }
+                    Some(_) => {}
+                    None => address = Some(share_address),
+                }
+                shares.push(share);
+            }
+            let address = address.expect("at least one share file");
+
+            let secret = combine_shares(&shares).map_err(|e| invalid(e.to_string()))?;
+            if secp256k1::SecretKey::from_slice(secret.as_slice()).is_err() {
+                return Err(invalid("shares do not rebuild a valid private key".into()));
+            }
+            let keypair = Keypair::from_secret_key(&secret);
+            if !keypair.address().to_checksum().eq_ignore_ascii_case(&address) {
+                return Err(invalid(format!(
+                    "rebuilt key does not derive {}; a share is wrong or from another key",
+                    address
+                )));
+            }
+            write_secret(keypair.private_key_hex().as_bytes())?;
+            println!();
+            Ok(())
+        }
+    }
+}
+
+/// Splits the key into `--shares` and writes one file per share, after
+/// checking that the shares rebuild it. Writes all share files or none.
+fn save_shares(
+    key: &PrivateKey,
+    result: &eth_vanity::VanityResult,
+    config: &Config,
+) -> io::Result<Vec<std::path::PathBuf>> {
+    let spec = config.shares.expect("--shares");
+    let dir = config.share_dir.as_deref().expect("--shares requires --share-dir");
+    let address = parse_address(&result.address);
+    let shares = spec.split(key.as_bytes());
+    let rebuilt = combine_shares(&shares[shares.len() - spec.threshold() as usize..])
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if *rebuilt != *key.as_bytes() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "shares do not rebuild the key"));
+    }
+    write_share_files(dir, &address, &shares, spec.count(), config.share_mnemonic)
+}
+
+fn parse_address(address: &str) -> Address {
+    let bytes: [u8; 20] = hex::decode(&address[2..])
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .expect("20-byte hex address");
+    Address::from_bytes(bytes)
+}
+
+fn save_keystore(
+    key: &PrivateKey,
+    result: &eth_vanity::VanityResult,
+    dir: &std::path::Path,
+    passphrase: &str,
+    config: &Config,
+) -> io::Result<std::path::PathBuf> {
+    let address = parse_address(&result.address);
+    let json = encrypt_keystore(
+        key.as_bytes(),
+        &address,
+        passphrase,
+        config.keystore_kdf,
+    );
+    write_keystore_file(dir, &address, &json)
+}
+
+/// Reads the keystore passphrase from `--password-fd` or prompts for it twice.
+fn read_passphrase(config: &Config) -> io::Result<Zeroizing<String>> {
+    if let Some(fd) = config.password_fd {
+        let mut contents = Zeroizing::new(String::new());
+        open_fd(fd)?.read_to_string(&mut contents)?;
+        let line = contents.lines().next().unwrap_or_default();
+        return Ok(Zeroizing::new(line.to_string()));
+    }
+    let passphrase = Zeroizing::new(rpassword::prompt_password("Keystore passphrase: ")?);
+    let repeated = Zeroizing::new(rpassword::prompt_password("Repeat passphrase: ")?);
+    if passphrase != repeated {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "passphrases do not match"));
+    }
+    Ok(passphrase)
+}
+
+/// Takes ownership of the inherited file descriptor `fd`.
+#[cfg(unix)]
+fn open_fd(fd: u32) -> io::Result<fs::File> {
+    use std::os::unix::io::{FromRawFd, RawFd};
+
+    let fd = RawFd::try_from(fd)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file descriptor out of range"))?;
+    // SAFETY: F_GETFD only queries the descriptor flags.
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `fd` is open and was handed to this process via --password-fd,
+    // so nothing else in it owns the descriptor.
+    Ok(unsafe { fs::File::from_raw_fd(fd) })
+}
+
+#[cfg(not(unix))]
+fn open_fd(_fd: u32) -> io::Result<fs::File> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "--password-fd is only supported on Unix; omit it to be prompted",
+    ))
+}
+
+/// Largest amount of extra entropy read, so a device like /dev/hwrng works.
+const MAX_ENTROPY_BYTES: u64 = 64 * 1024;
+
+/// Builds the entropy workers draw keys from: the OS RNG, mixed with
+/// `--entropy-file` if given, or a deterministic test seed.
+fn read_entropy(config: &Config) -> io::Result<Entropy> {
+    #[cfg(feature = "deterministic")]
+    if let Some(ref seed) = config.deterministic_seed {
+        return Ok(Entropy::deterministic(seed.as_bytes()));
+    }
+    let path = match config.entropy_file {
+        Some(ref path) => path,
+        None => return Ok(Entropy::Os),
+    };
+    // Preallocated so reading never leaves unzeroed copies behind
+    let mut extra = Zeroizing::new(Vec::with_capacity(MAX_ENTROPY_BYTES as usize));
+    if path.as_os_str() == "-" {
+        eprintln!("Enter extra entropy (e.g. dice rolls), then Ctrl-D:");
+        io::stdin().take(MAX_ENTROPY_BYTES).read_to_end(&mut extra)?;
+    } else {
+        fs::File::open(path)?.take(MAX_ENTROPY_BYTES).read_to_end(&mut extra)?;
+    }
+    if extra.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "entropy file is empty"));
+    }
+    Ok(Entropy::mixed(&extra))
 }
 
 fn print_progress(pool: &WorkerPool) {
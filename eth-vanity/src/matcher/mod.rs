@@ -4,7 +4,11 @@
 //! - Prefix: Match at the start of the address
 //! - Suffix: Match at the end of the address
 //! - Contains: Match anywhere in the address
+//!
+//! A [`Target`] selects whether the EOA or the contract it deploys is matched.
 
 mod pattern;
+mod target;
 
 pub use pattern::{MatchResult, Pattern, PatternType};
+pub use target::Target;
@@ -2,7 +2,7 @@
 
 use std::str::FromStr;
 
-use crate::crypto::Address;
+use crate::crypto::{Address, AddressFormat};
 
 /// The type of pattern matching to perform.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -70,6 +70,8 @@ pub struct Pattern {
     pattern_type: PatternType,
     /// Whether matching is case sensitive
     case_sensitive: bool,
+    /// Address representation the pattern is matched against
+    format: AddressFormat,
 }
 
 impl Pattern {
@@ -87,6 +89,7 @@ impl Pattern {
             suffix: None,
             pattern_type,
             case_sensitive,
+            format: AddressFormat::Hex,
         }
     }
 
@@ -103,9 +106,16 @@ impl Pattern {
             suffix: Some(normalize(suffix.into())),
             pattern_type: PatternType::PrefixAndSuffix,
             case_sensitive,
+            format: AddressFormat::Hex,
         }
     }
 
+    /// Matches against `format` instead of the hex address.
+    pub fn with_format(mut self, format: AddressFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Returns the pattern string.
     pub fn pattern(&self) -> &str {
         &self.pattern
@@ -121,13 +131,22 @@ impl Pattern {
         self.pattern_type
     }
 
+    /// Returns whether matching is case sensitive.
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Returns the address format the pattern is matched against.
+    pub fn format(&self) -> &AddressFormat {
+        &self.format
+    }
+
     /// Matches an address against this pattern.
     #[inline]
     pub fn matches(&self, address: &Address) -> MatchResult {
-        let addr_hex = if self.case_sensitive {
-            address.to_hex()
-        } else {
-            address.to_hex() // Already lowercase
+        let addr_hex = match self.format.match_body(address, self.case_sensitive) {
+            Some(body) => body,
+            None => return MatchResult::NoMatch,
         };
 
         let matched = match self.pattern_type {
@@ -152,9 +171,33 @@ impl Pattern {
     /// For hex patterns:
     /// - Each character has 16 possible values
     /// - Expected attempts = 16^n where n is pattern length
+    ///
+    /// Base58 characters have 58 values (29 for case-insensitive letters
+    /// with both cases), except the first one after the fixed prefix. ICAP
+    /// patterns also need the address to be below 2^155 (1 in 32).
     pub fn estimated_difficulty(&self) -> u64 {
-        let total_len = self.pattern.len() + self.suffix.as_ref().map_or(0, |s| s.len());
-        16u64.saturating_pow(total_len as u32)
+        if self.format.is_hex() {
+            let total_len = self.pattern.len() + self.suffix.as_ref().map_or(0, |s| s.len());
+            return 16u64.saturating_pow(total_len as u32);
+        }
+        let anchored = matches!(
+            self.pattern_type,
+            PatternType::Prefix | PatternType::PrefixAndSuffix
+        );
+        let odds = |(i, c): (usize, char)| {
+            if anchored && i == 0 {
+                self.format.first_symbol_odds(c, self.case_sensitive)
+            } else {
+                self.format.symbol_odds(c, self.case_sensitive)
+            }
+        };
+        let suffix = self.suffix.as_deref().unwrap_or("");
+        self.pattern
+            .chars()
+            .enumerate()
+            .map(odds)
+            .chain(suffix.chars().map(|c| self.format.symbol_odds(c, self.case_sensitive)))
+            .fold(self.format.eligibility_odds(), u64::saturating_mul)
     }
 
     /// Returns a human-readable difficulty estimate.
@@ -213,4 +256,18 @@ mod tests {
         let pattern = Pattern::new("dead", PatternType::Prefix, false);
         assert_eq!(pattern.estimated_difficulty(), 65536); // 16^4
     }
+
+    #[test]
+    fn test_tron_pattern() {
+        // USDT (TRC-20): TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t
+        let addr = make_address("a614f803b6fd780986a42c78ec9c7f77e6ded13c");
+        let tron = |p: &str, t: PatternType, cs: bool| Pattern::new(p, t, cs).with_format(AddressFormat::Tron);
+        assert!(tron("R7NH", PatternType::Prefix, true).matches(&addr).is_match());
+        assert!(!tron("r7nh", PatternType::Prefix, true).matches(&addr).is_match());
+        assert!(tron("r7nh", PatternType::Prefix, false).matches(&addr).is_match());
+        assert!(tron("Lj6t", PatternType::Suffix, true).matches(&addr).is_match());
+        // 'R' after T: 1 of 25; '7': 1 of 58; 'n' and 'h': either case, 1 of 29.
+        assert_eq!(tron("r7nh", PatternType::Prefix, false).estimated_difficulty(), 25 * 58 * 29 * 29);
+        assert_eq!(tron("R7NH", PatternType::Prefix, true).estimated_difficulty(), 25 * 58 * 58 * 58);
+    }
 }
@@ -0,0 +1,194 @@
+//! Which address derived from a candidate key the pattern is matched against.
+
+use crate::crypto::{contract_address, Address, SafeDeployment};
+
+use super::{MatchResult, Pattern};
+
+/// The address a candidate keypair is judged by.
+#[derive(Debug, Clone, Default)]
+pub enum Target {
+    /// The EOA address itself
+    #[default]
+    Eoa,
+    /// The contract the EOA deploys with CREATE at `nonce`, optionally with
+    /// a second pattern on the EOA address
+    Contract {
+        nonce: u64,
+        eoa_pattern: Option<Pattern>,
+    },
+    /// The 1/1 Safe owned by the EOA, optionally with a second pattern on
+    /// the EOA address
+    Safe {
+        deployment: Box<SafeDeployment>,
+        eoa_pattern: Option<Pattern>,
+    },
+}
+
+impl Target {
+    /// Returns true if only the EOA address is matched.
+    pub fn is_eoa(&self) -> bool {
+        matches!(self, Target::Eoa)
+    }
+
+    /// Matches a candidate EOA against `pattern` according to this target.
+    #[inline]
+    pub fn matches(&self, pattern: &Pattern, eoa: &Address) -> MatchResult {
+        match self {
+            Target::Eoa => pattern.matches(eoa),
+            Target::Contract { nonce, eoa_pattern } => {
+                // The EOA check is free; only hash the RLP when it passes.
+                if !eoa_matches(eoa_pattern, eoa) {
+                    return MatchResult::NoMatch;
+                }
+                pattern.matches(&contract_address(eoa, *nonce))
+            }
+            Target::Safe {
+                deployment,
+                eoa_pattern,
+            } => {
+                if !eoa_matches(eoa_pattern, eoa) {
+                    return MatchResult::NoMatch;
+                }
+                pattern.matches(&deployment.address(eoa))
+            }
+        }
+    }
+
+    /// Returns the address the pattern was matched against for a matched EOA.
+    pub fn matched_address(&self, eoa: &Address) -> Address {
+        match self {
+            Target::Eoa => *eoa,
+            Target::Contract { nonce, .. } => contract_address(eoa, *nonce),
+            Target::Safe { deployment, .. } => deployment.address(eoa),
+        }
+    }
+
+    /// Returns the contract address for a matched EOA, if targeting contracts.
+    pub fn contract_address(&self, eoa: &Address) -> Option<Address> {
+        match self {
+            Target::Eoa => None,
+            Target::Contract { nonce, .. } => Some(contract_address(eoa, *nonce)),
+            Target::Safe { .. } => None,
+        }
+    }
+
+    /// Returns the Safe address for a matched EOA, if targeting Safes.
+    pub fn safe_address(&self, eoa: &Address) -> Option<Address> {
+        match self {
+            Target::Safe { deployment, .. } => Some(deployment.address(eoa)),
+            _ => None,
+        }
+    }
+}
+
+/// Returns true if there is no EOA pattern or the EOA matches it.
+#[inline]
+fn eoa_matches(eoa_pattern: &Option<Pattern>, eoa: &Address) -> bool {
+    match eoa_pattern {
+        Some(eoa_pattern) => eoa_pattern.matches(eoa).is_match(),
+        None => true,
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Eoa => write!(f, "EOA address"),
+            Target::Contract {
+                nonce,
+                eoa_pattern: None,
+            } => write!(f, "contract address (CREATE, nonce {})", nonce),
+            Target::Contract {
+                nonce,
+                eoa_pattern: Some(eoa_pattern),
+            } => write!(
+                f,
+                "contract address (CREATE, nonce {}) + EOA {} ({})",
+                nonce,
+                eoa_pattern.pattern(),
+                eoa_pattern.pattern_type()
+            ),
+            Target::Safe {
+                eoa_pattern: None, ..
+            } => write!(f, "1/1 Safe address"),
+            Target::Safe {
+                eoa_pattern: Some(eoa_pattern),
+                ..
+            } => write!(
+                f,
+                "1/1 Safe address + EOA {} ({})",
+                eoa_pattern.pattern(),
+                eoa_pattern.pattern_type()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::PatternType;
+
+    fn make_address(hex_str: &str) -> Address {
+        let bytes: [u8; 20] = hex::decode(hex_str).unwrap().try_into().unwrap();
+        Address::from_bytes(bytes)
+    }
+
+    #[test]
+    fn test_contract_target_matches_contract_address() {
+        // nonce 0 contract of this deployer is 0xcd234a47...
+        let eoa = make_address("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        let pattern = Pattern::new("cd23", PatternType::Prefix, false);
+
+        assert!(!Target::Eoa.matches(&pattern, &eoa).is_match());
+        let target = Target::Contract {
+            nonce: 0,
+            eoa_pattern: None,
+        };
+        assert!(target.matches(&pattern, &eoa).is_match());
+        assert_eq!(
+            target.contract_address(&eoa).unwrap().to_hex(),
+            "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"
+        );
+    }
+
+    #[test]
+    fn test_contract_target_with_eoa_pattern() {
+        let eoa = make_address("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        let pattern = Pattern::new("cd23", PatternType::Prefix, false);
+
+        let both = Target::Contract {
+            nonce: 0,
+            eoa_pattern: Some(Pattern::new("6ac7", PatternType::Prefix, false)),
+        };
+        assert!(both.matches(&pattern, &eoa).is_match());
+
+        let wrong_eoa = Target::Contract {
+            nonce: 0,
+            eoa_pattern: Some(Pattern::new("beef", PatternType::Prefix, false)),
+        };
+        assert!(!wrong_eoa.matches(&pattern, &eoa).is_match());
+    }
+
+    #[test]
+    fn test_safe_target_checks_both_patterns() {
+        let eoa = make_address("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+        let deployment = SafeDeployment::new([0x11; 20], [0x22; 32], [0; 20], [0; 32]);
+        let safe = deployment.address(&eoa);
+        let pattern = Pattern::new(&safe.to_hex()[..4], PatternType::Prefix, false);
+
+        let deployment = Box::new(deployment);
+        let target = Target::Safe {
+            deployment: deployment.clone(),
+            eoa_pattern: Some(Pattern::new("6ac7", PatternType::Prefix, false)),
+        };
+        assert!(target.matches(&pattern, &eoa).is_match());
+        assert_eq!(target.safe_address(&eoa), Some(safe));
+
+        let wrong_eoa = Target::Safe {
+            deployment,
+            eoa_pattern: Some(Pattern::new("beef", PatternType::Prefix, false)),
+        };
+        assert!(!wrong_eoa.matches(&pattern, &eoa).is_match());
+    }
+}
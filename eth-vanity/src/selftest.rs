@@ -0,0 +1,408 @@
+//! Known-answer self-test of the backends, run at startup and by `self-test`.
+//!
+//! A backend whose output differs from the vectors is not used for mining.
+//! The CPU checks here cover key derivation, contract and Safe addresses,
+//! BIP39/BIP32 derivation, xpub child derivation, GPU key reconstruction and
+//! the result verifier;
+//! the OpenCL kernel is checked per device by `GpuWorker::self_test`.
+//! [`before_search`] runs both for the backends a search uses.
+
+use std::fmt;
+
+use secp256k1::Secp256k1;
+use zeroize::Zeroizing;
+
+use crate::config::Config;
+use crate::crypto::{
+    add_scalar_mod_n, contract_address, mnemonic_key, Address, DerivationPath,
+    ExtendedPrivateKey, ExtendedPublicKey, Keypair, MnemonicOrigin, SafeDeployment, ACCOUNT_PATH,
+};
+use crate::matcher::{Pattern, PatternType, Target};
+use crate::verify::Verifier;
+use crate::worker::VanityResult;
+
+/// Private keys 1, 2, 3 and n - 1 and their addresses.
+const KEYPAIR_VECTORS: [(&str, &str); 4] = [
+    (
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF",
+    ),
+    (
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "0x6813Eb9362372EEF6200f3b1dbC3f819671cBA69",
+    ),
+    (
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        "0x80C0dbf239224071c59dD8970ab9d542E3414aB2",
+    ),
+];
+
+/// CREATE addresses of deployer 0x6ac7..dbf0 at nonces 0 and 3.
+const CREATE_VECTORS: [(u64, &str); 2] = [
+    (0, "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d"),
+    (3, "0xffFd933A0bC612844eaF0C6Fe3E5b8E9B6C1d19c"),
+];
+const CREATE_DEPLOYER: &str = "6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+
+/// 1/1 Safe of the key-1 address from factory 0x4e1D..ec67, init code hash
+/// 0x2222..22, no fallback handler and saltNonce 0.
+const SAFE_FACTORY: &str = "4e1dcf7ad4e460cfd30791ccc4f9c8a4f820ec67";
+const SAFE_ADDRESS: &str = "0xDeB70FDa59bbab97933Fc4e6EB295E54C30bC24C";
+
+/// Phrases (empty passphrase) and the addresses of their keys at a path.
+const MNEMONIC_VECTORS: [(&str, &str, &str); 3] = [
+    (
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         abandon about",
+        "m/44'/60'/0'/0/0",
+        "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+    ),
+    (
+        "test test test test test test test test test test test junk",
+        "m/44'/60'/0'/0/0",
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    ),
+    (
+        "test test test test test test test test test test test junk",
+        "m/44'/60'/0'/0/1",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    ),
+];
+
+/// `(base, offset, base + offset mod n)` around the curve order n.
+const SCALAR_VECTORS: [(&str, u32, &str); 5] = [
+    (
+        "000000000000000000000000000000000000000000000000ffffffffffffffff",
+        1,
+        "0000000000000000000000000000000000000000000000010000000000000000",
+    ),
+    (
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd036413f",
+        1,
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+    ),
+    (
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        1,
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    ),
+    (
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        2,
+        "0000000000000000000000000000000000000000000000000000000000000001",
+    ),
+    (
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+        u32::MAX,
+        "00000000000000000000000000000000000000000000000000000000fffffffe",
+    ),
+];
+
+/// A CPU backend with known-answer vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// Private key to address (libsecp256k1 + tiny-keccak)
+    Keypair,
+    /// CREATE contract address
+    Create,
+    /// 1/1 Safe CREATE2 address
+    Safe,
+    /// BIP39 seed and BIP32 path derivation
+    Mnemonic,
+    /// Public derivation of normal children, which xpub mode searches
+    Xpub,
+    /// `base + offset mod n`, which rebuilds GPU hits
+    ScalarOffset,
+    /// The independent result verifier (k256 + sha3)
+    Verifier,
+}
+
+impl Check {
+    /// Every CPU check, in the order they run.
+    pub const ALL: [Check; 7] = [
+        Check::Keypair,
+        Check::Create,
+        Check::Safe,
+        Check::Mnemonic,
+        Check::Xpub,
+        Check::ScalarOffset,
+        Check::Verifier,
+    ];
+
+    /// Runs the check's vectors.
+    pub fn run(self) -> Result<(), SelfTestError> {
+        match self {
+            Check::Keypair => {
+                for (key, address) in KEYPAIR_VECTORS {
+                    let keypair = Keypair::from_secret_key(&decode(key));
+                    self.expect(key, address, &keypair.address().to_checksum())?;
+                }
+            }
+            Check::Create => {
+                let deployer = Address::from_bytes(decode(CREATE_DEPLOYER));
+                for (nonce, address) in CREATE_VECTORS {
+                    let got = contract_address(&deployer, nonce).to_checksum();
+                    self.expect(&format!("nonce {}", nonce), address, &got)?;
+                }
+            }
+            Check::Safe => {
+                let got = safe_deployment().address(key_one().address());
+                self.expect("owner = key 1", SAFE_ADDRESS, &got.to_checksum())?;
+            }
+            Check::Mnemonic => {
+                for (phrase, path, address) in MNEMONIC_VECTORS {
+                    let got = match mnemonic_key(phrase, &path.parse().expect("valid path")) {
+                        Ok(key) => {
+                            let keypair = Keypair::from_secret_key(&key.secret_bytes());
+                            keypair.address().to_checksum()
+                        }
+                        Err(e) => e,
+                    };
+                    self.expect(path, address, &got)?;
+                }
+            }
+            Check::Xpub => {
+                // The same addresses, from the phrase's account xpub
+                let secp = Secp256k1::verification_only();
+                for (phrase, path, address) in MNEMONIC_VECTORS {
+                    let path: DerivationPath = path.parse().expect("valid path");
+                    let index = *path.indices().last().expect("child index");
+                    let got = match account_xpub(phrase).derive_child(&secp, index) {
+                        Some(child) => Keypair::derive_address(child.public_key()).to_checksum(),
+                        None => "invalid child".to_string(),
+                    };
+                    self.expect(&format!("{}/{}", phrase, index), address, &got)?;
+                }
+            }
+            Check::ScalarOffset => {
+                for (base, offset, sum) in SCALAR_VECTORS {
+                    let got = hex::encode(add_scalar_mod_n(&decode(base), offset));
+                    self.expect(&format!("{} + {}", base, offset), sum, &got)?;
+                }
+            }
+            Check::Verifier => {
+                let any = Pattern::new("", PatternType::Contains, false);
+                let targets = [
+                    Target::Eoa,
+                    Target::Contract {
+                        nonce: 3,
+                        eoa_pattern: None,
+                    },
+                    Target::Safe {
+                        deployment: Box::new(safe_deployment()),
+                        eoa_pattern: None,
+                    },
+                ];
+                let keypair = key_one();
+                for target in targets {
+                    let result = VanityResult {
+                        private_key: Some(keypair.private_key().clone()),
+                        address: keypair.address().to_checksum(),
+                        icap: None,
+                        contract_address: target
+                            .contract_address(keypair.address())
+                            .map(|a| a.to_checksum()),
+                        safe_address: target
+                            .safe_address(keypair.address())
+                            .map(|a| a.to_checksum()),
+                        formatted_address: None,
+                        mnemonic: None,
+                        xpub_path: None,
+                        worker_id: 0,
+                    };
+                    let got = match Verifier::new(any.clone(), target.clone()).verify(&result) {
+                        Ok(()) => "ok".to_string(),
+                        Err(e) => e.to_string(),
+                    };
+                    self.expect(&target.to_string(), "ok", &got)?;
+                }
+
+                let (phrase, path, _) = MNEMONIC_VECTORS[0];
+                let path = path.parse().expect("valid path");
+                let keypair = Keypair::from_secret_key(
+                    &mnemonic_key(phrase, &path).expect("checked above").secret_bytes(),
+                );
+                let result = VanityResult {
+                    private_key: Some(keypair.private_key().clone()),
+                    address: keypair.address().to_checksum(),
+                    icap: None,
+                    contract_address: None,
+                    safe_address: None,
+                    formatted_address: None,
+                    mnemonic: Some(MnemonicOrigin::new(Zeroizing::new(phrase.into()), path)),
+                    xpub_path: None,
+                    worker_id: 0,
+                };
+                let got = match Verifier::new(any.clone(), Target::Eoa).verify(&result) {
+                    Ok(()) => "ok".to_string(),
+                    Err(e) => e.to_string(),
+                };
+                self.expect("mnemonic", "ok", &got)?;
+
+                let (phrase, _, address) = MNEMONIC_VECTORS[2];
+                let result = VanityResult {
+                    private_key: None,
+                    address: address.to_string(),
+                    icap: None,
+                    contract_address: None,
+                    safe_address: None,
+                    formatted_address: None,
+                    mnemonic: None,
+                    xpub_path: Some(DerivationPath::default().child(1)),
+                    worker_id: 0,
+                };
+                let verifier = Verifier::new(any, Target::Eoa).with_xpub(account_xpub(phrase));
+                let got = match verifier.verify(&result) {
+                    Ok(()) => "ok".to_string(),
+                    Err(e) => e.to_string(),
+                };
+                self.expect("xpub child 1", "ok", &got)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether a search with `config` relies on the code this
+    /// check covers.
+    pub fn used_by(self, config: &Config) -> bool {
+        match self {
+            Check::Keypair | Check::Verifier => true,
+            Check::Create => matches!(config.target(), Target::Contract { .. }),
+            Check::Safe => config.safe_deployment().is_some(),
+            Check::Mnemonic => config.mnemonic_spec().is_some(),
+            Check::Xpub => config.xpub.is_some(),
+            Check::ScalarOffset => config.gpu_enabled(),
+        }
+    }
+
+    fn expect(self, case: &str, expected: &str, got: &str) -> Result<(), SelfTestError> {
+        if expected == got {
+            return Ok(());
+        }
+        Err(SelfTestError {
+            backend: self.to_string(),
+            case: case.to_string(),
+            expected: expected.to_string(),
+            got: got.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Keypair => write!(f, "CPU keypair"),
+            Check::Create => write!(f, "CREATE address"),
+            Check::Safe => write!(f, "Safe CREATE2 address"),
+            Check::Mnemonic => write!(f, "BIP39/BIP32 derivation"),
+            Check::Xpub => write!(f, "BIP32 public derivation (xpub)"),
+            Check::ScalarOffset => write!(f, "GPU key reconstruction (add_scalar_mod_n)"),
+            Check::Verifier => write!(f, "Independent verifier"),
+        }
+    }
+}
+
+/// Runs the checks of every backend the search described by `config` uses,
+/// before any worker starts. A GPU failure (the kernel on the chosen device,
+/// or `add_scalar_mod_n`, which rebuilds its hits) turns the GPU off in
+/// `config` with a notice, and the search continues on CPU workers. A
+/// failure in CPU code leaves nothing safe to mine with and is returned.
+pub fn before_search(config: &mut Config) -> Result<(), SelfTestError> {
+    let checks: Vec<Check> = Check::ALL.into_iter().filter(|c| c.used_by(config)).collect();
+    for check in checks {
+        match check.run() {
+            Ok(()) => {}
+            Err(e) if check == Check::ScalarOffset => disable_gpu(config, &e),
+            Err(e) => return Err(e),
+        }
+    }
+    #[cfg(feature = "gpu")]
+    if config.gpu_enabled() && config.xpub.is_none() {
+        let device = config.gpu_device_index();
+        if let Err(e) = crate::worker::gpu::self_test_device(device, config.gpu_work_size()) {
+            disable_gpu(config, &e);
+        }
+    }
+    Ok(())
+}
+
+fn disable_gpu(config: &mut Config, error: &dyn fmt::Display) {
+    eprintln!("Warning: GPU disabled: {}", error);
+    eprintln!("Continuing with CPU-only workers.");
+    config.disable_gpu();
+}
+
+/// A backend whose output differs from a known answer.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{backend} self-test failed ({case}): expected {expected}, got {got}")]
+pub struct SelfTestError {
+    pub backend: String,
+    pub case: String,
+    pub expected: String,
+    pub got: String,
+}
+
+fn key_one() -> Keypair {
+    Keypair::from_secret_key(&decode(KEYPAIR_VECTORS[0].0))
+}
+
+fn safe_deployment() -> SafeDeployment {
+    SafeDeployment::new(decode(SAFE_FACTORY), [0x22; 32], [0; 20], [0; 32])
+}
+
+/// The xpub of `phrase` at the standard account path m/44'/60'/0'/0.
+fn account_xpub(phrase: &str) -> ExtendedPublicKey {
+    let seed = Zeroizing::new(
+        bip39::Mnemonic::parse(phrase)
+            .expect("valid phrase")
+            .to_seed(""),
+    );
+    ExtendedPrivateKey::from_seed(seed.as_slice())
+        .and_then(|master| master.derive_path(&ACCOUNT_PATH.parse().expect("valid path")))
+        .expect("valid vector")
+        .to_public()
+}
+
+fn decode<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .expect("valid vector")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_checks_pass() {
+        for check in Check::ALL {
+            assert_eq!(check.run(), Ok(()), "{}", check);
+        }
+    }
+
+    #[test]
+    fn test_before_search_runs_the_checks_in_use() {
+        use clap::Parser;
+
+        let mut config =
+            Config::parse_from(["eth_vanity", "-p", "dead", "--contract-nonce", "0"]);
+        assert!(Check::Create.used_by(&config));
+        assert!(!Check::Safe.used_by(&config));
+        assert!(!Check::Mnemonic.used_by(&config));
+        assert_eq!(before_search(&mut config), Ok(()));
+    }
+
+    #[test]
+    fn test_failure_reports_case() {
+        let err = Check::Create.expect("nonce 0", "0xaa", "0xbb").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CREATE address self-test failed (nonce 0): expected 0xaa, got 0xbb"
+        );
+    }
+}
@@ -0,0 +1,626 @@
+//! Independent re-derivation of every result before it is reported.
+//!
+//! The search derives addresses with libsecp256k1 and tiny-keccak. Here the
+//! public key comes from k256 (pure Rust) and every hash from sha3's
+//! Keccak-256, and the CREATE RLP and Safe `setup` calldata are encoded from
+//! scratch. Mnemonic results are re-derived from the phrase with the pbkdf2
+//! crate (the search uses bip39's own PBKDF2) and BIP32 on k256 scalars; only
+//! HMAC-SHA512 is shared. Xpub results carry no key; their address is
+//! re-derived from the xpub by adding k256 points. The Tron, bech32, Filecoin
+//! and ICAP strings are encoded again (only the sha2 and blake2 checksums are
+//! shared), and the pattern is checked on that string, not through
+//! `Pattern::matches`. A bug in either path shows up as a mismatch instead
+//! of a wrong key.
+
+use blake2::digest::VariableOutput;
+use blake2::Blake2bVar;
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::elliptic_curve::PrimeField;
+use sha2::{Sha256, Sha512};
+use sha3::{Digest, Keccak256};
+use zeroize::Zeroizing;
+
+use crate::crypto::{AddressFormat, DerivationPath, ExtendedPublicKey, SafeDeployment, HARDENED};
+use crate::matcher::{Pattern, PatternType, Target};
+use crate::worker::VanityResult;
+
+/// Re-derives results with an implementation independent of the search.
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    pattern: Pattern,
+    target: Target,
+    xpub: Option<ExtendedPublicKey>,
+}
+
+impl Verifier {
+    /// Creates a verifier for results of a search for `pattern` on `target`.
+    pub fn new(pattern: Pattern, target: Target) -> Self {
+        Self {
+            pattern,
+            target,
+            xpub: None,
+        }
+    }
+
+    /// Verifies keyless results by their child path below `xpub`.
+    pub fn with_xpub(mut self, xpub: ExtendedPublicKey) -> Self {
+        self.xpub = Some(xpub);
+        self
+    }
+
+    /// Checks that the result's key (or xpub child) derives the reported
+    /// addresses and that the matched address really matches the pattern.
+    pub fn verify(&self, result: &VanityResult) -> Result<(), VerifyError> {
+        let worker = result.worker_id;
+        let eoa = match (&result.private_key, &self.xpub, &result.xpub_path) {
+            (Some(key), ..) => eoa_address(key.as_bytes()),
+            (None, Some(xpub), Some(path)) => xpub_address(xpub, path),
+            (None, ..) => None,
+        }
+        .ok_or(VerifyError::InvalidKey { worker })?;
+        compare(worker, "address", Some(&result.address), Some(&eoa))?;
+        let icap = display(&AddressFormat::Icap, &eoa).map(|(icap, _)| icap);
+        compare_text(worker, "ICAP", result.icap.as_ref(), icap)?;
+        if let Some(ref origin) = result.mnemonic {
+            let from_phrase = mnemonic_key(origin.phrase(), origin.path())
+                .and_then(|key| eoa_address(&key))
+                .ok_or(VerifyError::InvalidKey { worker })?;
+            compare(worker, "mnemonic address", Some(&result.address), Some(&from_phrase))?;
+        }
+
+        let (contract, safe, eoa_pattern) = match self.target {
+            Target::Eoa => (None, None, None),
+            Target::Contract {
+                nonce,
+                ref eoa_pattern,
+            } => (Some(create_address(&eoa, nonce)), None, eoa_pattern.as_ref()),
+            Target::Safe {
+                ref deployment,
+                ref eoa_pattern,
+            } => (None, Some(safe_address(deployment, &eoa)), eoa_pattern.as_ref()),
+        };
+        compare(worker, "contract address", result.contract_address.as_ref(), contract.as_ref())?;
+        compare(worker, "Safe address", result.safe_address.as_ref(), safe.as_ref())?;
+
+        if let Some(eoa_pattern) = eoa_pattern {
+            check_match(worker, eoa_pattern, &eoa)?;
+        }
+        let matched = contract.or(safe).unwrap_or(eoa);
+        // Workers report the matched address in every format but hex and ICAP
+        let format = self.pattern.format();
+        let formatted = match format {
+            AddressFormat::Hex | AddressFormat::Icap => None,
+            _ => display(format, &matched).map(|(text, _)| text),
+        };
+        compare_text(worker, "formatted address", result.formatted_address.as_ref(), formatted)?;
+        check_match(worker, &self.pattern, &matched)
+    }
+}
+
+/// A result that failed independent verification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VerifyError {
+    #[error("worker {worker}: private key or xpub child path does not derive a valid key")]
+    InvalidKey { worker: usize },
+    #[error(
+        "worker {worker}: {what} mismatch: search reported {reported}, \
+         independent derivation gives {derived}"
+    )]
+    Mismatch {
+        worker: usize,
+        what: &'static str,
+        reported: String,
+        derived: String,
+    },
+    #[error("worker {worker}: {address} does not match pattern {pattern:?} ({pattern_type})")]
+    NoMatch {
+        worker: usize,
+        address: String,
+        pattern: String,
+        pattern_type: String,
+    },
+}
+
+fn compare(
+    worker: usize,
+    what: &'static str,
+    reported: Option<&String>,
+    derived: Option<&[u8; 20]>,
+) -> Result<(), VerifyError> {
+    compare_text(worker, what, reported, derived.map(checksum))
+}
+
+fn compare_text(
+    worker: usize,
+    what: &'static str,
+    reported: Option<&String>,
+    derived: Option<String>,
+) -> Result<(), VerifyError> {
+    if reported == derived.as_ref() {
+        return Ok(());
+    }
+    let none = || "none".to_string();
+    Err(VerifyError::Mismatch {
+        worker,
+        what,
+        reported: reported.cloned().unwrap_or_else(none),
+        derived: derived.unwrap_or_else(none),
+    })
+}
+
+/// Checks the pattern against the re-encoded address in its format, with
+/// the same case folding as the search: hex is always lowercase, Base58 and
+/// base36 fold unless case-sensitive.
+fn check_match(worker: usize, pattern: &Pattern, address: &[u8; 20]) -> Result<(), VerifyError> {
+    let format = pattern.format();
+    let body = display(format, address).map(|(text, start)| {
+        let body = &text[start..];
+        match format {
+            AddressFormat::Hex => body.to_ascii_lowercase(),
+            _ if pattern.case_sensitive() => body.to_string(),
+            _ => body.to_ascii_lowercase(),
+        }
+    });
+    let matched = body.is_some_and(|body| match pattern.pattern_type() {
+        PatternType::Prefix => body.starts_with(pattern.pattern()),
+        PatternType::Suffix => body.ends_with(pattern.pattern()),
+        PatternType::Contains => body.contains(pattern.pattern()),
+        PatternType::PrefixAndSuffix => {
+            body.starts_with(pattern.pattern())
+                && body.ends_with(pattern.suffix().unwrap_or_default())
+        }
+    });
+    if matched {
+        return Ok(());
+    }
+    Err(VerifyError::NoMatch {
+        worker,
+        address: checksum(address),
+        pattern: pattern.pattern().to_string(),
+        pattern_type: pattern.pattern_type().to_string(),
+    })
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+fn last_20(hash: [u8; 32]) -> [u8; 20] {
+    hash[12..].try_into().expect("20 bytes")
+}
+
+/// keccak256(uncompressed public key without the 0x04 tag)[12..], via k256.
+fn eoa_address(secret: &[u8; 32]) -> Option<[u8; 20]> {
+    let secret = k256::SecretKey::from_slice(secret).ok()?;
+    let point = secret.public_key().to_encoded_point(false);
+    Some(last_20(keccak256(&point.as_bytes()[1..])))
+}
+
+/// Address of the normal child at `path` below `xpub`, via k256 points:
+/// K_child = K_parent + I[..32]·G.
+fn xpub_address(xpub: &ExtendedPublicKey, path: &DerivationPath) -> Option<[u8; 20]> {
+    let mut point = k256::PublicKey::from_sec1_bytes(&xpub.public_key().serialize()).ok()?;
+    let mut chain_code = Zeroizing::new(*xpub.chain_code());
+    for &index in path.indices() {
+        if index >= HARDENED {
+            return None;
+        }
+        let parent = point.to_encoded_point(true);
+        let (tweak, child_chain_code) =
+            hmac_sha512(chain_code.as_slice(), &[parent.as_bytes(), &index.to_be_bytes()]);
+        let tweak = Option::<k256::Scalar>::from(k256::Scalar::from_repr((*tweak).into()))?;
+        let child = point.to_projective() + k256::ProjectivePoint::GENERATOR * tweak;
+        point = k256::PublicKey::from_affine(child.to_affine()).ok()?;
+        chain_code = child_chain_code;
+    }
+    let point = point.to_encoded_point(false);
+    Some(last_20(keccak256(&point.as_bytes()[1..])))
+}
+
+/// Key at `path` of a BIP39 phrase (empty passphrase), via k256 scalars.
+fn mnemonic_key(phrase: &str, path: &DerivationPath) -> Option<Zeroizing<[u8; 32]>> {
+    let mut seed = Zeroizing::new([0u8; 64]);
+    pbkdf2::pbkdf2_hmac::<Sha512>(phrase.as_bytes(), b"mnemonic", 2048, seed.as_mut_slice());
+
+    let (mut key, mut chain_code) = hmac_sha512(b"Bitcoin seed", &[seed.as_slice()]);
+    let mut scalar = Option::<k256::Scalar>::from(k256::Scalar::from_repr((*key).into()))?;
+    for &index in path.indices() {
+        let index_bytes = index.to_be_bytes();
+        let parent = Zeroizing::new(<[u8; 32]>::from(scalar.to_bytes()));
+        (key, chain_code) = if index >= HARDENED {
+            hmac_sha512(chain_code.as_slice(), &[&[0], parent.as_slice(), &index_bytes])
+        } else {
+            let secret = k256::SecretKey::from_slice(parent.as_slice()).ok()?;
+            let public_key = secret.public_key().to_encoded_point(true);
+            hmac_sha512(chain_code.as_slice(), &[public_key.as_bytes(), &index_bytes])
+        };
+        let tweak = Option::<k256::Scalar>::from(k256::Scalar::from_repr((*key).into()))?;
+        scalar += tweak;
+    }
+    let key = Zeroizing::new(<[u8; 32]>::from(scalar.to_bytes()));
+    (!bool::from(scalar.is_zero())).then_some(key)
+}
+
+/// HMAC-SHA512 split into its left (key) and right (chain code) halves.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut mac = <Hmac<Sha512> as Mac>::new_from_slice(key).expect("any key length");
+    for part in data {
+        mac.update(part);
+    }
+    let output = Zeroizing::new(<[u8; 64]>::from(mac.finalize().into_bytes()));
+    let (mut left, mut right) = (Zeroizing::new([0u8; 32]), Zeroizing::new([0u8; 32]));
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+/// keccak256(rlp([deployer, nonce]))[12..].
+fn create_address(deployer: &[u8; 20], nonce: u64) -> [u8; 20] {
+    let mut items = rlp_bytes(deployer);
+    let nonce_bytes = nonce.to_be_bytes();
+    let start = nonce_bytes.iter().position(|&b| b != 0).unwrap_or(8);
+    items.extend(rlp_bytes(&nonce_bytes[start..]));
+
+    let mut rlp = vec![0xc0 + items.len() as u8];
+    rlp.extend(items);
+    last_20(keccak256(&rlp))
+}
+
+/// RLP of a short byte string (under 56 bytes).
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    match bytes {
+        [b] if *b < 0x80 => vec![*b],
+        _ => {
+            let mut out = vec![0x80 + bytes.len() as u8];
+            out.extend_from_slice(bytes);
+            out
+        }
+    }
+}
+
+/// Address of the 1/1 Safe owned by `owner`, from `setup` calldata encoded
+/// word by word.
+fn safe_address(deployment: &SafeDeployment, owner: &[u8; 20]) -> [u8; 20] {
+    let signature = "setup(address[],uint256,address,bytes,address,address,uint256,address)";
+    let selector = keccak256(signature.as_bytes());
+    let zero = [0u8; 20];
+    let mut calldata = selector[..4].to_vec();
+    for word in [
+        uint_word(0x100),                            // _owners offset
+        uint_word(1),                                // _threshold
+        address_word(&zero),                         // to
+        uint_word(0x140),                            // data offset
+        address_word(deployment.fallback_handler()), // fallbackHandler
+        address_word(&zero),                         // paymentToken
+        uint_word(0),                                // payment
+        address_word(&zero),                         // paymentReceiver
+        uint_word(1),                                // _owners.length
+        address_word(owner),                         // _owners[0]
+        uint_word(0),                                // data.length
+    ] {
+        calldata.extend_from_slice(&word);
+    }
+
+    let mut salt_preimage = keccak256(&calldata).to_vec();
+    salt_preimage.extend_from_slice(deployment.salt_nonce());
+    let salt = keccak256(&salt_preimage);
+
+    let mut preimage = vec![0xff];
+    preimage.extend_from_slice(deployment.factory());
+    preimage.extend_from_slice(&salt);
+    preimage.extend_from_slice(deployment.init_code_hash());
+    last_20(keccak256(&preimage))
+}
+
+fn uint_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn address_word(address: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+/// EIP-55 checksummed `0x` address.
+fn checksum(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+    let mut out = String::from("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+        out.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+    }
+    out
+}
+
+/// The address as shown in `format` and where the part after the fixed
+/// prefix starts, or `None` if it has no such form (ICAP at 2^155 and above).
+fn display(format: &AddressFormat, address: &[u8; 20]) -> Option<(String, usize)> {
+    match format {
+        AddressFormat::Hex => Some((checksum(address), 2)),
+        // Base58Check of 0x41 || address
+        AddressFormat::Tron => {
+            let mut payload = [&[0x41][..], address].concat();
+            let check = Sha256::digest(Sha256::digest(&payload));
+            payload.extend_from_slice(&check[..4]);
+            let leading_ones = payload.iter().take_while(|&&b| b == 0).count();
+            let digits = base_digits(&payload, 58);
+            let text = "1".repeat(leading_ones) + &spell(&digits, BASE58);
+            Some((text, 1))
+        }
+        AddressFormat::Bech32 { hrp } => {
+            let mut values = five_bit_groups(address);
+            let mut checked: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+            checked.push(0);
+            checked.extend(hrp.bytes().map(|b| b & 31));
+            checked.extend_from_slice(&values);
+            checked.extend_from_slice(&[0; 6]);
+            let polymod = bech32_polymod(&checked) ^ 1;
+            values.extend((0..6).map(|i| ((polymod >> (25 - 5 * i)) & 31) as u8));
+            Some((format!("{}1{}", hrp, spell(&values, BECH32)), hrp.len() + 1))
+        }
+        // f410f || base32(address || blake2b-32(0x04 || 0x0a || address))
+        AddressFormat::Filecoin => {
+            let mut check = [0u8; 4];
+            let mut hasher = Blake2bVar::new(4).expect("valid output size");
+            blake2::digest::Update::update(&mut hasher, &[0x04, 0x0a]);
+            blake2::digest::Update::update(&mut hasher, address);
+            hasher.finalize_variable(&mut check).expect("matching output size");
+            let values = five_bit_groups(&[&address[..], &check].concat());
+            Some((format!("f410f{}", spell(&values, BASE32)), 5))
+        }
+        // XE || check digits || 30 base-36 digits, for addresses below 2^155
+        AddressFormat::Icap => {
+            if address[0] >> 3 != 0 {
+                return None;
+            }
+            let digits = base_digits(address, 36);
+            let bban = "0".repeat(30 - digits.len()) + &spell(&digits, BASE36);
+            let numeric: String = format!("{}XE00", bban)
+                .chars()
+                .map(|c| c.to_digit(36).expect("base-36 digit").to_string())
+                .collect();
+            let rem = numeric.bytes().fold(0, |rem, d| (rem * 10 + u32::from(d - b'0')) % 97);
+            Some((format!("XE{:02}{}", 98 - rem, bban), 4))
+        }
+    }
+}
+
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BASE32: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE36: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Digits of the big-endian number `bytes` in `radix`, most significant
+/// first, by repeated long division.
+fn base_digits(bytes: &[u8], radix: u32) -> Vec<u8> {
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::new();
+    while number.iter().any(|&b| b != 0) {
+        let mut rem = 0u32;
+        for byte in number.iter_mut() {
+            let acc = (rem << 8) | u32::from(*byte);
+            *byte = (acc / radix) as u8;
+            rem = acc % radix;
+        }
+        digits.push(rem as u8);
+    }
+    digits.reverse();
+    digits
+}
+
+fn spell(digits: &[u8], alphabet: &[u8]) -> String {
+    digits.iter().map(|&d| alphabet[d as usize] as char).collect()
+}
+
+/// `bytes` as 5-bit values, most significant bit first; the last group is
+/// padded with zero bits.
+fn five_bit_groups(bytes: &[u8]) -> Vec<u8> {
+    let bits: Vec<u8> = bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+        .collect();
+    bits.chunks(5)
+        .map(|chunk| chunk.iter().fold(0, |acc, bit| (acc << 1) | bit) << (5 - chunk.len()))
+        .collect()
+}
+
+/// BIP-173 checksum polynomial.
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut checksum = 1u32;
+    for &value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ff_ffff) << 5) ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{Address, Keypair, MnemonicOrigin};
+
+    fn result_for(keypair: &Keypair, target: &Target) -> VanityResult {
+        VanityResult {
+            private_key: Some(keypair.private_key().clone()),
+            address: keypair.address().to_checksum(),
+            icap: keypair.address().to_icap(),
+            contract_address: target.contract_address(keypair.address()).map(|a| a.to_checksum()),
+            safe_address: target.safe_address(keypair.address()).map(|a| a.to_checksum()),
+            formatted_address: None,
+            mnemonic: None,
+            xpub_path: None,
+            worker_id: 3,
+        }
+    }
+
+    #[test]
+    fn test_eoa_vector() {
+        let mut secret = [0u8; 32];
+        secret[31] = 1;
+        let address = eoa_address(&secret).unwrap();
+        assert_eq!(checksum(&address), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+    }
+
+    #[test]
+    fn test_verify_agrees_with_search_path() {
+        let any = Pattern::new("", PatternType::Contains, false);
+        let targets = [
+            Target::Eoa,
+            Target::Contract {
+                nonce: 0,
+                eoa_pattern: None,
+            },
+            Target::Contract {
+                nonce: 300,
+                eoa_pattern: None,
+            },
+            Target::Safe {
+                deployment: Box::new(SafeDeployment::new(
+                    [0x11; 20], [0x22; 32], [0x33; 20], [0x44; 32],
+                )),
+                eoa_pattern: None,
+            },
+        ];
+        for target in targets {
+            let keypair = Keypair::generate();
+            let verifier = Verifier::new(any.clone(), target.clone());
+            assert_eq!(verifier.verify(&result_for(&keypair, &target)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_origin() {
+        let phrase = "test test test test test test test test test test test junk";
+        let path: DerivationPath = "m/44'/60'/0'/0/1".parse().unwrap();
+        let key = mnemonic_key(phrase, &path).unwrap();
+        let mut result = result_for(&Keypair::from_secret_key(&key), &Target::Eoa);
+        result.mnemonic = Some(MnemonicOrigin::new(Zeroizing::new(phrase.into()), path));
+        let verifier = Verifier::new(Pattern::new("", PatternType::Contains, false), Target::Eoa);
+        assert_eq!(verifier.verify(&result), Ok(()));
+
+        let wrong_path = "m/44'/60'/0'/0/2".parse().unwrap();
+        result.mnemonic = Some(MnemonicOrigin::new(Zeroizing::new(phrase.into()), wrong_path));
+        assert!(matches!(
+            verifier.verify(&result),
+            Err(VerifyError::Mismatch { what: "mnemonic address", .. })
+        ));
+    }
+
+    #[test]
+    fn test_xpub_path() {
+        let phrase = "test test test test test test test test test test test junk";
+        let account: DerivationPath = "m/44'/60'/0'/0".parse().unwrap();
+        let key = mnemonic_key(phrase, &account.child(1)).unwrap();
+        let mut result = result_for(&Keypair::from_secret_key(&key), &Target::Eoa);
+        result.private_key = None;
+        result.xpub_path = Some(DerivationPath::default().child(1));
+
+        let seed = bip39::Mnemonic::parse(phrase).unwrap().to_seed("");
+        let xpub = crate::crypto::ExtendedPrivateKey::from_seed(&seed)
+            .and_then(|master| master.derive_path(&account))
+            .unwrap()
+            .to_public();
+        let any = Pattern::new("", PatternType::Contains, false);
+        let verifier = Verifier::new(any.clone(), Target::Eoa).with_xpub(xpub);
+        assert_eq!(verifier.verify(&result), Ok(()));
+
+        result.xpub_path = Some(DerivationPath::default().child(2));
+        assert!(matches!(
+            verifier.verify(&result),
+            Err(VerifyError::Mismatch { what: "address", .. })
+        ));
+        result.xpub_path = Some(DerivationPath::default().child(HARDENED));
+        assert!(matches!(verifier.verify(&result), Err(VerifyError::InvalidKey { .. })));
+        // Without the xpub a keyless result cannot be checked
+        let verifier = Verifier::new(any, Target::Eoa);
+        assert!(matches!(verifier.verify(&result), Err(VerifyError::InvalidKey { .. })));
+    }
+
+    #[test]
+    fn test_verify_reports_mismatches() {
+        let keypair = Keypair::from_secret_key(&[7; 32]);
+        let mut result = result_for(&keypair, &Target::Eoa);
+        result.address = Address::from_bytes([0; 20]).to_checksum();
+        let any = Pattern::new("", PatternType::Contains, false);
+        assert!(matches!(
+            Verifier::new(any, Target::Eoa).verify(&result),
+            Err(VerifyError::Mismatch { worker: 3, what: "address", .. })
+        ));
+
+        let result = result_for(&keypair, &Target::Eoa);
+        let pattern = if result.address[2..].starts_with('0') { "1" } else { "0" };
+        let pattern = Pattern::new(pattern, PatternType::Prefix, false);
+        assert!(matches!(
+            Verifier::new(pattern, Target::Eoa).verify(&result),
+            Err(VerifyError::NoMatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_formats_agree_with_search() {
+        let formats = [
+            AddressFormat::Hex,
+            AddressFormat::Tron,
+            AddressFormat::Bech32 { hrp: "one".into() },
+            AddressFormat::Filecoin,
+            AddressFormat::Icap,
+        ];
+        let mut addresses = vec![[0; 20], [0xff; 20], [0x07; 20]];
+        addresses.extend((0..20).map(|_| *Keypair::generate().address().as_bytes()));
+        for format in &formats {
+            for bytes in &addresses {
+                let address = Address::from_bytes(*bytes);
+                let derived = display(format, bytes);
+                match address.to_icap() {
+                    None if *format == AddressFormat::Icap => assert_eq!(derived, None),
+                    _ => assert_eq!(derived.unwrap().0, format.encode(&address)),
+                }
+                for case_sensitive in [false, true] {
+                    let body = match format.match_body(&address, case_sensitive) {
+                        Some(body) => body,
+                        None => continue,
+                    };
+                    let pattern = Pattern::new_prefix_and_suffix(
+                        &body[..2],
+                        &body[body.len() - 2..],
+                        case_sensitive,
+                    )
+                    .with_format(format.clone());
+                    assert_eq!(check_match(0, &pattern, bytes), Ok(()), "{}", body);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_reports_wrong_formatted_address() {
+        let keypair = Keypair::from_secret_key(&[7; 32]);
+        let mut result = result_for(&keypair, &Target::Eoa);
+        let tron = Pattern::new("", PatternType::Contains, false).with_format(AddressFormat::Tron);
+        let verifier = Verifier::new(tron, Target::Eoa);
+        result.formatted_address = Some(AddressFormat::Tron.encode(keypair.address()));
+        assert_eq!(verifier.verify(&result), Ok(()));
+
+        result.formatted_address = Some(AddressFormat::Tron.encode(&Address::from_bytes([1; 20])));
+        assert!(matches!(
+            verifier.verify(&result),
+            Err(VerifyError::Mismatch { what: "formatted address", .. })
+        ));
+        result.formatted_address = None;
+        assert!(matches!(
+            verifier.verify(&result),
+            Err(VerifyError::Mismatch { what: "formatted address", .. })
+        ));
+    }
+}
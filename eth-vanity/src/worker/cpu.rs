@@ -5,8 +5,8 @@ use std::sync::Arc;
 
 use crossbeam_channel::Sender;
 
-use crate::crypto::Keypair;
-use crate::matcher::Pattern;
+use crate::crypto::{Address, AddressFormat, KeySource, Keypair, PrivateKey};
+use crate::matcher::{Pattern, Target};
 
 use super::VanityResult;
 
@@ -42,12 +42,18 @@ pub struct CpuWorker {
     id: usize,
     /// The pattern to match against
     pattern: Pattern,
+    /// Which derived address the pattern applies to
+    target: Target,
+    /// Where candidate keys come from
+    source: Box<dyn KeySource>,
     /// Channel to send results
     result_tx: Sender<VanityResult>,
     /// Shared stop flag
     stop_flag: Arc<AtomicBool>,
     /// Worker statistics
     stats: Arc<WorkerStats>,
+    /// Lock the key of each result in memory (mlock)
+    lock_keys: bool,
 }
 
 impl CpuWorker {
@@ -55,6 +61,8 @@ impl CpuWorker {
     pub fn new(
         id: usize,
         pattern: Pattern,
+        target: Target,
+        source: Box<dyn KeySource>,
         result_tx: Sender<VanityResult>,
         stop_flag: Arc<AtomicBool>,
         stats: Arc<WorkerStats>,
@@ -62,19 +70,29 @@ impl CpuWorker {
         Self {
             id,
             pattern,
+            target,
+            source,
             result_tx,
             stop_flag,
             stats,
+            lock_keys: false,
         }
     }
 
+    /// Locks the key of each result in memory as soon as it is copied out
+    /// of the keypair, before it is sent to the pool.
+    pub fn with_locked_keys(mut self, lock_keys: bool) -> Self {
+        self.lock_keys = lock_keys;
+        self
+    }
+
     /// Runs the worker loop.
     ///
     /// Generates keypairs and tests them against the pattern until:
     /// - A match is found (sends result through channel)
     /// - Stop flag is set
     /// - Channel is closed
-    pub fn run(&self) {
+    pub fn run(&mut self) {
         // Process in batches to reduce atomic operation overhead
         const BATCH_SIZE: u64 = 1000;
 
@@ -86,15 +104,27 @@ impl CpuWorker {
 
             // Generate and test a batch of keypairs
             for _ in 0..BATCH_SIZE {
-                let keypair = Keypair::generate();
+                let keypair = Keypair::from_source(&mut *self.source);
 
-                if self.pattern.matches(keypair.address()).is_match() {
+                if self.target.matches(&self.pattern, keypair.address()).is_match() {
                     // Found a match!
                     self.stats.matches_found.fetch_add(1, Ordering::Relaxed);
 
                     let result = VanityResult {
-                        private_key: keypair.private_key_hex(),
+                        private_key: Some(result_key(keypair.private_key(), self.lock_keys)),
                         address: keypair.address().to_checksum(),
+                        icap: keypair.address().to_icap(),
+                        contract_address: self
+                            .target
+                            .contract_address(keypair.address())
+                            .map(|a| a.to_checksum()),
+                        safe_address: self
+                            .target
+                            .safe_address(keypair.address())
+                            .map(|a| a.to_checksum()),
+                        formatted_address: self.formatted_address(keypair.address()),
+                        mnemonic: self.source.mnemonic_origin(),
+                        xpub_path: None,
                         worker_id: self.id,
                     };
 
@@ -108,8 +138,28 @@ impl CpuWorker {
         }
     }
 
+    /// Returns the matched address in the pattern's format, unless that is
+    /// plain hex or ICAP (always reported in `VanityResult::icap`).
+    fn formatted_address(&self, eoa: &Address) -> Option<String> {
+        let format = self.pattern.format();
+        let reported = format.is_hex() || *format == AddressFormat::Icap;
+        (!reported).then(|| format.encode(&self.target.matched_address(eoa)))
+    }
+
     /// Returns the worker ID.
     pub fn id(&self) -> usize {
         self.id
     }
 }
+
+/// Copies a matching key into a result, locked in memory if `lock` is set.
+/// A key that cannot be locked is still reported, with a warning.
+pub(super) fn result_key(key: &PrivateKey, lock: bool) -> PrivateKey {
+    let mut key = key.clone();
+    if lock {
+        if let Err(e) = key.lock() {
+            eprintln!("Warning: could not lock private key in memory: {}", e);
+        }
+    }
+    key
+}
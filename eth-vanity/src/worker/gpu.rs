@@ -1,7 +1,7 @@
 //! GPU-based worker for vanity address generation using OpenCL.
 //!
 //! Uses the incremental key approach:
-//! 1. CPU generates random base private key
+//! 1. CPU draws a base private key from the worker's key source
 //! 2. CPU computes base public key Q = k * G
 //! 3. GPU computes Q + i*G for millions of offsets in parallel
 //! 4. GPU runs keccak256 and pattern matching
@@ -10,7 +10,7 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use crossbeam_channel::Sender;
+use crossbeam_channel::{bounded, Sender};
 use opencl3::command_queue::{CommandQueue, CL_QUEUE_PROFILING_ENABLE};
 use opencl3::context::Context;
 use opencl3::device::{get_all_devices, Device, CL_DEVICE_TYPE_GPU};
@@ -19,11 +19,13 @@ use opencl3::memory::{Buffer, CL_MEM_READ_ONLY, CL_MEM_READ_WRITE, CL_MEM_WRITE_
 use opencl3::program::Program;
 use opencl3::types::{cl_uchar, cl_uint, CL_BLOCKING};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use zeroize::Zeroizing;
 
-use crate::crypto::Keypair;
-use crate::matcher::Pattern;
+use crate::crypto::{add_scalar_mod_n, KeySource, Keypair, OsSource};
+use crate::matcher::{Pattern, PatternType};
+use crate::selftest::SelfTestError;
 
-use super::cpu::WorkerStats;
+use super::cpu::{result_key, WorkerStats};
 use super::VanityResult;
 
 /// OpenCL kernel source
@@ -49,6 +51,9 @@ pub enum GpuError {
 
     #[error("Kernel execution failed: {0}")]
     KernelExec(String),
+
+    #[error(transparent)]
+    SelfTest(#[from] SelfTestError),
 }
 
 /// Pattern configuration matching the GPU kernel's `gpu_pattern_config_t`.
@@ -86,6 +91,23 @@ pub fn list_devices() -> Vec<String> {
     }
 }
 
+/// Compiles the kernel on device `device_index` and runs
+/// [`GpuWorker::self_test`] with `work_size` keys per batch.
+pub fn self_test_device(device_index: usize, work_size: usize) -> Result<(), GpuError> {
+    let (result_tx, _result_rx) = bounded(1);
+    GpuWorker::new(
+        0,
+        Pattern::new("", PatternType::Prefix, false),
+        result_tx,
+        Arc::new(AtomicBool::new(false)),
+        Arc::new(WorkerStats::new()),
+        Box::new(OsSource),
+        device_index,
+        work_size,
+    )?
+    .self_test()
+}
+
 /// GPU worker that uses OpenCL for parallel vanity address generation.
 pub struct GpuWorker {
     /// Worker ID
@@ -98,6 +120,8 @@ pub struct GpuWorker {
     stop_flag: Arc<AtomicBool>,
     /// Shared statistics
     stats: Arc<WorkerStats>,
+    /// Where base keys come from
+    source: Box<dyn KeySource>,
     /// OpenCL context
     context: Context,
     /// OpenCL command queue
@@ -108,6 +132,8 @@ pub struct GpuWorker {
     work_size: usize,
     /// Precomputed G table (32 entries of 2^k * G, each 64 bytes)
     g_table: Vec<u8>,
+    /// Lock the key of each result in memory (mlock)
+    lock_keys: bool,
 }
 
 impl GpuWorker {
@@ -118,6 +144,7 @@ impl GpuWorker {
         result_tx: Sender<VanityResult>,
         stop_flag: Arc<AtomicBool>,
         stats: Arc<WorkerStats>,
+        source: Box<dyn KeySource>,
         device_index: usize,
         work_size: usize,
     ) -> Result<Self, GpuError> {
@@ -163,14 +190,23 @@ impl GpuWorker {
             result_tx,
             stop_flag,
             stats,
+            source,
             context,
             queue,
             kernel,
             work_size,
             g_table,
+            lock_keys: false,
         })
     }
 
+    /// Locks the key of each result in memory as soon as it is rebuilt on
+    /// the CPU, before it is sent to the pool.
+    pub fn with_locked_keys(mut self, lock_keys: bool) -> Self {
+        self.lock_keys = lock_keys;
+        self
+    }
+
     /// Precomputes the table of 2^k * G for k = 0..31.
     /// Each entry is 64 bytes: x (32 bytes BE) || y (32 bytes BE).
     fn compute_g_table() -> Vec<u8> {
@@ -256,77 +292,8 @@ impl GpuWorker {
         config
     }
 
-    /// Adds a scalar offset to a base private key modulo the secp256k1 curve order.
-    fn add_scalar_mod_n(base_key: &[u8; 32], offset: u32) -> [u8; 32] {
-        // secp256k1 curve order n
-        let n: [u64; 4] = [
-            0xBFD25E8CD0364141,
-            0xBAAEDCE6AF48A03B,
-            0xFFFFFFFFFFFFFFFE,
-            0xFFFFFFFFFFFFFFFF,
-        ];
-
-        // Convert base_key (big-endian) to u64 limbs (little-endian limb order)
-        let mut key = [0u64; 4];
-        for i in 0..4 {
-            let off = (3 - i) * 8;
-            key[i] = u64::from_be_bytes([
-                base_key[off],
-                base_key[off + 1],
-                base_key[off + 2],
-                base_key[off + 3],
-                base_key[off + 4],
-                base_key[off + 5],
-                base_key[off + 6],
-                base_key[off + 7],
-            ]);
-        }
-
-        // Add offset
-        let mut carry = offset as u128;
-        for limb in key.iter_mut() {
-            let sum = *limb as u128 + carry;
-            *limb = sum as u64;
-            carry = sum >> 64;
-        }
-
-        // Reduce mod n if needed
-        let mut gte_n = carry > 0;
-        if !gte_n {
-            // Compare key >= n
-            for i in (0..4).rev() {
-                if key[i] > n[i] {
-                    gte_n = true;
-                    break;
-                }
-                if key[i] < n[i] {
-                    break;
-                }
-            }
-        }
-
-        if gte_n {
-            let mut borrow: u128 = 0;
-            for i in 0..4 {
-                let diff = key[i] as u128 + (1u128 << 64) - n[i] as u128 - borrow;
-                key[i] = diff as u64;
-                borrow = 1 - (diff >> 64);
-            }
-        }
-
-        // Convert back to big-endian bytes
-        let mut result = [0u8; 32];
-        for i in 0..4 {
-            let off = (3 - i) * 8;
-            let bytes = key[i].to_be_bytes();
-            result[off..off + 8].copy_from_slice(&bytes);
-        }
-
-        result
-    }
-
     /// Runs the GPU worker main loop.
-    pub fn run(&self) {
+    pub fn run(&mut self) {
         loop {
             if self.stop_flag.load(Ordering::Relaxed) {
                 break;
@@ -344,13 +311,55 @@ impl GpuWorker {
     }
 
     /// Runs a single batch: generates base key, dispatches GPU kernel, reads results.
-    fn run_batch(&self) -> Result<(), GpuError> {
-        let secp = Secp256k1::new();
-
-        // Generate random base private key
-        let (secret_key, public_key) = secp.generate_keypair(&mut rand::thread_rng());
+    fn run_batch(&mut self) -> Result<(), GpuError> {
+        // Draw the base private key from the key source
+        let secret_key = self.source.next_secret_key();
         let base_key_bytes = secret_key.secret_bytes();
 
+        let config = Self::pattern_to_gpu_config(&self.pattern);
+        for gpu_result in self.dispatch(&secret_key, &config)? {
+            // Reconstruct private key: base_key + offset mod n
+            let derived_key = Zeroizing::new(add_scalar_mod_n(&base_key_bytes, gpu_result.offset));
+
+            // Verify on CPU
+            let keypair = Keypair::from_secret_key(&derived_key);
+            if self.pattern.matches(keypair.address()).is_match() {
+                self.stats.matches_found.fetch_add(1, Ordering::Relaxed);
+
+                let result = VanityResult {
+                    private_key: Some(result_key(keypair.private_key(), self.lock_keys)),
+                    address: keypair.address().to_checksum(),
+                    icap: keypair.address().to_icap(),
+                    contract_address: None,
+                    safe_address: None,
+                    formatted_address: None,
+                    mnemonic: None,
+                    xpub_path: None,
+                    worker_id: self.id,
+                };
+
+                let _ = self.result_tx.send(result);
+            }
+        }
+
+        // Update stats
+        self.stats
+            .keys_generated
+            .fetch_add(self.work_size as u64, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Runs the kernel over `base_key + 0 .. base_key + work_size` and
+    /// returns the hits it reports.
+    fn dispatch(
+        &self,
+        base_key: &SecretKey,
+        config: &GpuPatternConfig,
+    ) -> Result<Vec<GpuResult>, GpuError> {
+        let secp = Secp256k1::new();
+        let public_key = PublicKey::from_secret_key(&secp, base_key);
+
         // Get uncompressed public key (without 0x04 prefix)
         let pubkey_uncompressed = public_key.serialize_uncompressed();
         let base_pubkey_bytes: &[u8] = &pubkey_uncompressed[1..65]; // 64 bytes
@@ -371,10 +380,9 @@ impl GpuWorker {
             .map_err(|e| GpuError::BufferError(e.to_string()))?
         };
 
-        let config = Self::pattern_to_gpu_config(&self.pattern);
         let config_bytes = unsafe {
             std::slice::from_raw_parts(
-                &config as *const GpuPatternConfig as *const u8,
+                config as *const GpuPatternConfig as *const u8,
                 std::mem::size_of::<GpuPatternConfig>(),
             )
         };
@@ -464,9 +472,9 @@ impl GpuWorker {
         let num_results = (count_out[0] as usize).min(MAX_RESULTS_PER_BATCH as usize);
 
         // Read results if any
+        let mut results_out = Vec::new();
         if num_results > 0 {
-            let mut results_out =
-                vec![GpuResult::default(); MAX_RESULTS_PER_BATCH as usize];
+            results_out = vec![GpuResult::default(); MAX_RESULTS_PER_BATCH as usize];
             let results_bytes = unsafe {
                 std::slice::from_raw_parts_mut(
                     results_out.as_mut_ptr() as *mut u8,
@@ -479,37 +487,57 @@ impl GpuWorker {
                     .enqueue_read_buffer(&result_buf, CL_BLOCKING, 0, results_bytes, &[])
                     .map_err(|e| GpuError::BufferError(e.to_string()))?;
             }
+            results_out.truncate(num_results);
+            results_out.retain(|r| r.found != 0);
+        }
 
-            for i in 0..num_results {
-                let gpu_result = &results_out[i];
-                if gpu_result.found == 0 {
-                    continue;
-                }
-
-                // Reconstruct private key: base_key + offset mod n
-                let derived_key = Self::add_scalar_mod_n(&base_key_bytes, gpu_result.offset);
-
-                // Verify on CPU
-                let keypair = Keypair::from_secret_key(derived_key);
-                if self.pattern.matches(keypair.address()).is_match() {
-                    self.stats.matches_found.fetch_add(1, Ordering::Relaxed);
-
-                    let result = VanityResult {
-                        private_key: keypair.private_key_hex(),
-                        address: keypair.address().to_checksum(),
-                        worker_id: self.id,
-                    };
+        Ok(results_out)
+    }
 
-                    let _ = self.result_tx.send(result);
+    /// Runs known-answer vectors through the kernel on this device: from a
+    /// known base key (including n - 1 and n - 3, so the batch wraps past
+    /// the curve order), the kernel must report exactly the offset whose
+    /// full 40-nibble address was asked for. Mining on a device that fails
+    /// would hand out wrong keys.
+    pub fn self_test(&self) -> Result<(), GpuError> {
+        let last = (self.work_size as u32).saturating_sub(1);
+        let small = last.min(5);
+        let mut n_minus_1 = secp256k1::constants::CURVE_ORDER;
+        n_minus_1[31] -= 1;
+        let mut n_minus_3 = secp256k1::constants::CURVE_ORDER;
+        n_minus_3[31] -= 3;
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        let mut arbitrary = [0x5a; 32];
+        arbitrary[0] = 0x3c;
+
+        let vectors = [(one, small), (n_minus_1, small), (n_minus_3, small), (arbitrary, last)];
+        for (base, offset) in vectors {
+            let expected = Keypair::from_secret_key(&add_scalar_mod_n(&base, offset));
+            let expected_hex = expected.address().to_hex();
+            let pattern = Pattern::new(expected_hex.as_str(), PatternType::Prefix, false);
+            let config = Self::pattern_to_gpu_config(&pattern);
+            let base_key = SecretKey::from_slice(&base).expect("valid vector");
+
+            let hits = self.dispatch(&base_key, &config)?;
+            let got = match hits.as_slice() {
+                [hit] if hit.offset == offset && hit.addr == *expected.address().as_bytes() => {
+                    continue
                 }
-            }
+                [] => "no hit".to_string(),
+                hits => hits
+                    .iter()
+                    .map(|h| format!("offset {} -> 0x{}", h.offset, hex::encode(h.addr)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            return Err(GpuError::SelfTest(SelfTestError {
+                backend: format!("OpenCL kernel (GPU worker {})", self.id),
+                case: format!("0x{} + {}", hex::encode(base), offset),
+                expected: format!("offset {} -> 0x{}", offset, expected_hex),
+                got,
+            }));
         }
-
-        // Update stats
-        self.stats
-            .keys_generated
-            .fetch_add(self.work_size as u64, Ordering::Relaxed);
-
         Ok(())
     }
 }
@@ -518,27 +546,6 @@ impl GpuWorker {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_add_scalar_mod_n_basic() {
-        let mut base = [0u8; 32];
-        base[31] = 1; // key = 1
-        let result = GpuWorker::add_scalar_mod_n(&base, 5);
-        assert_eq!(result[31], 6); // 1 + 5 = 6
-        // All other bytes should be 0
-        for i in 0..31 {
-            assert_eq!(result[i], 0);
-        }
-    }
-
-    #[test]
-    fn test_add_scalar_mod_n_carry() {
-        let mut base = [0u8; 32];
-        base[31] = 0xFF;
-        let result = GpuWorker::add_scalar_mod_n(&base, 1);
-        assert_eq!(result[31], 0);
-        assert_eq!(result[30], 1); // carry propagated
-    }
-
     #[test]
     fn test_pattern_to_gpu_config_prefix() {
         let pattern = Pattern::new("dead", crate::matcher::PatternType::Prefix, false);
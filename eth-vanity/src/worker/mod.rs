@@ -3,6 +3,7 @@
 //! This module provides:
 //! - Multi-threaded CPU workers
 //! - GPU workers (OpenCL, behind `gpu` feature flag)
+//! - Keyless workers searching the children of an xpub
 //! - Coordinated work distribution
 //! - Progress tracking and reporting
 
@@ -10,8 +11,10 @@ mod cpu;
 #[cfg(feature = "gpu")]
 pub mod gpu;
 mod pool;
+mod xpub;
 
 pub use cpu::CpuWorker;
 #[cfg(feature = "gpu")]
 pub use gpu::GpuWorker;
 pub use pool::{VanityResult, WorkerPool};
+pub use xpub::XpubWorker;
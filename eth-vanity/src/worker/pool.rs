@@ -7,19 +7,38 @@ use std::time::{Duration, Instant};
 
 use crossbeam_channel::{bounded, Receiver, Sender};
 
-use crate::matcher::Pattern;
+use crate::crypto::{
+    DerivationPath, Entropy, KeySource, MnemonicOrigin, MnemonicSpec, PrivateKey, XpubSpec,
+};
+use crate::matcher::{Pattern, Target};
 
 #[cfg(feature = "gpu")]
 use super::gpu::GpuWorker;
 use super::cpu::{CpuWorker, WorkerStats};
+use super::xpub::XpubWorker;
 
 /// Result of a successful vanity address generation.
 #[derive(Debug, Clone)]
 pub struct VanityResult {
-    /// The private key (hex encoded, no 0x prefix)
-    pub private_key: String,
+    /// The private key (zeroed on drop, redacted in Debug); `None` in xpub
+    /// mode, where the key never reaches this machine
+    pub private_key: Option<PrivateKey>,
     /// The Ethereum address (checksummed with 0x prefix)
     pub address: String,
+    /// The direct ICAP of the address, when it is below 2^155
+    pub icap: Option<String>,
+    /// The contract address the EOA deploys with CREATE (checksummed), when
+    /// matching contract addresses
+    pub contract_address: Option<String>,
+    /// The 1/1 Safe owned by the EOA (checksummed), when matching Safe addresses
+    pub safe_address: Option<String>,
+    /// The matched address in the pattern's format (a Tron T-address or
+    /// bech32), when not hex
+    pub formatted_address: Option<String>,
+    /// The phrase and path the key was derived from, in mnemonic mode
+    pub mnemonic: Option<MnemonicOrigin>,
+    /// The child path below `--xpub` whose public key matched, in xpub mode
+    pub xpub_path: Option<DerivationPath>,
     /// The ID of the worker that found this result
     pub worker_id: usize,
 }
@@ -30,6 +49,8 @@ pub struct WorkerPool {
     num_workers: usize,
     /// The pattern to search for
     pattern: Pattern,
+    /// Which derived address the pattern applies to
+    target: Target,
     /// Worker thread handles (Option to allow taking during join)
     handles: Option<Vec<JoinHandle<()>>>,
     /// Channel receiver for results
@@ -44,22 +65,36 @@ pub struct WorkerPool {
 
 impl WorkerPool {
     /// Creates a new worker pool with the specified number of workers.
-    pub fn new(num_workers: usize, pattern: Pattern) -> Self {
+    ///
+    /// Each worker draws its keys from its own source built from `entropy`,
+    /// or from BIP39 phrases drawn from it when `mnemonic` is given. With
+    /// `lock_keys`, the key of each result is locked in memory by the worker.
+    pub fn new(
+        num_workers: usize,
+        pattern: Pattern,
+        target: Target,
+        entropy: Entropy,
+        mnemonic: Option<MnemonicSpec>,
+        lock_keys: bool,
+    ) -> Self {
         let (result_tx, result_rx) = bounded(100);
         let stop_flag = Arc::new(AtomicBool::new(false));
         let stats = Arc::new(WorkerStats::new());
 
         let handles = Self::spawn_workers(
-            num_workers,
             pattern.clone(),
+            target.clone(),
+            Self::key_sources(num_workers, &entropy, mnemonic),
             result_tx,
             stop_flag.clone(),
             stats.clone(),
+            lock_keys,
         );
 
         Self {
             num_workers,
             pattern,
+            target,
             handles: Some(handles),
             result_rx,
             stop_flag,
@@ -68,11 +103,17 @@ impl WorkerPool {
         }
     }
 
-    /// Creates a new worker pool with optional GPU acceleration.
+    /// Creates a new worker pool with optional GPU acceleration. Set
+    /// `enable_gpu` only for a device that passed
+    /// [`before_search`](crate::selftest::before_search).
     #[cfg(feature = "gpu")]
     pub fn new_with_gpu(
         num_cpu_workers: usize,
         pattern: Pattern,
+        target: Target,
+        entropy: Entropy,
+        mnemonic: Option<MnemonicSpec>,
+        lock_keys: bool,
         enable_gpu: bool,
         gpu_device: usize,
         gpu_work_size: usize,
@@ -82,32 +123,47 @@ impl WorkerPool {
         let stats = Arc::new(WorkerStats::new());
 
         let mut handles = Self::spawn_workers(
-            num_cpu_workers,
             pattern.clone(),
+            target.clone(),
+            Self::key_sources(num_cpu_workers, &entropy, mnemonic),
             result_tx.clone(),
             stop_flag.clone(),
             stats.clone(),
+            lock_keys,
         );
 
         let mut gpu_active = false;
 
-        if enable_gpu {
+        // The kernel only matches EOA addresses, in hex.
+        if enable_gpu && !target.is_eoa() {
+            eprintln!("Warning: GPU matching supports EOA addresses only.");
+            eprintln!("Continuing with CPU-only workers.");
+        } else if enable_gpu && mnemonic.is_some() {
+            eprintln!("Warning: GPU matching does not support mnemonic mode.");
+            eprintln!("Continuing with CPU-only workers.");
+        } else if enable_gpu && !pattern.format().is_hex() {
+            eprintln!("Warning: GPU matching supports hex addresses only.");
+            eprintln!("Continuing with CPU-only workers.");
+        } else if enable_gpu {
             let gpu_pattern = pattern.clone();
             let gpu_tx = result_tx.clone();
             let gpu_stop = stop_flag.clone();
             let gpu_stats = stats.clone();
             let gpu_id = num_cpu_workers; // GPU worker gets next ID
 
+            // The device already passed `selftest::before_search`
             match GpuWorker::new(
                 gpu_id,
                 gpu_pattern.clone(),
                 gpu_tx.clone(),
                 gpu_stop.clone(),
                 gpu_stats.clone(),
+                entropy.source(gpu_id),
                 gpu_device,
                 gpu_work_size,
             ) {
                 Ok(gpu_worker) => {
+                    let mut gpu_worker = gpu_worker.with_locked_keys(lock_keys);
                     let handle = thread::Builder::new()
                         .name("vanity-gpu-worker".into())
                         .spawn(move || {
@@ -136,6 +192,7 @@ impl WorkerPool {
         Self {
             num_workers: total_workers,
             pattern,
+            target,
             handles: Some(handles),
             result_rx,
             stop_flag,
@@ -144,17 +201,75 @@ impl WorkerPool {
         }
     }
 
-    /// Spawns worker threads.
-    fn spawn_workers(
+    /// Creates a pool that searches the children of an xpub instead of
+    /// random keys; workers stop when the index space is exhausted.
+    pub fn new_xpub(num_workers: usize, pattern: Pattern, target: Target, spec: XpubSpec) -> Self {
+        let (result_tx, result_rx) = bounded(100);
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let stats = Arc::new(WorkerStats::new());
+
+        let handles = (0..num_workers)
+            .map(|id| {
+                let mut worker = XpubWorker::new(
+                    id,
+                    pattern.clone(),
+                    target.clone(),
+                    spec.clone(),
+                    result_tx.clone(),
+                    stop_flag.clone(),
+                    stats.clone(),
+                )
+                .with_stride(num_workers);
+
+                thread::Builder::new()
+                    .name(format!("vanity-xpub-worker-{}", id))
+                    .spawn(move || worker.run())
+                    .expect("Failed to spawn worker thread")
+            })
+            .collect();
+
+        Self {
+            num_workers,
+            pattern,
+            target,
+            handles: Some(handles),
+            result_rx,
+            stop_flag,
+            stats,
+            start_time: Instant::now(),
+        }
+    }
+
+    /// Builds the key source of each of `num_workers` workers.
+    fn key_sources(
         num_workers: usize,
+        entropy: &Entropy,
+        mnemonic: Option<MnemonicSpec>,
+    ) -> Vec<Box<dyn KeySource>> {
+        (0..num_workers)
+            .map(|id| match mnemonic {
+                Some(spec) => spec.source(entropy.source(id)),
+                None => entropy.source(id),
+            })
+            .collect()
+    }
+
+    /// Spawns one worker thread per key source.
+    fn spawn_workers(
         pattern: Pattern,
+        target: Target,
+        sources: Vec<Box<dyn KeySource>>,
         result_tx: Sender<VanityResult>,
         stop_flag: Arc<AtomicBool>,
         stats: Arc<WorkerStats>,
+        lock_keys: bool,
     ) -> Vec<JoinHandle<()>> {
-        (0..num_workers)
-            .map(|id| {
+        sources
+            .into_iter()
+            .enumerate()
+            .map(|(id, source)| {
                 let pattern = pattern.clone();
+                let target = target.clone();
                 let result_tx = result_tx.clone();
                 let stop_flag = stop_flag.clone();
                 let stats = stats.clone();
@@ -162,7 +277,10 @@ impl WorkerPool {
                 thread::Builder::new()
                     .name(format!("vanity-worker-{}", id))
                     .spawn(move || {
-                        let worker = CpuWorker::new(id, pattern, result_tx, stop_flag, stats);
+                        let mut worker = CpuWorker::new(
+                            id, pattern, target, source, result_tx, stop_flag, stats,
+                        )
+                        .with_locked_keys(lock_keys);
                         worker.run();
                     })
                     .expect("Failed to spawn worker thread")
@@ -187,6 +305,11 @@ impl WorkerPool {
         self.result_rx.iter()
     }
 
+    /// Returns true once every worker has exited (an exhausted xpub search).
+    pub fn is_finished(&self) -> bool {
+        self.handles.iter().flatten().all(JoinHandle::is_finished)
+    }
+
     /// Signals all workers to stop.
     pub fn stop(&self) {
         self.stop_flag.store(true, Ordering::Relaxed);
@@ -212,6 +335,11 @@ impl WorkerPool {
         &self.pattern
     }
 
+    /// Returns which derived address the pattern applies to.
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
     /// Returns the total keys generated across all workers.
     pub fn total_keys(&self) -> u64 {
         self.stats.total_keys()
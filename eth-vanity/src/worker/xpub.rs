@@ -0,0 +1,191 @@
+//! Worker that searches the normal children of an extended public key.
+//!
+//! Only public keys are derived, so no private key exists on this machine;
+//! the owner of the xprv derives the key of the reported child path.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crossbeam_channel::Sender;
+use secp256k1::{Secp256k1, VerifyOnly};
+
+use crate::crypto::{
+    Address, AddressFormat, DerivationPath, ExtendedPublicKey, Keypair, XpubSpec, HARDENED,
+};
+use crate::matcher::{Pattern, Target};
+
+use super::cpu::WorkerStats;
+use super::VanityResult;
+
+/// A worker that walks child indices `id, id + stride, ...` below an xpub,
+/// at depth 1 (`i`) or depth 2 (`i/j`, the spec's `j` range for each of
+/// its `i`).
+pub struct XpubWorker {
+    /// Worker ID
+    id: usize,
+    /// Number of workers sharing the index space
+    stride: usize,
+    /// The pattern to match against
+    pattern: Pattern,
+    /// Which derived address the pattern applies to
+    target: Target,
+    /// The xpub and depth searched
+    spec: XpubSpec,
+    /// Channel to send results
+    result_tx: Sender<VanityResult>,
+    /// Shared stop flag
+    stop_flag: Arc<AtomicBool>,
+    /// Worker statistics
+    stats: Arc<WorkerStats>,
+    /// Context for public key tweaks
+    secp: Secp256k1<VerifyOnly>,
+}
+
+impl XpubWorker {
+    /// Creates a new xpub worker, starting at index `id` with stride 1.
+    pub fn new(
+        id: usize,
+        pattern: Pattern,
+        target: Target,
+        spec: XpubSpec,
+        result_tx: Sender<VanityResult>,
+        stop_flag: Arc<AtomicBool>,
+        stats: Arc<WorkerStats>,
+    ) -> Self {
+        Self {
+            id,
+            stride: 1,
+            pattern,
+            target,
+            spec,
+            result_tx,
+            stop_flag,
+            stats,
+            secp: Secp256k1::verification_only(),
+        }
+    }
+
+    /// Shares the index space with `stride` workers (IDs `0..stride`).
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        self.stride = stride.max(1);
+        self
+    }
+
+    /// Runs the worker loop until the stop flag is set or the worker's
+    /// share of the index space is exhausted.
+    pub fn run(&mut self) {
+        let outer = (self.id as u32..HARDENED).step_by(self.stride);
+        let xpub = self.spec.xpub();
+        if self.spec.depth() < 2 {
+            self.search(xpub, &DerivationPath::default(), outer);
+            return;
+        }
+        for i in outer {
+            // A small j range may never fill a batch, so check here too
+            if self.stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            // An invalid index (never seen in practice) is skipped, as BIP32 says
+            let Some(node) = xpub.derive_child(&self.secp, i) else {
+                continue;
+            };
+            let path = DerivationPath::default().child(i);
+            if !self.search(&node, &path, 0..self.spec.indices()) {
+                break;
+            }
+        }
+    }
+
+    /// Tests the children `indices` of `parent` (at `path` below the xpub).
+    /// Returns false once the stop flag is set.
+    fn search(
+        &self,
+        parent: &ExtendedPublicKey,
+        path: &DerivationPath,
+        indices: impl Iterator<Item = u32>,
+    ) -> bool {
+        // Process in batches to reduce atomic operation overhead
+        const BATCH_SIZE: u64 = 1000;
+
+        let mut batch = 0;
+        for index in indices {
+            if let Some(child) = parent.derive_child(&self.secp, index) {
+                let address = Keypair::derive_address(child.public_key());
+                if self.target.matches(&self.pattern, &address).is_match() {
+                    self.stats.matches_found.fetch_add(1, Ordering::Relaxed);
+                    let _ = self.result_tx.send(self.result(&address, path.child(index)));
+                }
+            }
+
+            batch += 1;
+            if batch == BATCH_SIZE {
+                self.stats.keys_generated.fetch_add(BATCH_SIZE, Ordering::Relaxed);
+                batch = 0;
+                if self.stop_flag.load(Ordering::Relaxed) {
+                    return false;
+                }
+            }
+        }
+        self.stats.keys_generated.fetch_add(batch, Ordering::Relaxed);
+        true
+    }
+
+    fn result(&self, address: &Address, path: DerivationPath) -> VanityResult {
+        let format = self.pattern.format();
+        let reported = format.is_hex() || *format == AddressFormat::Icap;
+        VanityResult {
+            private_key: None,
+            address: address.to_checksum(),
+            icap: address.to_icap(),
+            contract_address: self.target.contract_address(address).map(|a| a.to_checksum()),
+            safe_address: self.target.safe_address(address).map(|a| a.to_checksum()),
+            formatted_address: (!reported)
+                .then(|| format.encode(&self.target.matched_address(address))),
+            mnemonic: None,
+            xpub_path: Some(path),
+            worker_id: self.id,
+        }
+    }
+
+    /// Returns the worker ID.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::PatternType;
+
+    #[test]
+    fn test_depth_two_moves_on_to_next_i() {
+        let xpub: ExtendedPublicKey =
+            "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsef\
+             D265TMg7usUDFdp6W1EGMcet8"
+                .parse()
+                .unwrap();
+        let (result_tx, result_rx) = crossbeam_channel::unbounded();
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let mut worker = XpubWorker::new(
+            0,
+            Pattern::new("", PatternType::Contains, false),
+            Target::Eoa,
+            XpubSpec::new(xpub, 2, 3),
+            result_tx,
+            stop_flag.clone(),
+            Arc::new(WorkerStats::new()),
+        );
+        let handle = std::thread::spawn(move || worker.run());
+
+        let paths: Vec<String> = result_rx
+            .iter()
+            .take(7)
+            .map(|result| result.xpub_path.unwrap().to_string())
+            .collect();
+        stop_flag.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert_eq!(paths, ["m/0/0", "m/0/1", "m/0/2", "m/1/0", "m/1/1", "m/1/2", "m/2/0"]);
+    }
+}
@@ -21,6 +21,8 @@ const CHAIN_NAMES = {
   '43114': 'Avalanche',
   '11155111': 'Sepolia',
   '84532': 'Base Sepolia',
+  '324': 'zkSync Era',
+  '300': 'zkSync Sepolia',
 };
 
 function parseArgs() {
@@ -97,12 +99,15 @@ async function main() {
   console.log(`# Owners:            ${owners.join(', ')}`);
   console.log(`# Threshold:         ${safeConfig.threshold}`);
   console.log('');
+  const derivationArgs = safeConfig.zksync
+    ? ['--chain', 'zksync', '--singleton', safeConfig.singletonAddress, '--bytecode-hash', safeConfig.bytecodeHash]
+    : ['--init-code-hash', safeConfig.initCodeHash];
   console.log('--factory', safeConfig.factory);
-  console.log('--init-code-hash', safeConfig.initCodeHash);
+  for (let i = 0; i < derivationArgs.length; i += 2) console.log(derivationArgs[i], derivationArgs[i + 1]);
   console.log('--initializer-hash', safeConfig.initializerHash);
   console.log('');
   console.log('# Mine with: node run.js --owners', cli.owners, '--threshold', threshold, '--pattern <hex>');
-  console.log('# Or: safe_vanity -p dead --factory', safeConfig.factory, '--init-code-hash', safeConfig.initCodeHash, '--initializer-hash', safeConfig.initializerHash);
+  console.log('# Or: safe_vanity -p dead --factory', safeConfig.factory, ...derivationArgs, '--initializer-hash', safeConfig.initializerHash);
   console.log('');
   console.log('# Deploy: node deploy.js --owners', cli.owners, '--threshold', threshold, '--salt-nonce <DECIMAL> --rpc-url', config.rpcUrl, '--deploy --private-key <KEY>');
 }
@@ -10,7 +10,7 @@ import {
   ensure0x,
   ZERO,
   SAFE_SETUP_ABI,
-  computeCreate2Address,
+  computeSafeAddress,
   toChecksumAddress,
   saltNonceDecimalToBytes,
 } from './safe-config.js';
@@ -82,12 +82,7 @@ export async function deploySafe(owners, threshold, saltNonce, privateKey, confi
   const receipt = await publicClient.waitForTransactionReceipt({ hash: txHash });
 
   const saltNonceBytes = saltNonceDecimalToBytes(saltNonceBigInt.toString());
-  const addressBytes = computeCreate2Address(
-    safeConfig.factory,
-    safeConfig.initCodeHash,
-    safeConfig.initializerHash,
-    saltNonceBytes
-  );
+  const addressBytes = computeSafeAddress(safeConfig, saltNonceBytes);
   const address = toChecksumAddress(addressBytes);
 
   return { txHash, receipt, address };
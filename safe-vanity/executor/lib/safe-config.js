@@ -35,6 +35,22 @@ export const DEFAULT_CONFIG = {
   caseSensitive: false,
 };
 
+/** zkSync Era chains (mainnet, Sepolia): CREATE2 uses the zkSync formula. */
+export const ZKSYNC_CHAIN_IDS = ['324', '300'];
+
+/** SafeProxy bytecode hash per Safe version on zkSync Era (safe-deployments `zksync` type). */
+export const ZKSYNC_SAFE_PROXY_BYTECODE_HASHES = {
+  '1.3.0': '0x0100004124426fb9ebb25e27d670c068e52f9ba631bd383279a188be47e3f86d',
+  '1.4.1': '0x0100003b6cfa15bd7d1cae1c9c022074524d7785d34859ad0576d8fab4305d4f',
+};
+
+/** keccak256("zksyncCreate2"). */
+const ZKSYNC_CREATE2_PREFIX = '2020dba91b30cc0006188af794c2fb30dd8520db7e2c088b7fc7c103c00ca494';
+
+export function isZkSyncChain(chainId) {
+  return ZKSYNC_CHAIN_IDS.includes(String(chainId));
+}
+
 /** Safe.setup calldata ABI — export for deploy lib and REST API. */
 export const SAFE_SETUP_ABI = [
   {
@@ -127,18 +143,28 @@ export async function fetchSafeConfig(owners, threshold, config) {
     else fallbackHandler = ZERO;
   }
 
-  const publicClient = createPublicClient({ transport: http(config.rpcUrl) });
-  const creationCodeHex = await publicClient.readContract({
-    address: factoryAddress,
-    abi: FACTORY_ABI,
-    functionName: 'proxyCreationCode',
-    args: [],
-  });
-  const creationCode = strip0x(creationCodeHex);
   const singletonPadded = strip0x(ensure0x(singletonAddress)).padStart(64, '0');
-  const deploymentDataHex = '0x' + creationCode + singletonPadded;
-  const initCodeHashHex = viemKeccak256(deploymentDataHex);
-  const initCodeHash = strip0x(initCodeHashHex);
+  const zksync = isZkSyncChain(chainId);
+  let initCodeHashHex = null;
+  let bytecodeHash = null;
+  let constructorInputHash = null;
+  if (zksync) {
+    // zkSync CREATE2 hashes the proxy bytecode and constructor input separately.
+    bytecodeHash = config.zksyncBytecodeHash ?? ZKSYNC_SAFE_PROXY_BYTECODE_HASHES[singletonDeployment.version];
+    if (!bytecodeHash) throw new Error(`No zkSync SafeProxy bytecode hash for Safe ${singletonDeployment.version}`);
+    constructorInputHash = viemKeccak256('0x' + singletonPadded);
+  } else {
+    const publicClient = createPublicClient({ transport: http(config.rpcUrl) });
+    const creationCodeHex = await publicClient.readContract({
+      address: factoryAddress,
+      abi: FACTORY_ABI,
+      functionName: 'proxyCreationCode',
+      args: [],
+    });
+    const creationCode = strip0x(creationCodeHex);
+    const deploymentDataHex = '0x' + creationCode + singletonPadded;
+    initCodeHashHex = viemKeccak256(deploymentDataHex);
+  }
 
   const initializerCalldata = encodeFunctionData({
     abi: SAFE_SETUP_ABI,
@@ -159,7 +185,10 @@ export async function fetchSafeConfig(owners, threshold, config) {
 
   return {
     factory: factoryAddress,
-    initCodeHash: initCodeHashHex.startsWith('0x') ? initCodeHashHex : '0x' + initCodeHash,
+    initCodeHash: initCodeHashHex,
+    zksync,
+    bytecodeHash,
+    constructorInputHash,
     initializerHash: initializerHashHex.startsWith('0x') ? initializerHashHex : '0x' + initializerHash,
     chainId,
     rpcUrl: config.rpcUrl,
@@ -195,6 +224,38 @@ export function computeCreate2Address(factory, initCodeHash, initializerHash, sa
   return hash.slice(12, 32);
 }
 
+/**
+ * Compute a zkSync Era CREATE2 address for a Safe (same formula as the Rust miner with --chain zksync):
+ * keccak256(keccak256("zksyncCreate2") || pad32(factory) || salt || bytecodeHash || constructorInputHash)[12:32].
+ */
+export function computeZkSyncCreate2Address(factory, bytecodeHash, constructorInputHash, initializerHash, saltNonceBytes) {
+  const initializerHashBytes = Buffer.from(strip0x(initializerHash), 'hex');
+  const salt = Buffer.from(jsKeccak256.arrayBuffer(Buffer.concat([initializerHashBytes, saltNonceBytes])));
+  const preimage = Buffer.concat([
+    Buffer.from(ZKSYNC_CREATE2_PREFIX, 'hex'),
+    Buffer.from(strip0x(factory).padStart(64, '0'), 'hex'),
+    salt,
+    Buffer.from(strip0x(bytecodeHash), 'hex'),
+    Buffer.from(strip0x(constructorInputHash), 'hex'),
+  ]);
+  const hash = Buffer.from(jsKeccak256.arrayBuffer(preimage));
+  return hash.slice(12, 32);
+}
+
+/** Compute the Safe address for a fetched config, using the zkSync formula on zkSync chains. */
+export function computeSafeAddress(safeConfig, saltNonceBytes) {
+  if (safeConfig.zksync) {
+    return computeZkSyncCreate2Address(
+      safeConfig.factory,
+      safeConfig.bytecodeHash,
+      safeConfig.constructorInputHash,
+      safeConfig.initializerHash,
+      saltNonceBytes
+    );
+  }
+  return computeCreate2Address(safeConfig.factory, safeConfig.initCodeHash, safeConfig.initializerHash, saltNonceBytes);
+}
+
 /** EIP-55 checksum for 20-byte address. */
 export function toChecksumAddress(addressBytes) {
   const hex = addressBytes.toString('hex').toLowerCase();
@@ -223,12 +284,7 @@ export async function predictSafeAddress(owners, threshold, saltNonce, config) {
   const safeConfig = await fetchSafeConfig(owners, threshold, config);
   const saltNonceStr = typeof saltNonce === 'bigint' ? saltNonce.toString() : String(saltNonce);
   const saltNonceBytes = saltNonceDecimalToBytes(saltNonceStr);
-  const addressBytes = computeCreate2Address(
-    safeConfig.factory,
-    safeConfig.initCodeHash,
-    safeConfig.initializerHash,
-    saltNonceBytes
-  );
+  const addressBytes = computeSafeAddress(safeConfig, saltNonceBytes);
   const addressChecksum = toChecksumAddress(addressBytes);
   const saltNonceHex = BigInt(saltNonceStr).toString(16).padStart(64, '0');
   return {
@@ -61,6 +61,8 @@ function parseArgs() {
     workers: null,
     count: null,
     reportInterval: null,
+    childNonce: null,
+    parentPattern: null,
     deploy: false,
     privateKey: null,
   };
@@ -81,6 +83,8 @@ function parseArgs() {
     else if ((args[i] === '--workers' || args[i] === '-w') && args[i + 1]) out.workers = args[++i];
     else if ((args[i] === '--count' || args[i] === '-n') && args[i + 1]) out.count = args[++i];
     else if ((args[i] === '--report-interval' || args[i] === '-r') && args[i + 1]) out.reportInterval = args[++i];
+    else if (args[i] === '--child-nonce' && args[i + 1]) out.childNonce = args[++i];
+    else if (args[i] === '--parent-pattern' && args[i + 1]) out.parentPattern = args[++i];
     else if (args[i] === '--deploy') out.deploy = true;
     else if (args[i] === '--private-key' && args[i + 1]) out.privateKey = args[++i];
   }
@@ -129,6 +133,8 @@ async function main() {
     console.error('  --miner-path <bin>  Path to safe_vanity binary');
     console.error('  -w, --workers <n>   Worker threads');
     console.error('  -n, --count <n>      Stop after N matches (default 1)');
+    console.error('  --child-nonce <n>    Match the Safe\'s CREATE child at nonce n (1 = first, e.g. CreateCall)');
+    console.error('  --parent-pattern <hex>  Also require the Safe to start with this (with --child-nonce)');
     console.error('  --deploy             Deploy Safe after mining (confirmation prompt)');
     console.error('  --private-key <hex>  Deployer key (or set SAFE_DEPLOYER_PRIVATE_KEY)');
     process.exit(1);
@@ -167,14 +173,18 @@ async function main() {
   }
 
   console.log('Factory:   ', safeConfig.factory);
-  console.log('Init hash: ', safeConfig.initCodeHash.slice(0, 18) + '...');
+  if (safeConfig.zksync) console.log('Bytecode:  ', safeConfig.bytecodeHash.slice(0, 18) + '... (zkSync)');
+  else console.log('Init hash: ', safeConfig.initCodeHash.slice(0, 18) + '...');
   console.log('Initl hash:', safeConfig.initializerHash.slice(0, 18) + '...');
   console.log('');
 
+  const derivationArgs = safeConfig.zksync
+    ? ['--chain', 'zksync', '--singleton', safeConfig.singletonAddress, '--bytecode-hash', safeConfig.bytecodeHash]
+    : ['--init-code-hash', safeConfig.initCodeHash];
   const minerArgs = [
     '-p', cli.pattern,
     '--factory', safeConfig.factory,
-    '--init-code-hash', safeConfig.initCodeHash,
+    ...derivationArgs,
     '--initializer-hash', safeConfig.initializerHash,
     '-n', String(config.count ?? 1),
     '-r', String(config.reportInterval ?? 5),
@@ -183,6 +193,8 @@ async function main() {
   if (config.patternType && config.patternType !== 'prefix') minerArgs.push('-t', config.patternType);
   if (config.caseSensitive) minerArgs.push('-c');
   if (config.workers != null) minerArgs.push('-w', String(config.workers));
+  if (cli.childNonce != null) minerArgs.push('--child-nonce', cli.childNonce);
+  if (cli.parentPattern != null) minerArgs.push('--parent-pattern', cli.parentPattern);
 
   const { cmd, args, cwd } = resolveMiner(config.minerPath, minerArgs);
   console.log('Starting miner:', cwd ? `cargo run (${cwd})` : cmd, args.join(' '));
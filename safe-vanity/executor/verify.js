@@ -6,10 +6,21 @@
  *   node verify.js --factory <40-hex> --init-code-hash <64-hex> --initializer-hash <64-hex> --salt-nonce <decimal-or-64-hex>
  *   Salt-nonce: decimal (from miner "Salt (dec):") or 64-char hex. Computes address; compare with miner output.
  *
+ *   zkSync Era: replace --init-code-hash with --zksync --singleton <40-hex> [--bytecode-hash <64-hex>]
+ *   (bytecode hash defaults to the Safe 1.4.1 SafeProxy).
+ *
  * Mode 2 - Safe SDK: node verify.js --sdk [--rpc-url <url>] [--owner <0x...>] [--salt-nonce <uint256>]
  */
 
-import { strip0x, computeCreate2Address, toChecksumAddress, saltNonceDecimalToBytes } from './lib/safe-config.js';
+import { keccak256 } from 'viem';
+import {
+  strip0x,
+  computeCreate2Address,
+  computeZkSyncCreate2Address,
+  toChecksumAddress,
+  saltNonceDecimalToBytes,
+  ZKSYNC_SAFE_PROXY_BYTECODE_HASHES,
+} from './lib/safe-config.js';
 
 function hexToBytes(hex) {
   const h = strip0x(hex);
@@ -24,7 +35,37 @@ function saltNonceToBytes(saltNonceStr) {
   return saltNonceDecimalToBytes(saltNonceStr);
 }
 
+async function runZkSyncFormulaMode(args) {
+  const bytecodeHash = args.bytecodeHash ?? ZKSYNC_SAFE_PROXY_BYTECODE_HASHES['1.4.1'];
+  const singleton = hexToBytes(args.singleton);
+  const saltNonceBytes = saltNonceToBytes(args.saltNonce);
+
+  if (hexToBytes(args.factory).length !== 20) throw new Error('factory must be 20 bytes (40 hex chars)');
+  if (singleton.length !== 20) throw new Error('singleton must be 20 bytes (40 hex chars)');
+  if (hexToBytes(bytecodeHash).length !== 32) throw new Error('bytecode_hash must be 32 bytes');
+  if (hexToBytes(args.initializerHash).length !== 32) throw new Error('initializer_hash must be 32 bytes');
+  if (saltNonceBytes.length !== 32) throw new Error('salt_nonce must be 32 bytes (decimal or 64 hex)');
+
+  const constructorInputHash = keccak256('0x' + singleton.toString('hex').padStart(64, '0'));
+  const address = computeZkSyncCreate2Address(
+    args.factory,
+    bytecodeHash,
+    constructorInputHash,
+    args.initializerHash,
+    saltNonceBytes
+  );
+
+  console.log('zkSync CREATE2 formula result:');
+  console.log('Address:     ', toChecksumAddress(address));
+  console.log('Address hex: 0x' + address.toString('hex'));
+  console.log('Salt nonce:  ', args.saltNonce);
+}
+
 async function runFormulaMode(args) {
+  if (args.zksync) {
+    await runZkSyncFormulaMode(args);
+    return;
+  }
   const factory = hexToBytes(args.factory);
   const initCodeHash = hexToBytes(args.initCodeHash);
   const initializerHash = hexToBytes(args.initializerHash);
@@ -82,12 +123,15 @@ async function runSdkMode(args) {
 
 function parseArgs() {
   const args = process.argv.slice(2);
-  const out = { mode: 'formula', factory: null, initCodeHash: null, initializerHash: null, saltNonce: null, sdk: false, rpcUrl: null, owner: null };
+  const out = { mode: 'formula', factory: null, initCodeHash: null, initializerHash: null, saltNonce: null, sdk: false, rpcUrl: null, owner: null, zksync: false, singleton: null, bytecodeHash: null };
   for (let i = 0; i < args.length; i++) {
     if (args[i] === '--factory' && args[i + 1]) { out.factory = args[++i]; }
     else if (args[i] === '--init-code-hash' && args[i + 1]) { out.initCodeHash = args[++i]; }
     else if (args[i] === '--initializer-hash' && args[i + 1]) { out.initializerHash = args[++i]; }
     else if (args[i] === '--salt-nonce' && args[i + 1]) { out.saltNonce = args[++i]; }
+    else if (args[i] === '--zksync') { out.zksync = true; }
+    else if (args[i] === '--singleton' && args[i + 1]) { out.singleton = args[++i]; }
+    else if (args[i] === '--bytecode-hash' && args[i + 1]) { out.bytecodeHash = args[++i]; }
     else if (args[i] === '--sdk') { out.mode = 'sdk'; }
     else if (args[i] === '--rpc-url' && args[i + 1]) { out.rpcUrl = args[++i]; }
     else if (args[i] === '--owner' && args[i + 1]) { out.owner = args[++i]; }
@@ -103,8 +147,9 @@ async function main() {
     return;
   }
 
-  if (!args.factory || !args.initCodeHash || !args.initializerHash || !args.saltNonce) {
-    console.error('Formula mode requires: --factory, --init-code-hash, --initializer-hash, --salt-nonce');
+  const derivationArg = args.zksync ? args.singleton : args.initCodeHash;
+  if (!args.factory || !derivationArg || !args.initializerHash || !args.saltNonce) {
+    console.error('Formula mode requires: --factory, --init-code-hash (or --zksync --singleton), --initializer-hash, --salt-nonce');
     console.error('Salt-nonce: decimal (from miner) or 64-char hex.');
     console.error('Example: node verify.js --factory <40 hex> --init-code-hash <64 hex> --initializer-hash <64 hex> --salt-nonce 12345');
     console.error('');
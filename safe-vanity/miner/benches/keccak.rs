@@ -0,0 +1,76 @@
+//! Throughput of the Keccak backends against the scalar reference path.
+//!
+//! Run with `cargo bench --bench keccak`. For each derivation, every backend
+//! the CPU supports hashes the same nonces; the report gives nanoseconds per
+//! nonce and the speedup over `Scalar`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use safe_vanity::crypto::keccak_simd::BATCH_LANES;
+use safe_vanity::crypto::KeccakBackend;
+use safe_vanity::{BatchHasher, Derivation, SaltPreset};
+
+/// Minimum measuring time per backend.
+const MEASURE: Duration = Duration::from_secs(2);
+
+fn main() {
+    let derivations = [
+        (
+            "safe",
+            Derivation::Safe {
+                factory: [0x4e; 20],
+                init_code_hash: [0x76; 32],
+                initializer_hash: [0xa1; 32],
+            },
+        ),
+        (
+            "create2",
+            Derivation::Create2 {
+                deployer: [0x11; 20],
+                init_code_hash: [0x22; 32],
+                preset: SaltPreset::Raw,
+            },
+        ),
+    ];
+
+    for (name, derivation) in derivations {
+        let mut scalar_ns = None;
+        for backend in [KeccakBackend::Scalar, KeccakBackend::Avx2, KeccakBackend::Avx512] {
+            if !backend.is_supported() {
+                println!("{:<8} {:<16} unsupported on this CPU", name, backend.to_string());
+                continue;
+            }
+            let ns = nanos_per_nonce(&BatchHasher::new(backend, derivation.clone()));
+            let scalar = *scalar_ns.get_or_insert(ns);
+            println!(
+                "{:<8} {:<16} {:>8.1} ns/nonce  {:>5.2}x scalar",
+                name,
+                backend.to_string(),
+                ns,
+                scalar / ns
+            );
+        }
+    }
+}
+
+/// Hashes batches of distinct nonces for at least [`MEASURE`].
+fn nanos_per_nonce(hasher: &BatchHasher) -> f64 {
+    let mut nonces = [[0u8; 32]; BATCH_LANES];
+    let mut out = [[0u8; 20]; BATCH_LANES];
+    let mut counter = 0u64;
+    let mut batches = 0u64;
+    let start = Instant::now();
+    while start.elapsed() < MEASURE {
+        for _ in 0..1000 {
+            for nonce in nonces.iter_mut() {
+                counter += 1;
+                nonce[24..].copy_from_slice(&counter.to_be_bytes());
+            }
+            hasher.hash_batch(black_box(&nonces), &mut out);
+            black_box(&out);
+        }
+        batches += 1000;
+    }
+    start.elapsed().as_nanos() as f64 / (batches * BATCH_LANES as u64) as f64
+}
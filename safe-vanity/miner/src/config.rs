@@ -1,17 +1,151 @@
 //! Runtime configuration for Safe vanity address mining.
 
-use crate::matcher::PatternType;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::crypto::account::{
+    CoinbaseSmartWalletFactory, KernelFactory, LightAccountFactory, SimpleAccountFactory,
+};
+use crate::crypto::create2::{zksync_safe_constructor_input_hash, zksync_safe_deployment};
+use crate::crypto::erc6551::ERC6551_REGISTRY;
+use crate::crypto::{
+    clone_init_code_hash, keccak256, AccountFactory, AccountKind, Derivation, KeccakBackend, SaltPreset, TokenBoundAccount,
+};
+use crate::matcher::{ChildTarget, HookFlags, Pattern, PatternType};
 use clap::Parser;
 
+/// What is being mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Safe proxy via SafeProxyFactory (mines `saltNonce`)
+    #[default]
+    Safe,
+    /// Any CREATE2 deployment (mines the raw 32-byte salt)
+    Create2,
+    /// CREATE3 via a fixed proxy (mines the salt; address ignores init code)
+    Create3,
+    /// ERC-4337 smart account via its factory (mines the createAccount salt/nonce)
+    Account,
+    /// Uniswap v4 hook via CREATE2 (mines the salt; low 14 bits = --hooks flags)
+    Hook,
+    /// ERC-6551 token-bound account via the registry (mines the createAccount salt)
+    Tba,
+    /// EIP-1167 minimal-proxy clone of --implementation via CREATE2 (mines the salt)
+    Clone,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "safe" => Ok(Mode::Safe),
+            "create2" => Ok(Mode::Create2),
+            "create3" => Ok(Mode::Create3),
+            "account" => Ok(Mode::Account),
+            "hook" => Ok(Mode::Hook),
+            "tba" | "erc6551" => Ok(Mode::Tba),
+            "clone" | "eip1167" => Ok(Mode::Clone),
+            _ => Err(format!("Unknown mode: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mode::Safe => write!(f, "safe"),
+            Mode::Create2 => write!(f, "create2"),
+            Mode::Create3 => write!(f, "create3"),
+            Mode::Account => write!(f, "account"),
+            Mode::Hook => write!(f, "hook"),
+            Mode::Tba => write!(f, "tba"),
+            Mode::Clone => write!(f, "clone"),
+        }
+    }
+}
+
+/// Chain family, which selects the CREATE2 address formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Chain {
+    /// EIP-1014 CREATE2 (Ethereum and EVM-equivalent chains)
+    #[default]
+    Evm,
+    /// zkSync Era ContractDeployer CREATE2 (bytecode hash + constructor input hash)
+    ZkSync,
+}
+
+impl FromStr for Chain {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "evm" | "ethereum" => Ok(Chain::Evm),
+            "zksync" | "zksync-era" => Ok(Chain::ZkSync),
+            _ => Err(format!("Unknown chain: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Chain::Evm => write!(f, "evm"),
+            Chain::ZkSync => write!(f, "zksync"),
+        }
+    }
+}
+
+/// Salt layout required by the CREATE2 factory (create2 mode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaltPresetKind {
+    /// Any salt, used unchanged
+    #[default]
+    Raw,
+    /// ImmutableCreate2Factory: salt starts with the caller address
+    Immutable,
+    /// CreateX: caller / zero prefix, protection flag byte, guarded salt
+    CreateX,
+}
+
+impl FromStr for SaltPresetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "raw" | "none" => Ok(SaltPresetKind::Raw),
+            "immutable" | "immutablecreate2factory" => Ok(SaltPresetKind::Immutable),
+            "createx" => Ok(SaltPresetKind::CreateX),
+            _ => Err(format!("Unknown salt preset: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for SaltPresetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaltPresetKind::Raw => write!(f, "raw"),
+            SaltPresetKind::Immutable => write!(f, "immutable"),
+            SaltPresetKind::CreateX => write!(f, "createx"),
+        }
+    }
+}
+
 /// Safe Vanity Address Miner
 ///
 /// Mines saltNonce values until the CREATE2-derived Safe proxy address
-/// matches the given pattern (prefix/suffix/contains).
+/// matches the given pattern (prefix/suffix/contains). In create2 / create3
+/// mode, mines the salt of any CREATE2 / CREATE3 deployment instead; in
+/// account mode, the salt of an ERC-4337 smart account factory; in hook
+/// mode, a CREATE2 salt whose address carries the given Uniswap v4 hook flags;
+/// in tba mode, the ERC-6551 registry salt of an NFT's token-bound account;
+/// in clone mode, the CREATE2 salt of an EIP-1167 clone.
 #[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
 pub struct Config {
-    /// Pattern to search for (hex characters only: 0-9, a-f)
-    #[arg(short, long)]
+    /// Pattern to search for (hex characters only: 0-9, a-f). Optional in
+    /// hook mode
+    #[arg(short, long, default_value = "")]
     pub pattern: String,
 
     /// Suffix pattern (when used, --pattern becomes the prefix and matching uses both)
@@ -22,17 +156,107 @@ pub struct Config {
     #[arg(short = 't', long, default_value = "prefix")]
     pub pattern_type: PatternType,
 
-    /// SafeProxyFactory address (20 bytes, hex with or without 0x)
+    /// Mining mode: safe (saltNonce), create2 (raw salt), create3 (salt, deployer only),
+    /// account (ERC-4337 account factory salt), hook (Uniswap v4 hook salt),
+    /// tba (ERC-6551 token-bound account salt), or clone (EIP-1167 clone salt)
+    #[arg(short = 'm', long, default_value = "safe")]
+    pub mode: Mode,
+
+    /// Address formula: evm (EIP-1014) or zksync (zkSync Era; safe and create2 mode)
+    #[arg(long, default_value = "evm")]
+    pub chain: Chain,
+
+    /// SafeProxyFactory / CREATE2 deployer address (20 bytes, hex with or without 0x).
+    /// Defaults to the canonical factory address for --salt-preset immutable/createx,
+    /// to the zkSync SafeProxyFactory of --safe-version in zksync safe mode, and
+    /// to the ERC-6551 registry in tba mode
+    #[arg(long, visible_alias = "deployer")]
+    pub factory: Option<String>,
+
+    /// keccak256(init code) — 32 bytes hex. In safe mode: keccak256(creationCode || singleton)
+    #[arg(long, conflicts_with = "init_code")]
+    pub init_code_hash: Option<String>,
+
+    /// Init code (hex, or path to a file with hex or raw bytes); hashed to get --init-code-hash.
+    /// With --account simple: the factory's ERC1967Proxy creation code
+    #[arg(long)]
+    pub init_code: Option<String>,
+
+    /// keccak256(initializer) — 32 bytes hex (safe mode; from Safe setup: owners, threshold, etc.)
+    #[arg(long)]
+    pub initializer_hash: Option<String>,
+
+    /// Safe singleton (mastercopy) address, the SafeProxy constructor argument
+    /// (zksync safe mode)
+    #[arg(long)]
+    pub singleton: Option<String>,
+
+    /// zkSync bytecode hash of the deployed contract, 32 bytes hex (zksync).
+    /// Defaults to the SafeProxy bytecode hash of --safe-version in safe mode
+    #[arg(long)]
+    pub bytecode_hash: Option<String>,
+
+    /// Safe release whose zkSync factory and proxy bytecode hash are used: 1.3.0 or 1.4.1
+    #[arg(long, default_value = "1.4.1")]
+    pub safe_version: String,
+
+    /// Constructor arguments (ABI-encoded hex) of the deployed contract (zksync create2 mode)
+    #[arg(long)]
+    pub constructor_input: Option<String>,
+
+    /// Account factory preset (account mode): simple, light, kernel, or coinbase
+    #[arg(long)]
+    pub account: Option<AccountKind>,
+
+    /// Implementation address (account mode; tba and clone mode)
     #[arg(long)]
-    pub factory: String,
+    pub implementation: Option<String>,
 
-    /// keccak256(creationCode || singleton) — 32 bytes hex
+    /// Account owner address (simple, light); comma-separated owners, addresses or
+    /// 64-byte passkey public keys (coinbase)
+    #[arg(long, value_delimiter = ',')]
+    pub owner: Vec<String>,
+
+    /// `initialize` calldata passed to createAccount (account mode: kernel), hex
+    #[arg(long)]
+    pub init_data: Option<String>,
+
+    /// NFT contract of the token-bound account (tba mode)
+    #[arg(long)]
+    pub token_contract: Option<String>,
+
+    /// NFT token id of the token-bound account (tba mode), decimal or 0x hex
     #[arg(long)]
-    pub init_code_hash: String,
+    pub token_id: Option<String>,
 
-    /// keccak256(initializer) — 32 bytes hex (from Safe setup: owners, threshold, etc.)
+    /// Uniswap v4 hook callbacks to enable (hook mode), comma-separated, e.g.
+    /// beforeSwap,afterSwap; `none` or raw bits as 0x....
+    /// The low 14 address bits must equal exactly these flags
     #[arg(long)]
-    pub initializer_hash: String,
+    pub hooks: Option<HookFlags>,
+
+    /// Salt layout (create2/create3/hook mode): raw, immutable (ImmutableCreate2Factory), or createx
+    #[arg(long, default_value = "raw")]
+    pub salt_preset: SaltPresetKind,
+
+    /// Address that calls the factory; fixed as the salt prefix
+    /// (required for immutable, enables permissioned deploys for createx)
+    #[arg(long)]
+    pub caller: Option<String>,
+
+    /// Chain id for CreateX cross-chain redeploy protection (createx), or the
+    /// chain of the NFT (tba mode)
+    #[arg(long)]
+    pub chain_id: Option<u64>,
+
+    /// Match the contract the mined address deploys with CREATE at this nonce
+    /// (1 = first deployment of a contract, e.g. via CreateCall) instead of the address itself
+    #[arg(long)]
+    pub child_nonce: Option<u64>,
+
+    /// Also require the mined (parent) address to start with this pattern (with --child-nonce)
+    #[arg(long, requires = "child_nonce")]
+    pub parent_pattern: Option<String>,
 
     /// Number of worker threads (default: number of CPU cores)
     #[arg(short = 'w', long)]
@@ -49,6 +273,10 @@ pub struct Config {
     /// Progress report interval in seconds
     #[arg(short = 'r', long, default_value = "5")]
     pub report_interval: u64,
+
+    /// Keccak backend: auto, scalar, avx2, or avx512 (auto picks the widest supported)
+    #[arg(long, default_value = "auto")]
+    pub backend: KeccakBackend,
 }
 
 impl Config {
@@ -69,7 +297,7 @@ impl Config {
                 "Pattern must contain only hex characters (0-9, a-f)".into(),
             ));
         }
-        if pattern.is_empty() {
+        if pattern.is_empty() && self.mode != Mode::Hook {
             return Err(ConfigError::InvalidPattern("Pattern cannot be empty".into()));
         }
         if pattern.len() > 40 {
@@ -100,33 +328,321 @@ impl Config {
             }
         }
 
-        let factory_hex = self.factory.strip_prefix("0x").unwrap_or(&self.factory);
-        if factory_hex.len() != 40 || !factory_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        if let Some(ref parent_pattern) = self.parent_pattern {
+            let parent_norm = if self.case_sensitive {
+                parent_pattern.clone()
+            } else {
+                parent_pattern.to_lowercase()
+            };
+            if !parent_norm.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(ConfigError::InvalidPattern(
+                    "Parent pattern must contain only hex characters (0-9, a-f)".into(),
+                ));
+            }
+            if parent_norm.is_empty() || parent_norm.len() > 40 {
+                return Err(ConfigError::InvalidPattern(
+                    "Parent pattern must be 1 to 40 characters".into(),
+                ));
+            }
+        }
+        if self.child_nonce.is_some() && self.mode == Mode::Hook {
             return Err(ConfigError::InvalidConfig(
-                "factory must be 20 bytes (40 hex chars)".into(),
+                "--child-nonce cannot be combined with hook mode (flags apply to the hook itself)"
+                    .into(),
             ));
         }
 
-        let init_hash_hex = self
-            .init_code_hash
-            .strip_prefix("0x")
-            .unwrap_or(&self.init_code_hash);
-        if init_hash_hex.len() != 64 || !init_hash_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        if !matches!(self.mode, Mode::Create2 | Mode::Create3 | Mode::Hook)
+            && self.salt_preset != SaltPresetKind::Raw
+        {
             return Err(ConfigError::InvalidConfig(
-                "init_code_hash must be 32 bytes (64 hex chars)".into(),
+                "--salt-preset is only valid in create2/create3/hook mode".into(),
             ));
         }
+        match (self.mode, self.hooks) {
+            (Mode::Hook, None) => {
+                return Err(ConfigError::InvalidConfig(
+                    "--hooks is required in hook mode (use `none` for no callbacks)".into(),
+                ));
+            }
+            (mode, Some(_)) if mode != Mode::Hook => {
+                return Err(ConfigError::InvalidConfig(
+                    "--hooks is only valid in hook mode".into(),
+                ));
+            }
+            _ => {}
+        }
+        if let Some(ref caller) = self.caller {
+            if parse_hex::<20>(caller).is_none() {
+                return Err(ConfigError::InvalidConfig(
+                    "caller must be 20 bytes (40 hex chars)".into(),
+                ));
+            }
+        }
+        match self.salt_preset {
+            SaltPresetKind::Raw
+                if self.caller.is_some() || (self.chain_id.is_some() && self.mode != Mode::Tba) =>
+            {
+                return Err(ConfigError::InvalidConfig(
+                    "--caller/--chain-id require --salt-preset immutable or createx".into(),
+                ));
+            }
+            SaltPresetKind::Immutable if self.caller.is_none() => {
+                return Err(ConfigError::InvalidConfig(
+                    "--caller is required with --salt-preset immutable".into(),
+                ));
+            }
+            SaltPresetKind::Immutable if self.chain_id.is_some() => {
+                return Err(ConfigError::InvalidConfig(
+                    "--chain-id is only used with --salt-preset createx".into(),
+                ));
+            }
+            _ => {}
+        }
 
-        let initl_hash_hex = self
-            .initializer_hash
-            .strip_prefix("0x")
-            .unwrap_or(&self.initializer_hash);
-        if initl_hash_hex.len() != 64 || !initl_hash_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        if self.chain == Chain::ZkSync {
+            self.validate_zksync()?;
+        } else if self.singleton.is_some()
+            || self.bytecode_hash.is_some()
+            || self.constructor_input.is_some()
+        {
             return Err(ConfigError::InvalidConfig(
-                "initializer_hash must be 32 bytes (64 hex chars)".into(),
+                "--singleton/--bytecode-hash/--constructor-input require --chain zksync".into(),
             ));
         }
 
+        match self.factory.as_deref() {
+            Some(factory) => {
+                if parse_hex::<20>(factory).is_none() {
+                    return Err(ConfigError::InvalidConfig(
+                        "factory must be 20 bytes (40 hex chars)".into(),
+                    ));
+                }
+            }
+            None if self.default_factory().is_some() => {}
+            None => {
+                return Err(ConfigError::InvalidConfig(
+                    "--factory/--deployer is required".into(),
+                ));
+            }
+        }
+
+        if self.chain == Chain::ZkSync {
+            // The bytecode hash replaces the init code hash; checked above.
+        } else if self.mode == Mode::Create3 {
+            if self.init_code_hash.is_some() || self.init_code.is_some() {
+                return Err(ConfigError::InvalidConfig(
+                    "create3 addresses do not depend on init code; drop --init-code(-hash)".into(),
+                ));
+            }
+        } else if self.mode == Mode::Account {
+            self.validate_account()?;
+        } else if self.mode == Mode::Tba {
+            self.validate_token_bound()?;
+        } else {
+            self.resolve_init_code_hash()?;
+        }
+
+        if self.mode == Mode::Safe {
+            let initializer_hash = self.initializer_hash.as_deref().ok_or_else(|| {
+                ConfigError::InvalidConfig("--initializer-hash is required in safe mode".into())
+            })?;
+            if parse_hex::<32>(initializer_hash).is_none() {
+                return Err(ConfigError::InvalidConfig(
+                    "initializer_hash must be 32 bytes (64 hex chars)".into(),
+                ));
+            }
+        }
+
+        if !self.backend.is_supported() {
+            return Err(ConfigError::InvalidConfig(format!(
+                "backend {} is not supported on this CPU",
+                self.backend
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the zkSync inputs: bytecode hash, singleton (safe) or constructor input (create2).
+    fn validate_zksync(&self) -> Result<(), ConfigError> {
+        if !matches!(self.mode, Mode::Safe | Mode::Create2) {
+            return Err(ConfigError::InvalidConfig(
+                "--chain zksync supports safe and create2 mode only".into(),
+            ));
+        }
+        if self.salt_preset != SaltPresetKind::Raw {
+            return Err(ConfigError::InvalidConfig(
+                "--salt-preset is not available with --chain zksync".into(),
+            ));
+        }
+        if self.init_code_hash.is_some() || self.init_code.is_some() {
+            return Err(ConfigError::InvalidConfig(
+                "zkSync addresses use --bytecode-hash; drop --init-code(-hash)".into(),
+            ));
+        }
+        if self.child_nonce.is_some() {
+            // zkSync CREATE hashes "zksyncCreate", sender and nonce, not rlp([sender, nonce])
+            return Err(ConfigError::InvalidConfig(
+                "--child-nonce derives EVM CREATE addresses; not available with --chain zksync"
+                    .into(),
+            ));
+        }
+        if self.mode == Mode::Safe && zksync_safe_deployment(&self.safe_version).is_none() {
+            return Err(ConfigError::InvalidConfig(format!(
+                "no zkSync Safe deployment for version {} (use 1.3.0 or 1.4.1)",
+                self.safe_version
+            )));
+        }
+        match self.bytecode_hash.as_deref() {
+            Some(hash) if parse_hex::<32>(hash).is_none() => {
+                return Err(ConfigError::InvalidConfig(
+                    "bytecode_hash must be 32 bytes (64 hex chars)".into(),
+                ));
+            }
+            None if self.mode == Mode::Create2 => {
+                return Err(ConfigError::InvalidConfig(
+                    "--bytecode-hash is required in zksync create2 mode".into(),
+                ));
+            }
+            _ => {}
+        }
+        if self.mode == Mode::Safe {
+            let singleton = self.singleton.as_deref().ok_or_else(|| {
+                ConfigError::InvalidConfig("--singleton is required in zksync safe mode".into())
+            })?;
+            if parse_hex::<20>(singleton).is_none() {
+                return Err(ConfigError::InvalidConfig(
+                    "singleton must be 20 bytes (40 hex chars)".into(),
+                ));
+            }
+            if self.constructor_input.is_some() {
+                return Err(ConfigError::InvalidConfig(
+                    "the SafeProxy constructor input is derived from --singleton".into(),
+                ));
+            }
+        } else {
+            if self.singleton.is_some() {
+                return Err(ConfigError::InvalidConfig(
+                    "--singleton is only used in zksync safe mode".into(),
+                ));
+            }
+            if let Some(input) = self.constructor_input.as_deref() {
+                hex::decode(input.strip_prefix("0x").unwrap_or(input)).map_err(|e| {
+                    ConfigError::InvalidConfig(format!("constructor input is not valid hex: {}", e))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the NFT and implementation of a token-bound account.
+    fn validate_token_bound(&self) -> Result<(), ConfigError> {
+        if self.init_code_hash.is_some() || self.init_code.is_some() {
+            return Err(ConfigError::InvalidConfig(
+                "token-bound accounts derive the init code from --implementation; drop --init-code(-hash)"
+                    .into(),
+            ));
+        }
+        for (flag, value) in [
+            ("--implementation", &self.implementation),
+            ("--token-contract", &self.token_contract),
+        ] {
+            let value = value.as_deref().ok_or_else(|| {
+                ConfigError::InvalidConfig(format!("{} is required in tba mode", flag))
+            })?;
+            if parse_hex::<20>(value).is_none() {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "{} must be 20 bytes (40 hex chars)",
+                    flag
+                )));
+            }
+        }
+        let token_id = self.token_id.as_deref().ok_or_else(|| {
+            ConfigError::InvalidConfig("--token-id is required in tba mode".into())
+        })?;
+        if parse_uint256(token_id).is_none() {
+            return Err(ConfigError::InvalidConfig(
+                "token id must be a uint256 (decimal or 0x hex)".into(),
+            ));
+        }
+        if self.chain_id.is_none() {
+            return Err(ConfigError::InvalidConfig(
+                "--chain-id is required in tba mode".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks the inputs required by the selected account factory preset.
+    fn validate_account(&self) -> Result<(), ConfigError> {
+        let kind = self.account.ok_or_else(|| {
+            ConfigError::InvalidConfig("--account is required in account mode".into())
+        })?;
+        if kind == AccountKind::Simple {
+            // The proxy creation code comes from the factory's build; the
+            // constructor arguments are appended from --implementation/--owner.
+            if self.init_code_hash.is_some() {
+                return Err(ConfigError::InvalidConfig(
+                    "simple accounts derive the init code from --implementation and --owner; \
+                     pass the ERC1967Proxy creation code as --init-code"
+                        .into(),
+                ));
+            }
+            let code = self.init_code.as_deref().ok_or_else(|| {
+                ConfigError::InvalidConfig(
+                    "--init-code (ERC1967Proxy creation code) is required for simple accounts"
+                        .into(),
+                )
+            })?;
+            load_init_code(code)?;
+        } else if self.init_code_hash.is_some() || self.init_code.is_some() {
+            return Err(ConfigError::InvalidConfig(format!(
+                "{} accounts derive the init code from --implementation; drop --init-code(-hash)",
+                kind
+            )));
+        }
+        let implementation = self.implementation.as_deref().ok_or_else(|| {
+            ConfigError::InvalidConfig(format!("--implementation is required for {} accounts", kind))
+        })?;
+        if parse_hex::<20>(implementation).is_none() {
+            return Err(ConfigError::InvalidConfig(
+                "implementation must be 20 bytes (40 hex chars)".into(),
+            ));
+        }
+        match kind {
+            AccountKind::Simple | AccountKind::Light => {
+                if self.owner.len() != 1 || parse_hex::<20>(&self.owner[0]).is_none() {
+                    return Err(ConfigError::InvalidConfig(format!(
+                        "{} accounts need exactly one --owner address",
+                        kind
+                    )));
+                }
+            }
+            AccountKind::Kernel => {
+                let data = self.init_data.as_deref().ok_or_else(|| {
+                    ConfigError::InvalidConfig("--init-data is required for kernel accounts".into())
+                })?;
+                hex::decode(data.strip_prefix("0x").unwrap_or(data)).map_err(|e| {
+                    ConfigError::InvalidConfig(format!("init data is not valid hex: {}", e))
+                })?;
+            }
+            AccountKind::Coinbase => {
+                if self.owner.is_empty() {
+                    return Err(ConfigError::InvalidConfig(
+                        "coinbase accounts need at least one --owner".into(),
+                    ));
+                }
+                for owner in &self.owner {
+                    if parse_hex::<20>(owner).is_none() && parse_hex::<64>(owner).is_none() {
+                        return Err(ConfigError::InvalidConfig(format!(
+                            "owner {} must be an address or a 64-byte public key",
+                            owner
+                        )));
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -150,31 +666,239 @@ impl Config {
         })
     }
 
-    /// Factory address as 20 bytes (after validation).
+    /// Factory address as 20 bytes (after validation), falling back to the
+    /// salt preset's canonical factory or the zkSync SafeProxyFactory.
     pub fn factory_bytes(&self) -> [u8; 20] {
-        let h = self.factory.strip_prefix("0x").unwrap_or(&self.factory);
-        let bytes = hex::decode(h).expect("validated hex");
-        bytes.try_into().expect("20 bytes")
+        match self.factory.as_deref() {
+            Some(factory) => parse_hex(factory).expect("validated hex"),
+            None => self.default_factory().expect("validated"),
+        }
+    }
+
+    /// Canonical factory used when --factory is omitted.
+    fn default_factory(&self) -> Option<[u8; 20]> {
+        match (self.chain, self.mode) {
+            (Chain::ZkSync, Mode::Safe) => {
+                zksync_safe_deployment(&self.safe_version).map(|(factory, _)| factory)
+            }
+            (Chain::ZkSync, _) => None,
+            (Chain::Evm, Mode::Tba) => Some(ERC6551_REGISTRY),
+            (Chain::Evm, _) => self.salt_preset().default_factory(),
+        }
+    }
+
+    /// zkSync bytecode hash (after validation), falling back to the SafeProxy
+    /// bytecode hash of --safe-version.
+    pub fn bytecode_hash_bytes(&self) -> [u8; 32] {
+        match self.bytecode_hash.as_deref() {
+            Some(hash) => parse_hex(hash).expect("validated hex"),
+            None => zksync_safe_deployment(&self.safe_version).expect("validated").1,
+        }
     }
 
-    /// Init code hash as 32 bytes.
+    /// keccak256 of the constructor input on zkSync (after validation):
+    /// abi.encode(singleton) in safe mode, --constructor-input in create2 mode.
+    pub fn constructor_input_hash(&self) -> [u8; 32] {
+        if self.mode == Mode::Safe {
+            let singleton = parse_hex(self.singleton.as_deref().expect("validated")).expect("validated hex");
+            return zksync_safe_constructor_input_hash(&singleton);
+        }
+        let input = self.constructor_input.as_deref().unwrap_or("");
+        keccak256(&hex::decode(input.strip_prefix("0x").unwrap_or(input)).expect("validated hex"))
+    }
+
+    /// Salt preset for create2/create3 mode (after validation).
+    pub fn salt_preset(&self) -> SaltPreset {
+        let caller = self
+            .caller
+            .as_deref()
+            .map(|c| parse_hex::<20>(c).expect("validated hex"));
+        match self.salt_preset {
+            SaltPresetKind::Raw => SaltPreset::Raw,
+            SaltPresetKind::Immutable => SaltPreset::Immutable {
+                caller: caller.expect("validated"),
+            },
+            SaltPresetKind::CreateX => SaltPreset::CreateX {
+                caller,
+                chain_id: self.chain_id,
+            },
+        }
+    }
+
+    /// Human-readable summary of the salt constraints, if any.
+    pub fn salt_preset_description(&self) -> Option<String> {
+        let protection = match self.salt_preset() {
+            SaltPreset::Raw => return None,
+            SaltPreset::Immutable { .. } => "caller prefix".to_string(),
+            SaltPreset::CreateX { caller, chain_id } => match (caller, chain_id) {
+                (Some(_), Some(id)) => format!("permissioned, cross-chain protected (chain {})", id),
+                (Some(_), None) => "permissioned".to_string(),
+                (None, Some(id)) => format!("cross-chain protected (chain {})", id),
+                (None, None) => "unprotected".to_string(),
+            },
+        };
+        Some(format!(
+            "{} ({}, {} free bytes)",
+            self.salt_preset,
+            protection,
+            32 - self.salt_preset().fixed_len()
+        ))
+    }
+
+    /// Init code hash as 32 bytes, from --init-code-hash or by hashing --init-code.
     pub fn init_code_hash_bytes(&self) -> [u8; 32] {
-        let h = self
-            .init_code_hash
-            .strip_prefix("0x")
-            .unwrap_or(&self.init_code_hash);
-        let bytes = hex::decode(h).expect("validated hex");
-        bytes.try_into().expect("32 bytes")
+        self.resolve_init_code_hash().expect("validated")
     }
 
-    /// Initializer hash as 32 bytes.
+    /// Initializer hash as 32 bytes (safe mode).
     pub fn initializer_hash_bytes(&self) -> [u8; 32] {
-        let h = self
-            .initializer_hash
-            .strip_prefix("0x")
-            .unwrap_or(&self.initializer_hash);
-        let bytes = hex::decode(h).expect("validated hex");
-        bytes.try_into().expect("32 bytes")
+        parse_hex(self.initializer_hash.as_deref().expect("validated")).expect("validated hex")
+    }
+
+    /// Address derivation for the selected mode (after validation).
+    pub fn derivation(&self) -> Derivation {
+        if self.chain == Chain::ZkSync {
+            return Derivation::ZkSync {
+                deployer: self.factory_bytes(),
+                bytecode_hash: self.bytecode_hash_bytes(),
+                constructor_input_hash: self.constructor_input_hash(),
+                initializer_hash: (self.mode == Mode::Safe).then(|| self.initializer_hash_bytes()),
+            };
+        }
+        match self.mode {
+            Mode::Safe => Derivation::Safe {
+                factory: self.factory_bytes(),
+                init_code_hash: self.init_code_hash_bytes(),
+                initializer_hash: self.initializer_hash_bytes(),
+            },
+            Mode::Create2 | Mode::Hook | Mode::Clone => Derivation::Create2 {
+                deployer: self.factory_bytes(),
+                init_code_hash: self.init_code_hash_bytes(),
+                preset: self.salt_preset(),
+            },
+            Mode::Create3 => Derivation::Create3 {
+                deployer: self.factory_bytes(),
+                preset: self.salt_preset(),
+            },
+            Mode::Account => Derivation::Account(self.account_factory()),
+            Mode::Tba => Derivation::TokenBound(self.token_bound_account()),
+        }
+    }
+
+    /// Token-bound account for tba mode (after validation).
+    pub fn token_bound_account(&self) -> TokenBoundAccount {
+        let address = |value: &Option<String>| {
+            parse_hex::<20>(value.as_deref().expect("validated")).expect("validated hex")
+        };
+        let mut chain_id = [0u8; 32];
+        chain_id[24..].copy_from_slice(&self.chain_id.expect("validated").to_be_bytes());
+        TokenBoundAccount::new(
+            self.factory_bytes(),
+            address(&self.implementation),
+            chain_id,
+            address(&self.token_contract),
+            parse_uint256(self.token_id.as_deref().expect("validated")).expect("validated"),
+        )
+    }
+
+    /// Account factory for account mode (after validation).
+    pub fn account_factory(&self) -> Arc<dyn AccountFactory> {
+        let factory = self.factory_bytes();
+        let implementation = || {
+            parse_hex::<20>(self.implementation.as_deref().expect("validated")).expect("validated hex")
+        };
+        match self.account.expect("validated") {
+            AccountKind::Simple => {
+                let code = self.init_code.as_deref().expect("validated");
+                Arc::new(SimpleAccountFactory::new(
+                    factory,
+                    implementation(),
+                    parse_hex(&self.owner[0]).expect("validated hex"),
+                    &load_init_code(code).expect("validated"),
+                ))
+            }
+            AccountKind::Light => Arc::new(LightAccountFactory::new(
+                factory,
+                implementation(),
+                parse_hex(&self.owner[0]).expect("validated hex"),
+            )),
+            AccountKind::Kernel => {
+                let data = self.init_data.as_deref().expect("validated");
+                let data = hex::decode(data.strip_prefix("0x").unwrap_or(data)).expect("validated hex");
+                Arc::new(KernelFactory::new(factory, implementation(), &data))
+            }
+            AccountKind::Coinbase => {
+                let owners: Vec<Vec<u8>> = self
+                    .owner
+                    .iter()
+                    .map(|owner| match parse_hex::<20>(owner) {
+                        Some(address) => CoinbaseSmartWalletFactory::address_owner(&address),
+                        None => parse_hex::<64>(owner).expect("validated hex").to_vec(),
+                    })
+                    .collect();
+                Arc::new(CoinbaseSmartWalletFactory::new(factory, implementation(), &owners))
+            }
+        }
+    }
+
+    /// Reads --init-code-hash, or hashes --init-code (hex string or file),
+    /// or the EIP-1167 clone init code of --implementation in clone mode.
+    fn resolve_init_code_hash(&self) -> Result<[u8; 32], ConfigError> {
+        if self.mode == Mode::Clone {
+            if self.init_code_hash.is_some() || self.init_code.is_some() {
+                return Err(ConfigError::InvalidConfig(
+                    "clones derive the init code from --implementation; drop --init-code(-hash)"
+                        .into(),
+                ));
+            }
+            let implementation = self.implementation.as_deref().ok_or_else(|| {
+                ConfigError::InvalidConfig("--implementation is required in clone mode".into())
+            })?;
+            let implementation = parse_hex::<20>(implementation).ok_or_else(|| {
+                ConfigError::InvalidConfig("implementation must be 20 bytes (40 hex chars)".into())
+            })?;
+            return Ok(clone_init_code_hash(&implementation));
+        }
+        if let Some(ref hash) = self.init_code_hash {
+            return parse_hex(hash).ok_or_else(|| {
+                ConfigError::InvalidConfig("init_code_hash must be 32 bytes (64 hex chars)".into())
+            });
+        }
+        let init_code = self.init_code.as_deref().ok_or_else(|| {
+            ConfigError::InvalidConfig("--init-code-hash or --init-code is required".into())
+        })?;
+        Ok(keccak256(&load_init_code(init_code)?))
+    }
+
+    /// Builds the pattern, including hook flags.
+    pub fn pattern(&self) -> Pattern {
+        let pattern = if let Some(ref suffix) = self.normalized_suffix() {
+            Pattern::new_prefix_and_suffix(self.normalized_pattern(), suffix.clone(), self.case_sensitive)
+        } else {
+            Pattern::new(
+                self.normalized_pattern(),
+                self.effective_pattern_type(),
+                self.case_sensitive,
+            )
+        };
+        match self.hooks {
+            Some(flags) if self.mode == Mode::Hook => pattern.with_hook_flags(flags),
+            _ => pattern,
+        }
+    }
+
+    /// CREATE child the pattern applies to, if any.
+    pub fn child_target(&self) -> Option<ChildTarget> {
+        let parent_pattern = self.parent_pattern.as_ref().map(|p| {
+            let p = if self.case_sensitive {
+                p.clone()
+            } else {
+                p.to_lowercase()
+            };
+            Pattern::new(p, PatternType::Prefix, self.case_sensitive)
+        });
+        self.child_nonce
+            .map(|nonce| ChildTarget::new(nonce, parent_pattern))
     }
 
     /// Effective pattern type (prefix+suffix if suffix is set).
@@ -187,6 +911,64 @@ impl Config {
     }
 }
 
+/// Parses exactly N bytes of hex (with or without 0x).
+pub(crate) fn parse_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    let h = s.strip_prefix("0x").unwrap_or(s);
+    if h.len() != N * 2 {
+        return None;
+    }
+    hex::decode(h).ok()?.try_into().ok()
+}
+
+/// Parses a uint256 given in decimal or as 0x-prefixed hex, as a big-endian word.
+pub(crate) fn parse_uint256(s: &str) -> Option<[u8; 32]> {
+    let mut word = [0u8; 32];
+    if let Some(h) = s.strip_prefix("0x") {
+        if h.is_empty() || h.len() > 64 {
+            return None;
+        }
+        let bytes = hex::decode(format!("{:0>64}", h)).ok()?;
+        word.copy_from_slice(&bytes);
+        return Some(word);
+    }
+    if s.is_empty() {
+        return None;
+    }
+    for c in s.chars() {
+        let mut carry = c.to_digit(10)?;
+        for byte in word.iter_mut().rev() {
+            let v = *byte as u32 * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            return None;
+        }
+    }
+    Some(word)
+}
+
+/// Loads init code given inline as hex, or from a file holding hex text or raw bytes.
+fn load_init_code(arg: &str) -> Result<Vec<u8>, ConfigError> {
+    let inline = arg.strip_prefix("0x").unwrap_or(arg);
+    if inline.chars().all(|c| c.is_ascii_hexdigit()) && !std::path::Path::new(arg).exists() {
+        return hex::decode(inline)
+            .map_err(|e| ConfigError::InvalidConfig(format!("init code is not valid hex: {}", e)));
+    }
+
+    let raw = std::fs::read(arg)
+        .map_err(|e| ConfigError::InvalidConfig(format!("cannot read init code {}: {}", arg, e)))?;
+    let text = String::from_utf8_lossy(&raw);
+    let text = text.trim();
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_hexdigit()) {
+        hex::decode(text)
+            .map_err(|e| ConfigError::InvalidConfig(format!("init code is not valid hex: {}", e)))
+    } else {
+        Ok(raw)
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
     #[error("Invalid pattern: {0}")]
@@ -194,3 +976,338 @@ pub enum ConfigError {
     #[error("Invalid config: {0}")]
     InvalidConfig(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::account::simple_account_init_code;
+
+    fn parse(args: &[&str]) -> Config {
+        Config::try_parse_from(std::iter::once("safe_vanity").chain(args.iter().copied())).unwrap()
+    }
+
+    #[test]
+    fn test_safe_mode_requires_initializer_hash() {
+        let config = parse(&[
+            "-p", "dead",
+            "--factory", "0x4e1DCf7AD4e460CfD30791CCC4F9c8a4f820ec67",
+            "--init-code-hash", &"11".repeat(32),
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_create2_mode_hashes_init_code() {
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+            "--init-code", "0xdeadbeef",
+        ]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.init_code_hash_bytes(), keccak256(&[0xde, 0xad, 0xbe, 0xef]));
+        assert!(matches!(config.derivation(), Derivation::Create2 { .. }));
+    }
+
+    #[test]
+    fn test_immutable_preset_requires_caller() {
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "dead",
+            "--init-code", "0xdeadbeef",
+            "--salt-preset", "immutable",
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_createx_preset_defaults_factory() {
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "dead",
+            "--init-code", "0xdeadbeef",
+            "--salt-preset", "createx",
+            "--caller", "0x00000000000000000000000000000000deadbeef",
+            "--chain-id", "1",
+        ]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.factory_bytes(), crate::crypto::salt::CREATEX_FACTORY);
+        let mut caller = [0u8; 20];
+        caller[16..].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            config.salt_preset(),
+            SaltPreset::CreateX {
+                caller: Some(caller),
+                chain_id: Some(1),
+            }
+        );
+    }
+
+    #[test]
+    fn test_create3_mode_needs_no_init_code() {
+        let config = parse(&[
+            "-m", "create3",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+        ]);
+        assert!(config.validate().is_ok());
+        assert!(matches!(config.derivation(), Derivation::Create3 { .. }));
+
+        let config = parse(&[
+            "-m", "create3",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+            "--init-code", "0xdeadbeef",
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_account_mode_light() {
+        let config = parse(&[
+            "-m", "account",
+            "-p", "dead",
+            "--account", "light",
+            "--factory", "0x0000000000000000000000000000000000001111",
+            "--implementation", "0x0000000000000000000000000000000000002222",
+            "--owner", "0x0000000000000000000000000000000000003333",
+        ]);
+        assert!(config.validate().is_ok());
+        let factory = config.account_factory();
+        assert_eq!(factory.name(), "light");
+        let nonce = [7u8; 32];
+        assert_eq!(config.derivation().address(&nonce), factory.address(&nonce));
+    }
+
+    #[test]
+    fn test_account_mode_simple() {
+        let config = parse(&[
+            "-m", "account",
+            "-p", "dead",
+            "--account", "simple",
+            "--factory", "0x0000000000000000000000000000000000001111",
+            "--implementation", &"22".repeat(20),
+            "--owner", &"33".repeat(20),
+            "--init-code", "0x60806040",
+        ]);
+        assert!(config.validate().is_ok());
+        let factory = config.account_factory();
+        assert_eq!(factory.name(), "simple");
+        let init_code = simple_account_init_code(&[0x60, 0x80, 0x60, 0x40], &[0x22; 20], &[0x33; 20]);
+        assert_eq!(factory.init_code_hash(), &keccak256(&init_code));
+        let nonce = [7u8; 32];
+        assert_eq!(config.derivation().address(&nonce), factory.address(&nonce));
+    }
+
+    #[test]
+    fn test_account_mode_requires_inputs() {
+        let config = parse(&[
+            "-m", "account",
+            "-p", "dead",
+            "--account", "kernel",
+            "--factory", "0x0000000000000000000000000000000000001111",
+            "--implementation", "0x0000000000000000000000000000000000002222",
+        ]);
+        assert!(config.validate().is_err());
+
+        let config = parse(&[
+            "-m", "account",
+            "-p", "dead",
+            "--account", "simple",
+            "--factory", "0x0000000000000000000000000000000000001111",
+        ]);
+        assert!(config.validate().is_err());
+
+        let config = parse(&[
+            "-m", "account",
+            "-p", "dead",
+            "--account", "simple",
+            "--factory", "0x0000000000000000000000000000000000001111",
+            "--implementation", "0x0000000000000000000000000000000000002222",
+            "--owner", "0x0000000000000000000000000000000000003333",
+            "--init-code-hash", &"11".repeat(32),
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_hook_mode() {
+        let config = parse(&[
+            "-m", "hook",
+            "--deployer", "0x4e59b44847b379578588920cA78FbF26c0B4956C",
+            "--init-code-hash", &"11".repeat(32),
+            "--hooks", "beforeSwap,afterSwap",
+        ]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.pattern().hook_flags().map(|f| f.bits()), Some(0x00c0));
+        assert!(matches!(config.derivation(), Derivation::Create2 { .. }));
+
+        let config = parse(&[
+            "-m", "hook",
+            "--deployer", "0x4e59b44847b379578588920cA78FbF26c0B4956C",
+            "--init-code-hash", &"11".repeat(32),
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_zksync_safe_mode_defaults() {
+        let config = parse(&[
+            "--chain", "zksync",
+            "-p", "dead",
+            "--singleton", "0x0000000000000000000000000000000000001111",
+            "--initializer-hash", &"22".repeat(32),
+        ]);
+        assert!(config.validate().is_ok());
+        let (factory, bytecode_hash) = zksync_safe_deployment("1.4.1").unwrap();
+        assert_eq!(config.factory_bytes(), factory);
+        assert_eq!(config.bytecode_hash_bytes(), bytecode_hash);
+        assert!(matches!(
+            config.derivation(),
+            Derivation::ZkSync { initializer_hash: Some(_), .. }
+        ));
+
+        let config = parse(&[
+            "--chain", "zksync",
+            "-p", "dead",
+            "--initializer-hash", &"22".repeat(32),
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_zksync_create2_mode() {
+        let config = parse(&[
+            "--chain", "zksync",
+            "-m", "create2",
+            "-p", "dead",
+            "--deployer", "0x0000000000000000000000000000000000001111",
+            "--bytecode-hash", &"01".repeat(32),
+            "--constructor-input", "0xdeadbeef",
+        ]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.constructor_input_hash(), keccak256(&[0xde, 0xad, 0xbe, 0xef]));
+
+        let config = parse(&[
+            "--chain", "zksync",
+            "-m", "create3",
+            "-p", "dead",
+            "--deployer", "0x0000000000000000000000000000000000001111",
+        ]);
+        assert!(config.validate().is_err());
+
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "dead",
+            "--deployer", "0x0000000000000000000000000000000000001111",
+            "--init-code", "0xdeadbeef",
+            "--bytecode-hash", &"01".repeat(32),
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_tba_mode_defaults_registry() {
+        let config = parse(&[
+            "-m", "tba",
+            "-p", "dead",
+            "--implementation", "0x0000000000000000000000000000000000001111",
+            "--token-contract", "0x0000000000000000000000000000000000002222",
+            "--token-id", "300",
+            "--chain-id", "1",
+        ]);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.factory_bytes(), ERC6551_REGISTRY);
+        let mut token_id = [0u8; 32];
+        token_id[30..].copy_from_slice(&[0x01, 0x2c]);
+        let mut chain_id = [0u8; 32];
+        chain_id[31] = 1;
+        let mut implementation = [0u8; 20];
+        implementation[18..].copy_from_slice(&[0x11, 0x11]);
+        let mut token_contract = [0u8; 20];
+        token_contract[18..].copy_from_slice(&[0x22, 0x22]);
+        let account =
+            TokenBoundAccount::new(ERC6551_REGISTRY, implementation, chain_id, token_contract, token_id);
+        let nonce = [9u8; 32];
+        assert_eq!(config.derivation().address(&nonce), account.address(&nonce));
+
+        let config = parse(&[
+            "-m", "tba",
+            "-p", "dead",
+            "--implementation", "0x0000000000000000000000000000000000001111",
+            "--token-contract", "0x0000000000000000000000000000000000002222",
+            "--token-id", "300",
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_uint256() {
+        let mut expected = [0u8; 32];
+        expected[30..].copy_from_slice(&[0x01, 0x2c]);
+        assert_eq!(parse_uint256("300"), Some(expected));
+        assert_eq!(parse_uint256("0x12c"), Some(expected));
+        assert_eq!(parse_uint256(&"9".repeat(78)), None);
+        assert_eq!(parse_uint256("12a"), None);
+    }
+
+    #[test]
+    fn test_clone_mode_hashes_clone_init_code() {
+        let config = parse(&[
+            "-m", "clone",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+            "--implementation", "0x0000000000000000000000000000000000001111",
+        ]);
+        assert!(config.validate().is_ok());
+        let mut implementation = [0u8; 20];
+        implementation[18..].copy_from_slice(&[0x11, 0x11]);
+        assert_eq!(config.init_code_hash_bytes(), clone_init_code_hash(&implementation));
+        assert!(matches!(config.derivation(), Derivation::Create2 { .. }));
+
+        let config = parse(&[
+            "-m", "clone",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+            "--implementation", "0x0000000000000000000000000000000000001111",
+            "--init-code", "0xdeadbeef",
+        ]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_child_target() {
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+            "--init-code", "0xdeadbeef",
+            "--child-nonce", "1",
+            "--parent-pattern", "BEEF",
+        ]);
+        assert!(config.validate().is_ok());
+        let child = config.child_target().unwrap();
+        assert_eq!(child.nonce(), 1);
+        assert_eq!(child.parent_pattern().unwrap().pattern(), "beef");
+
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "dead",
+            "--deployer", "0x00000000000000000000000000000000deadbeef",
+            "--init-code", "0xdeadbeef",
+            "--child-nonce", "1",
+            "--parent-pattern", "xyz",
+        ]);
+        assert!(config.validate().is_err());
+
+        let config = parse(&[
+            "--chain", "zksync",
+            "-m", "create2",
+            "-p", "dead",
+            "--deployer", "0x0000000000000000000000000000000000001111",
+            "--bytecode-hash", &"01".repeat(32),
+            "--child-nonce", "1",
+        ]);
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidConfig(_))));
+    }
+}
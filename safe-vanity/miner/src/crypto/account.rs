@@ -0,0 +1,452 @@
+//! Counterfactual addresses of ERC-4337 smart accounts.
+//!
+//! Account factories deploy with CREATE2 like SafeProxyFactory, but each one
+//! derives the CREATE2 salt and init code differently. [`AccountFactory`]
+//! captures that difference so any factory can be mined the same way as a
+//! Safe: the mined 32-byte value is the `salt` / `nonce` argument of the
+//! factory's `createAccount`, and the address is
+//! `CREATE2(factory, factory_salt(mined), init_code_hash)`.
+//!
+//! Built-in presets:
+//! - [`SimpleAccountFactory`]: eth-infinitism SimpleAccount, raw salt,
+//!   OpenZeppelin ERC1967Proxy + `initialize(owner)` init code
+//! - [`LightAccountFactory`]: Alchemy LightAccount v2, salt = keccak256(owner || salt)
+//! - [`KernelFactory`]: ZeroDev Kernel v3, salt = keccak256(initData || salt)
+//! - [`CoinbaseSmartWalletFactory`]: salt = keccak256(abi.encode(owners, nonce))
+//!
+//! The last three deploy a Solady ERC1967 proxy, whose init code depends only
+//! on the implementation (see [`erc1967_init_code_hash`]).
+
+use std::fmt;
+
+use tiny_keccak::{Hasher, Keccak};
+
+use super::{create2_address, keccak256};
+
+/// How a factory turns the mined value into the CREATE2 salt.
+///
+/// Lets the multi-lane Keccak backends pick a specialised path; anything
+/// else is hashed one nonce at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaltShape {
+    /// The mined value is the CREATE2 salt.
+    Identity,
+    /// salt = keccak256(word || mined), the same shape as the Safe salt.
+    Prefixed([u8; 32]),
+    /// Any other transformation.
+    Custom,
+}
+
+/// A CREATE2 account factory.
+pub trait AccountFactory: fmt::Debug + Send + Sync {
+    /// Short preset name (e.g. "light").
+    fn name(&self) -> &'static str;
+
+    /// Address of the factory contract (executes CREATE2).
+    fn factory(&self) -> &[u8; 20];
+
+    /// keccak256 of the account init code.
+    fn init_code_hash(&self) -> &[u8; 32];
+
+    /// Returns the CREATE2 salt for a mined value.
+    fn salt(&self, nonce: &[u8; 32]) -> [u8; 32];
+
+    /// Describes [`AccountFactory::salt`] for the batch hasher.
+    fn salt_shape(&self) -> SaltShape {
+        SaltShape::Custom
+    }
+
+    /// Returns the counterfactual account address for a mined value.
+    fn address(&self, nonce: &[u8; 32]) -> [u8; 20] {
+        create2_address(self.factory(), &self.salt(nonce), self.init_code_hash())
+    }
+}
+
+/// Built-in account factory presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    Simple,
+    Light,
+    Kernel,
+    Coinbase,
+}
+
+impl std::str::FromStr for AccountKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "simple" | "simpleaccount" => Ok(AccountKind::Simple),
+            "light" | "lightaccount" => Ok(AccountKind::Light),
+            "kernel" => Ok(AccountKind::Kernel),
+            "coinbase" | "cbsw" => Ok(AccountKind::Coinbase),
+            _ => Err(format!("Unknown account factory: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for AccountKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountKind::Simple => write!(f, "simple"),
+            AccountKind::Light => write!(f, "light"),
+            AccountKind::Kernel => write!(f, "kernel"),
+            AccountKind::Coinbase => write!(f, "coinbase"),
+        }
+    }
+}
+
+/// keccak256 of the Solady `LibClone` ERC1967 proxy init code for `implementation`.
+pub fn erc1967_init_code_hash(implementation: &[u8; 20]) -> [u8; 32] {
+    let mut code = [0u8; 95];
+    code[0..9].copy_from_slice(&[0x60, 0x3d, 0x3d, 0x81, 0x60, 0x22, 0x3d, 0x39, 0x73]);
+    code[9..29].copy_from_slice(implementation);
+    code[29..31].copy_from_slice(&[0x60, 0x09]);
+    code[31..95].copy_from_slice(&ERC1967_INIT_CODE_TAIL);
+    keccak256(&code)
+}
+
+/// Runtime prelude of the Solady ERC1967 proxy: reads the implementation
+/// slot (`PUSH32 0x3608...2bbc`) and delegatecalls.
+const ERC1967_INIT_CODE_TAIL: [u8; 64] = [
+    0x51, 0x55, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x36, 0x3d, 0x7f, 0x36, 0x08, 0x94, 0xa1,
+    0x3b, 0xa1, 0xa3, 0x21, 0x06, 0x67, 0xc8, 0x28, 0x49, 0x2d, 0xb9, 0x8d, 0xca, 0x3e, 0x20, 0x76,
+    0xcc, 0x37, 0x35, 0xa9, 0x20, 0xa3, 0xca, 0x50, 0x5d, 0x38, 0x2b, 0xbc, 0x54, 0x5a, 0xf4, 0x3d,
+    0x60, 0x00, 0x80, 0x3e, 0x60, 0x38, 0x57, 0x3d, 0x60, 0x00, 0xfd, 0x5b, 0x3d, 0x60, 0x00, 0xf3,
+];
+
+/// eth-infinitism SimpleAccountFactory: `createAccount(owner, salt)`.
+///
+/// The salt is used as-is; the owner is baked into the init code
+/// (`ERC1967Proxy.creationCode || abi.encode(implementation, initialize(owner))`).
+/// The proxy creation code depends on the compiler settings the factory was
+/// built with, so it is passed in rather than embedded.
+#[derive(Debug, Clone)]
+pub struct SimpleAccountFactory {
+    factory: [u8; 20],
+    init_code_hash: [u8; 32],
+}
+
+impl SimpleAccountFactory {
+    pub fn new(
+        factory: [u8; 20],
+        implementation: [u8; 20],
+        owner: [u8; 20],
+        proxy_creation_code: &[u8],
+    ) -> Self {
+        Self {
+            factory,
+            init_code_hash: keccak256(&simple_account_init_code(
+                proxy_creation_code,
+                &implementation,
+                &owner,
+            )),
+        }
+    }
+}
+
+/// Selector of `SimpleAccount.initialize(address)`.
+const INITIALIZE_SELECTOR: [u8; 4] = [0xc4, 0xd6, 0x6d, 0xe8];
+
+/// Init code deployed by SimpleAccountFactory for `owner`: the proxy creation
+/// code followed by the ABI-encoded `ERC1967Proxy(address logic, bytes data)`
+/// constructor arguments, with `data = initialize(owner)`.
+pub fn simple_account_init_code(
+    proxy_creation_code: &[u8],
+    implementation: &[u8; 20],
+    owner: &[u8; 20],
+) -> Vec<u8> {
+    let mut code = proxy_creation_code.to_vec();
+    let mut args = [0u8; 160];
+    args[12..32].copy_from_slice(implementation);
+    args[63] = 0x40; // offset of `data`
+    args[95] = 4 + 32; // length of `data`
+    args[96..100].copy_from_slice(&INITIALIZE_SELECTOR);
+    args[112..132].copy_from_slice(owner);
+    code.extend_from_slice(&args);
+    code
+}
+
+impl AccountFactory for SimpleAccountFactory {
+    fn name(&self) -> &'static str {
+        "simple"
+    }
+
+    fn factory(&self) -> &[u8; 20] {
+        &self.factory
+    }
+
+    fn init_code_hash(&self) -> &[u8; 32] {
+        &self.init_code_hash
+    }
+
+    fn salt(&self, nonce: &[u8; 32]) -> [u8; 32] {
+        *nonce
+    }
+
+    fn salt_shape(&self) -> SaltShape {
+        SaltShape::Identity
+    }
+}
+
+/// Alchemy LightAccountFactory v2: `createAccount(owner, salt)`,
+/// CREATE2 salt = keccak256(abi.encode(owner, salt)).
+#[derive(Debug, Clone)]
+pub struct LightAccountFactory {
+    factory: [u8; 20],
+    init_code_hash: [u8; 32],
+    owner_word: [u8; 32],
+}
+
+impl LightAccountFactory {
+    pub fn new(factory: [u8; 20], implementation: [u8; 20], owner: [u8; 20]) -> Self {
+        let mut owner_word = [0u8; 32];
+        owner_word[12..].copy_from_slice(&owner);
+        Self {
+            factory,
+            init_code_hash: erc1967_init_code_hash(&implementation),
+            owner_word,
+        }
+    }
+}
+
+impl AccountFactory for LightAccountFactory {
+    fn name(&self) -> &'static str {
+        "light"
+    }
+
+    fn factory(&self) -> &[u8; 20] {
+        &self.factory
+    }
+
+    fn init_code_hash(&self) -> &[u8; 32] {
+        &self.init_code_hash
+    }
+
+    fn salt(&self, nonce: &[u8; 32]) -> [u8; 32] {
+        let mut preimage = [0u8; 64];
+        preimage[0..32].copy_from_slice(&self.owner_word);
+        preimage[32..64].copy_from_slice(nonce);
+        keccak256(&preimage)
+    }
+
+    fn salt_shape(&self) -> SaltShape {
+        SaltShape::Prefixed(self.owner_word)
+    }
+}
+
+/// ZeroDev KernelFactory (Kernel v3): `createAccount(data, salt)`,
+/// CREATE2 salt = keccak256(abi.encodePacked(data, salt)).
+#[derive(Debug, Clone)]
+pub struct KernelFactory {
+    factory: [u8; 20],
+    init_code_hash: [u8; 32],
+    /// `initialize` calldata, hashed before the salt.
+    data: Vec<u8>,
+}
+
+impl KernelFactory {
+    /// `data` is the `initialize` calldata passed to `createAccount`.
+    pub fn new(factory: [u8; 20], implementation: [u8; 20], data: &[u8]) -> Self {
+        Self {
+            factory,
+            init_code_hash: erc1967_init_code_hash(&implementation),
+            data: data.to_vec(),
+        }
+    }
+}
+
+impl AccountFactory for KernelFactory {
+    fn name(&self) -> &'static str {
+        "kernel"
+    }
+
+    fn factory(&self) -> &[u8; 20] {
+        &self.factory
+    }
+
+    fn init_code_hash(&self) -> &[u8; 32] {
+        &self.init_code_hash
+    }
+
+    fn salt(&self, nonce: &[u8; 32]) -> [u8; 32] {
+        hash_parts(&[&self.data, nonce])
+    }
+}
+
+/// Coinbase Smart Wallet factory: `createAccount(owners, nonce)`,
+/// CREATE2 salt = keccak256(abi.encode(bytes[] owners, uint256 nonce)).
+#[derive(Debug, Clone)]
+pub struct CoinbaseSmartWalletFactory {
+    factory: [u8; 20],
+    init_code_hash: [u8; 32],
+    /// ABI encoding with a zero nonce word (bytes 32..64).
+    encoded: Vec<u8>,
+}
+
+impl CoinbaseSmartWalletFactory {
+    /// Each owner is `abi.encode(address)` (32 bytes) or a passkey public key (64 bytes).
+    pub fn new(factory: [u8; 20], implementation: [u8; 20], owners: &[Vec<u8>]) -> Self {
+        Self {
+            factory,
+            init_code_hash: erc1967_init_code_hash(&implementation),
+            encoded: encode_bytes_array_and_nonce(owners),
+        }
+    }
+
+    /// Owner bytes for an EOA owner: `abi.encode(owner)`.
+    pub fn address_owner(owner: &[u8; 20]) -> Vec<u8> {
+        let mut word = vec![0u8; 32];
+        word[12..].copy_from_slice(owner);
+        word
+    }
+}
+
+impl AccountFactory for CoinbaseSmartWalletFactory {
+    fn name(&self) -> &'static str {
+        "coinbase"
+    }
+
+    fn factory(&self) -> &[u8; 20] {
+        &self.factory
+    }
+
+    fn init_code_hash(&self) -> &[u8; 32] {
+        &self.init_code_hash
+    }
+
+    fn salt(&self, nonce: &[u8; 32]) -> [u8; 32] {
+        hash_parts(&[&self.encoded[..32], nonce, &self.encoded[64..]])
+    }
+}
+
+/// keccak256 of the concatenation of `parts`.
+fn hash_parts(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut out = [0u8; 32];
+    hasher.finalize(&mut out);
+    out
+}
+
+/// abi.encode(bytes[] items, uint256 0): head (offset, nonce), then the
+/// array length, element offsets and length-prefixed, 32-byte padded elements.
+fn encode_bytes_array_and_nonce(items: &[Vec<u8>]) -> Vec<u8> {
+    let word = |n: usize| {
+        let mut w = [0u8; 32];
+        w[24..].copy_from_slice(&(n as u64).to_be_bytes());
+        w
+    };
+    let padded = |len: usize| len.div_ceil(32) * 32;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&word(0x40));
+    out.extend_from_slice(&[0u8; 32]);
+    out.extend_from_slice(&word(items.len()));
+    let mut offset = 32 * items.len();
+    for item in items {
+        out.extend_from_slice(&word(offset));
+        offset += 32 + padded(item.len());
+    }
+    for item in items {
+        out.extend_from_slice(&word(item.len()));
+        out.extend_from_slice(item);
+        out.resize(out.len() + padded(item.len()) - item.len(), 0);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erc1967_init_code_layout() {
+        let implementation = [0x11u8; 20];
+        let code = [
+            &hex::decode("603d3d8160223d3973").unwrap()[..],
+            &implementation,
+            &hex::decode(
+                "60095155f3363d3d373d3d363d7f360894a13ba1a3210667c828492db98dca3e2076\
+                 cc3735a920a3ca505d382bbc545af43d6000803e6038573d6000fd5b3d6000f3",
+            )
+            .unwrap(),
+        ]
+        .concat();
+        assert_eq!(code.len(), 95);
+        assert_eq!(erc1967_init_code_hash(&implementation), keccak256(&code));
+    }
+
+    #[test]
+    fn test_simple_account_init_code() {
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&keccak256(b"initialize(address)")[..4]);
+        assert_eq!(selector, INITIALIZE_SELECTOR);
+
+        let creation_code = [0x60u8, 0x80, 0x60, 0x40];
+        let code = simple_account_init_code(&creation_code, &[0x22; 20], &[0x33; 20]);
+        let expected = [
+            &creation_code[..],
+            &[0u8; 12],
+            &[0x22; 20],
+            &hex::decode(format!("{:064x}{:064x}c4d66de8", 0x40, 36)).unwrap(),
+            &[0u8; 12],
+            &[0x33; 20],
+            &[0u8; 28],
+        ]
+        .concat();
+        assert_eq!(code, expected);
+
+        let factory = SimpleAccountFactory::new([1u8; 20], [0x22; 20], [0x33; 20], &creation_code);
+        assert_eq!(factory.init_code_hash(), &keccak256(&expected));
+        assert_eq!(factory.salt(&[4u8; 32]), [4u8; 32]);
+    }
+
+    #[test]
+    fn test_light_account_salt() {
+        let factory = LightAccountFactory::new([1u8; 20], [2u8; 20], [3u8; 20]);
+        let nonce = [4u8; 32];
+        let mut preimage = vec![0u8; 12];
+        preimage.extend_from_slice(&[3u8; 20]);
+        preimage.extend_from_slice(&nonce);
+        assert_eq!(factory.salt(&nonce), keccak256(&preimage));
+        match factory.salt_shape() {
+            SaltShape::Prefixed(word) => assert_eq!(word[..], preimage[..32]),
+            shape => panic!("unexpected shape {:?}", shape),
+        }
+    }
+
+    #[test]
+    fn test_kernel_salt_is_packed() {
+        let data = [0xaau8; 5];
+        let factory = KernelFactory::new([1u8; 20], [2u8; 20], &data);
+        let nonce = [4u8; 32];
+        assert_eq!(factory.salt(&nonce), keccak256(&[&data[..], &nonce].concat()));
+    }
+
+    #[test]
+    fn test_coinbase_owner_encoding() {
+        let owners = vec![
+            CoinbaseSmartWalletFactory::address_owner(&[3u8; 20]),
+            vec![0x5a; 64],
+        ];
+        let encoded = encode_bytes_array_and_nonce(&owners);
+        let hex = hex::encode(&encoded);
+        let words: Vec<&str> = (0..hex.len() / 64).map(|i| &hex[i * 64..(i + 1) * 64]).collect();
+        assert_eq!(words.len(), 2 + 1 + 2 + 2 + 3);
+        assert!(words[0].ends_with("40"));
+        assert!(words[2].ends_with("02"));
+        assert!(words[3].ends_with("40")); // first element after 2 offset words
+        assert!(words[4].ends_with("80")); // 0x40 + 32 (len) + 32 (data)
+        assert!(words[5].ends_with("20"));
+        assert!(words[7].ends_with("40"));
+
+        let factory = CoinbaseSmartWalletFactory::new([1u8; 20], [2u8; 20], &owners);
+        let nonce = [4u8; 32];
+        let mut expected = encoded.clone();
+        expected[32..64].copy_from_slice(&nonce);
+        assert_eq!(factory.salt(&nonce), keccak256(&expected));
+    }
+}
@@ -0,0 +1,102 @@
+//! CREATE and CREATE3 address computation.
+//!
+//! CREATE: address = keccak256(rlp([deployer, nonce]))[12..32].
+//!
+//! CREATE3 (Solady / 0xSequence / CreateX): the deployer CREATE2s a fixed
+//! minimal proxy with the salt, and the proxy CREATEs the contract with
+//! nonce 1, so the address depends only on the deployer and the salt:
+//!   proxy   = CREATE2(deployer, salt, keccak256(PROXY_INIT_CODE))
+//!   address = CREATE(proxy, 1)
+
+use crate::crypto::{create2_address, keccak256};
+
+/// Init code of the CREATE3 proxy: `CALLDATACOPY` the child init code, `CREATE` it.
+pub const CREATE3_PROXY_INIT_CODE: [u8; 16] = [
+    0x67, 0x36, 0x3d, 0x3d, 0x37, 0x36, 0x3d, 0x34, 0xf0, 0x3d, 0x52, 0x60, 0x08, 0x60, 0x18, 0xf3,
+];
+
+/// keccak256(CREATE3_PROXY_INIT_CODE).
+pub const CREATE3_PROXY_INIT_CODE_HASH: [u8; 32] = [
+    0x21, 0xc3, 0x5d, 0xbe, 0x1b, 0x34, 0x4a, 0x24, 0x88, 0xcf, 0x33, 0x21, 0xd6, 0xce, 0x54, 0x2f,
+    0x8e, 0x9f, 0x30, 0x55, 0x44, 0xff, 0x09, 0xe4, 0x99, 0x3a, 0x62, 0x31, 0x9a, 0x49, 0x7c, 0x1f,
+];
+
+/// Computes the address of a contract created with CREATE by `deployer` at `nonce`.
+pub fn create_address(deployer: &[u8; 20], nonce: u64) -> [u8; 20] {
+    // rlp([deployer, nonce]): list header, 0x94 || deployer, then the nonce
+    // as a single byte (< 0x80), 0x80 for zero, or 0x80+len || big-endian bytes.
+    let mut rlp = [0u8; 32];
+    rlp[1] = 0x94;
+    rlp[2..22].copy_from_slice(deployer);
+    let mut len = 22;
+    match nonce {
+        0 => {
+            rlp[len] = 0x80;
+            len += 1;
+        }
+        1..=0x7f => {
+            rlp[len] = nonce as u8;
+            len += 1;
+        }
+        _ => {
+            let bytes = nonce.to_be_bytes();
+            let skip = nonce.leading_zeros() as usize / 8;
+            rlp[len] = 0x80 + (8 - skip) as u8;
+            rlp[len + 1..len + 1 + 8 - skip].copy_from_slice(&bytes[skip..]);
+            len += 1 + 8 - skip;
+        }
+    }
+    rlp[0] = 0xc0 + (len - 1) as u8;
+
+    let hash = keccak256(&rlp[..len]);
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&hash[12..32]);
+    addr
+}
+
+/// Computes the CREATE3 address for `deployer` and the salt it passes to CREATE2.
+pub fn create3_address(deployer: &[u8; 20], salt: &[u8; 32]) -> [u8; 20] {
+    let proxy = create2_address(deployer, salt, &CREATE3_PROXY_INIT_CODE_HASH);
+    create_address(&proxy, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_hex(deployer: &str, nonce: u64) -> String {
+        let deployer: [u8; 20] = hex::decode(deployer).unwrap().try_into().unwrap();
+        hex::encode(create_address(&deployer, nonce))
+    }
+
+    #[test]
+    fn test_create_address_vectors() {
+        let deployer = "6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+        assert_eq!(create_hex(deployer, 0), "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d");
+        assert_eq!(create_hex(deployer, 1), "343c43a37d37dff08ae8c4a11544c718abb4fcf8");
+        assert_eq!(create_hex(deployer, 2), "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91");
+        assert_eq!(create_hex(deployer, 3), "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c");
+    }
+
+    #[test]
+    fn test_create_address_multibyte_nonce() {
+        let deployer = [0x11u8; 20];
+        let mut rlp = vec![0xd8, 0x94];
+        rlp.extend_from_slice(&deployer);
+        rlp.extend_from_slice(&[0x82, 0x01, 0x00]);
+        assert_eq!(create_address(&deployer, 256)[..], keccak256(&rlp)[12..]);
+    }
+
+    #[test]
+    fn test_create3_proxy_hash() {
+        assert_eq!(keccak256(&CREATE3_PROXY_INIT_CODE), CREATE3_PROXY_INIT_CODE_HASH);
+    }
+
+    #[test]
+    fn test_create3_ignores_child_init_code() {
+        let deployer = [0x11u8; 20];
+        let salt = [0x22u8; 32];
+        let proxy = create2_address(&deployer, &salt, &CREATE3_PROXY_INIT_CODE_HASH);
+        assert_eq!(create3_address(&deployer, &salt), create_address(&proxy, 1));
+    }
+}
@@ -3,6 +3,14 @@
 //! Matches SafeProxyFactory.createProxyWithNonce:
 //!   salt = keccak256(abi.encodePacked(keccak256(initializer), saltNonce))
 //!   address = CREATE2(factory, salt, keccak256(deploymentData))[12:32]
+//!
+//! EIP-1167 clones (OpenZeppelin `Clones.cloneDeterministic`) are plain
+//! CREATE2 deployments of a 55-byte init code that embeds the implementation
+//! (see [`clone_init_code`]).
+//!
+//! zkSync Era replaces the EIP-1014 formula (see [`zksync_create2_address`]):
+//! the proxy is identified by its bytecode hash and the constructor input
+//! (`abi.encode(singleton)`) is hashed separately.
 
 use crate::crypto::keccak256;
 
@@ -15,17 +23,22 @@ pub fn safe_salt(initializer_hash: &[u8; 32], salt_nonce: &[u8; 32]) -> [u8; 32]
     keccak256(&preimage)
 }
 
-/// Computes the Safe proxy address (CREATE2).
-/// Preimage: 0xff (1) || factory (20) || salt (32) || init_code_hash (32) = 85 bytes.
-/// Address = keccak256(preimage)[12..32].
+/// Computes the Safe proxy address (CREATE2 from the SafeProxyFactory).
 pub fn safe_address(
     factory: &[u8; 20],
     init_code_hash: &[u8; 32],
     salt: &[u8; 32],
 ) -> [u8; 20] {
+    create2_address(factory, salt, init_code_hash)
+}
+
+/// Computes an EIP-1014 CREATE2 address for any deployer.
+/// Preimage: 0xff (1) || deployer (20) || salt (32) || init_code_hash (32) = 85 bytes.
+/// Address = keccak256(preimage)[12..32].
+pub fn create2_address(deployer: &[u8; 20], salt: &[u8; 32], init_code_hash: &[u8; 32]) -> [u8; 20] {
     let mut preimage = [0u8; 85];
     preimage[0] = 0xff;
-    preimage[1..21].copy_from_slice(factory);
+    preimage[1..21].copy_from_slice(deployer);
     preimage[21..53].copy_from_slice(salt);
     preimage[53..85].copy_from_slice(init_code_hash);
 
@@ -35,6 +48,81 @@ pub fn safe_address(
     addr
 }
 
+/// Init code of an EIP-1167 minimal proxy: 10-byte creation prefix, then the
+/// 45-byte runtime code that DELEGATECALLs `implementation`.
+pub fn clone_init_code(implementation: &[u8; 20]) -> [u8; 55] {
+    let mut init_code = [0u8; 55];
+    init_code[0..20].copy_from_slice(&[
+        0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d,
+        0x3d, 0x3d, 0x36, 0x3d, 0x73,
+    ]);
+    init_code[20..40].copy_from_slice(implementation);
+    init_code[40..55].copy_from_slice(&[
+        0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+    ]);
+    init_code
+}
+
+/// keccak256 of the EIP-1167 clone init code for `implementation`.
+pub fn clone_init_code_hash(implementation: &[u8; 20]) -> [u8; 32] {
+    keccak256(&clone_init_code(implementation))
+}
+
+/// keccak256("zksyncCreate2"), the domain prefix of zkSync Era CREATE2.
+pub const ZKSYNC_CREATE2_PREFIX: [u8; 32] = [
+    0x20, 0x20, 0xdb, 0xa9, 0x1b, 0x30, 0xcc, 0x00, 0x06, 0x18, 0x8a, 0xf7, 0x94, 0xc2, 0xfb, 0x30,
+    0xdd, 0x85, 0x20, 0xdb, 0x7e, 0x2c, 0x08, 0x8b, 0x7f, 0xc7, 0xc1, 0x03, 0xc0, 0x0c, 0xa4, 0x94,
+];
+
+/// Computes a zkSync Era CREATE2 address.
+/// Preimage: keccak256("zksyncCreate2") (32) || sender (32, left-padded) || salt (32)
+///   || bytecode_hash (32) || keccak256(constructor_input) (32) = 160 bytes.
+/// Address = keccak256(preimage)[12..32].
+pub fn zksync_create2_address(
+    sender: &[u8; 20],
+    salt: &[u8; 32],
+    bytecode_hash: &[u8; 32],
+    constructor_input_hash: &[u8; 32],
+) -> [u8; 20] {
+    let mut preimage = [0u8; 160];
+    preimage[0..32].copy_from_slice(&ZKSYNC_CREATE2_PREFIX);
+    preimage[44..64].copy_from_slice(sender);
+    preimage[64..96].copy_from_slice(salt);
+    preimage[96..128].copy_from_slice(bytecode_hash);
+    preimage[128..160].copy_from_slice(constructor_input_hash);
+
+    let hash = keccak256(&preimage);
+    let mut addr = [0u8; 20];
+    addr.copy_from_slice(&hash[12..32]);
+    addr
+}
+
+/// SafeProxyFactory address and SafeProxy bytecode hash of the zkSync Era
+/// Safe deployments (safe-deployments `zksync` deployment type).
+pub fn zksync_safe_deployment(version: &str) -> Option<([u8; 20], [u8; 32])> {
+    let (factory, bytecode_hash) = match version {
+        "1.3.0" => (
+            "daec33641865e4651fb43181c6db6f7232ee91c2",
+            "0100004124426fb9ebb25e27d670c068e52f9ba631bd383279a188be47e3f86d",
+        ),
+        "1.4.1" => (
+            "c329d02fd8cb2fc13aa919005af46320794a8629",
+            "0100003b6cfa15bd7d1cae1c9c022074524d7785d34859ad0576d8fab4305d4f",
+        ),
+        _ => return None,
+    };
+    let factory = hex::decode(factory).ok()?.try_into().ok()?;
+    let bytecode_hash = hex::decode(bytecode_hash).ok()?.try_into().ok()?;
+    Some((factory, bytecode_hash))
+}
+
+/// keccak256 of the SafeProxy constructor input on zkSync: abi.encode(singleton).
+pub fn zksync_safe_constructor_input_hash(singleton: &[u8; 20]) -> [u8; 32] {
+    let mut input = [0u8; 32];
+    input[12..32].copy_from_slice(singleton);
+    keccak256(&input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,6 +146,47 @@ mod tests {
         assert_eq!(a1, a2);
     }
 
+    fn create2_hex(deployer: &str, salt: &str, init_code: &str) -> String {
+        let deployer: [u8; 20] = hex::decode(deployer).unwrap().try_into().unwrap();
+        let salt: [u8; 32] = hex::decode(salt).unwrap().try_into().unwrap();
+        let init_code_hash = keccak256(&hex::decode(init_code).unwrap());
+        hex::encode(create2_address(&deployer, &salt, &init_code_hash))
+    }
+
+    /// Examples from EIP-1014.
+    #[test]
+    fn test_create2_eip1014_vectors() {
+        let zero_salt = "0000000000000000000000000000000000000000000000000000000000000000";
+        assert_eq!(
+            create2_hex("0000000000000000000000000000000000000000", zero_salt, "00"),
+            "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38"
+        );
+        assert_eq!(
+            create2_hex("deadbeef00000000000000000000000000000000", zero_salt, "00"),
+            "b928f69bb1d91cd65274e3c79d8986362984fda3"
+        );
+        assert_eq!(
+            create2_hex(
+                "deadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00"
+            ),
+            "d04116cdd17bebe565eb2422f2497e06cc1c9833"
+        );
+        assert_eq!(
+            create2_hex(
+                "00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef"
+            ),
+            "60f3f640a8508fc6a86d45df051962668e1e8ac7"
+        );
+        assert_eq!(
+            create2_hex("0000000000000000000000000000000000000000", zero_salt, ""),
+            "e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0"
+        );
+    }
+
     /// Known vector: all-zero inputs. Verifies formula is deterministic; run
     /// verify-with-safe-sdk/verify.js with same (zero) hex inputs to cross-check.
     #[test]
@@ -73,4 +202,62 @@ mod tests {
         let addr2 = safe_address(&factory, &init_hash, &safe_salt(&initializer_hash, &salt_nonce));
         assert_eq!(addr, addr2);
     }
+
+    #[test]
+    fn test_clone_init_code() {
+        // OpenZeppelin Clones: 0x3d602d80600a3d3981f3 || 363d3d373d3d3d363d73 || impl || 5af43d82803e903d91602b57fd5bf3
+        let implementation = [0xbbu8; 20];
+        let expected = format!(
+            "3d602d80600a3d3981f3363d3d373d3d3d363d73{}5af43d82803e903d91602b57fd5bf3",
+            "bb".repeat(20)
+        );
+        assert_eq!(hex::encode(clone_init_code(&implementation)), expected);
+        assert_eq!(
+            clone_init_code_hash(&implementation),
+            keccak256(&hex::decode(expected).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_zksync_safe_deployments() {
+        for version in ["1.3.0", "1.4.1"] {
+            let (_, bytecode_hash) = zksync_safe_deployment(version).unwrap();
+            // zkSync bytecode hash: version byte 0x01, 0x00, then the length in
+            // 32-byte words, which zkSync requires to be odd.
+            assert_eq!(bytecode_hash[0..2], [0x01, 0x00]);
+            let words = u16::from_be_bytes([bytecode_hash[2], bytecode_hash[3]]);
+            assert_eq!(words % 2, 1, "{}", version);
+        }
+        assert!(zksync_safe_deployment("1.2.0").is_none());
+    }
+
+    #[test]
+    fn test_zksync_prefix() {
+        assert_eq!(keccak256(b"zksyncCreate2"), ZKSYNC_CREATE2_PREFIX);
+    }
+
+    #[test]
+    fn test_zksync_create2_layout() {
+        let sender = [0x11u8; 20];
+        let salt = [0x22u8; 32];
+        let bytecode_hash = [0x33u8; 32];
+        let input_hash = keccak256(&[]);
+        let preimage = [
+            &keccak256(b"zksyncCreate2")[..],
+            &[0u8; 12],
+            &sender,
+            &salt,
+            &bytecode_hash,
+            &input_hash,
+        ]
+        .concat();
+        assert_eq!(
+            zksync_create2_address(&sender, &salt, &bytecode_hash, &input_hash)[..],
+            keccak256(&preimage)[12..]
+        );
+        assert_ne!(
+            zksync_create2_address(&sender, &salt, &bytecode_hash, &input_hash),
+            create2_address(&sender, &salt, &bytecode_hash)
+        );
+    }
 }
@@ -0,0 +1,207 @@
+//! How a mined 32-byte value turns into a deployed address.
+//!
+//! Every mode mines the same thing: a 32-byte big-endian counter that the
+//! workers increment. The derivation decides what that value means (a Safe
+//! `saltNonce`, a raw CREATE2 salt, ...) and how the address is computed.
+
+use std::sync::Arc;
+
+use super::account::AccountFactory;
+use super::create::{create3_address, CREATE3_PROXY_INIT_CODE_HASH};
+use super::create2::{create2_address, safe_salt, zksync_create2_address};
+use super::erc6551::TokenBoundAccount;
+use super::salt::SaltPreset;
+
+/// Address derivation for one mining mode.
+#[derive(Debug, Clone)]
+pub enum Derivation {
+    /// SafeProxyFactory: the mined value is `saltNonce`,
+    /// salt = keccak256(initializerHash || saltNonce).
+    Safe {
+        factory: [u8; 20],
+        init_code_hash: [u8; 32],
+        initializer_hash: [u8; 32],
+    },
+    /// CREATE2 through a factory: the mined value is the salt passed to the
+    /// factory, constrained and transformed according to `preset`.
+    Create2 {
+        deployer: [u8; 20],
+        init_code_hash: [u8; 32],
+        preset: SaltPreset,
+    },
+    /// ERC-4337 account factory: the mined value is the `salt` / `nonce`
+    /// argument of `createAccount`.
+    Account(Arc<dyn AccountFactory>),
+    /// zkSync Era CREATE2 (ContractDeployer formula). With `initializer_hash`
+    /// the mined value is a Safe `saltNonce`; otherwise it is the raw salt.
+    ZkSync {
+        deployer: [u8; 20],
+        bytecode_hash: [u8; 32],
+        constructor_input_hash: [u8; 32],
+        initializer_hash: Option<[u8; 32]>,
+    },
+    /// ERC-6551 registry: the mined value is the `salt` of `createAccount`,
+    /// which also appears in the account's init code.
+    TokenBound(TokenBoundAccount),
+    /// CREATE3 through a factory: the mined value is the salt passed to the
+    /// factory; the address depends only on the deployer and that salt.
+    Create3 {
+        deployer: [u8; 20],
+        preset: SaltPreset,
+    },
+}
+
+impl Derivation {
+    /// Returns the CREATE2 salt for a mined value.
+    pub fn salt(&self, nonce: &[u8; 32]) -> [u8; 32] {
+        match self {
+            Derivation::Safe {
+                initializer_hash, ..
+            } => safe_salt(initializer_hash, nonce),
+            Derivation::Create2 { preset, .. } | Derivation::Create3 { preset, .. } => {
+                preset.guard(nonce)
+            }
+            Derivation::Account(factory) => factory.salt(nonce),
+            Derivation::TokenBound(_) => *nonce,
+            Derivation::ZkSync {
+                initializer_hash, ..
+            } => match initializer_hash {
+                Some(initializer_hash) => safe_salt(initializer_hash, nonce),
+                None => *nonce,
+            },
+        }
+    }
+
+    /// Forces the bytes of a mined value that the deployer fixes
+    /// (e.g. the caller prefix of a protected salt).
+    #[inline]
+    pub fn apply_prefix(&self, nonce: &mut [u8; 32]) {
+        if let Derivation::Create2 { preset, .. } | Derivation::Create3 { preset, .. } = self {
+            preset.apply_prefix(nonce);
+        }
+    }
+
+    /// Returns the address for a mined value.
+    pub fn address(&self, nonce: &[u8; 32]) -> [u8; 20] {
+        let salt = self.salt(nonce);
+        match self {
+            Derivation::Create3 { deployer, .. } => create3_address(deployer, &salt),
+            Derivation::ZkSync {
+                deployer,
+                bytecode_hash,
+                constructor_input_hash,
+                ..
+            } => zksync_create2_address(deployer, &salt, bytecode_hash, constructor_input_hash),
+            Derivation::TokenBound(account) => account.address(&salt),
+            _ => create2_address(
+                self.deployer(),
+                &salt,
+                self.init_code_hash().expect("fixed init code"),
+            ),
+        }
+    }
+
+    /// Address that executes CREATE2.
+    pub fn deployer(&self) -> &[u8; 20] {
+        match self {
+            Derivation::Safe { factory, .. } => factory,
+            Derivation::Create2 { deployer, .. } | Derivation::Create3 { deployer, .. } => deployer,
+            Derivation::Account(factory) => factory.factory(),
+            Derivation::ZkSync { deployer, .. } => deployer,
+            Derivation::TokenBound(account) => account.registry(),
+        }
+    }
+
+    /// keccak256 of the init code deployed by CREATE2 (the fixed proxy for
+    /// CREATE3, the bytecode hash on zkSync), or `None` if the init code
+    /// depends on the salt (ERC-6551).
+    pub fn init_code_hash(&self) -> Option<&[u8; 32]> {
+        match self {
+            Derivation::Safe { init_code_hash, .. } | Derivation::Create2 { init_code_hash, .. } => {
+                Some(init_code_hash)
+            }
+            Derivation::Create3 { .. } => Some(&CREATE3_PROXY_INIT_CODE_HASH),
+            Derivation::Account(factory) => Some(factory.init_code_hash()),
+            Derivation::ZkSync { bytecode_hash, .. } => Some(bytecode_hash),
+            Derivation::TokenBound(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::safe_address;
+
+    #[test]
+    fn test_safe_derivation_matches_safe_address() {
+        let derivation = Derivation::Safe {
+            factory: [1u8; 20],
+            init_code_hash: [2u8; 32],
+            initializer_hash: [3u8; 32],
+        };
+        let nonce = [4u8; 32];
+        let salt = safe_salt(&[3u8; 32], &nonce);
+        assert_eq!(derivation.salt(&nonce), salt);
+        assert_eq!(derivation.address(&nonce), safe_address(&[1u8; 20], &[2u8; 32], &salt));
+    }
+
+    #[test]
+    fn test_create2_derivation_uses_raw_salt() {
+        let derivation = Derivation::Create2 {
+            deployer: [1u8; 20],
+            init_code_hash: [2u8; 32],
+            preset: SaltPreset::Raw,
+        };
+        let nonce = [4u8; 32];
+        assert_eq!(derivation.salt(&nonce), nonce);
+        assert_eq!(derivation.address(&nonce), create2_address(&[1u8; 20], &nonce, &[2u8; 32]));
+    }
+
+    #[test]
+    fn test_create2_derivation_applies_createx_guard() {
+        let preset = SaltPreset::CreateX {
+            caller: Some([5u8; 20]),
+            chain_id: None,
+        };
+        let derivation = Derivation::Create2 {
+            deployer: [1u8; 20],
+            init_code_hash: [2u8; 32],
+            preset,
+        };
+        let mut nonce = [4u8; 32];
+        derivation.apply_prefix(&mut nonce);
+        assert_eq!(nonce[0..20], [5u8; 20]);
+        assert_eq!(nonce[20], 0x00);
+        let guarded = preset.guard(&nonce);
+        assert_ne!(guarded, nonce);
+        assert_eq!(derivation.address(&nonce), create2_address(&[1u8; 20], &guarded, &[2u8; 32]));
+    }
+
+    #[test]
+    fn test_create3_derivation() {
+        let derivation = Derivation::Create3 {
+            deployer: [1u8; 20],
+            preset: SaltPreset::Raw,
+        };
+        let nonce = [4u8; 32];
+        assert_eq!(derivation.address(&nonce), create3_address(&[1u8; 20], &nonce));
+    }
+
+    #[test]
+    fn test_zksync_safe_derivation() {
+        let derivation = Derivation::ZkSync {
+            deployer: [1u8; 20],
+            bytecode_hash: [2u8; 32],
+            constructor_input_hash: [5u8; 32],
+            initializer_hash: Some([3u8; 32]),
+        };
+        let nonce = [4u8; 32];
+        let salt = safe_salt(&[3u8; 32], &nonce);
+        assert_eq!(derivation.salt(&nonce), salt);
+        assert_eq!(
+            derivation.address(&nonce),
+            zksync_create2_address(&[1u8; 20], &salt, &[2u8; 32], &[5u8; 32])
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! ERC-6551 token-bound account addresses.
+//!
+//! The reference registry deploys an EIP-1167 proxy with the account's
+//! context appended to its runtime code:
+//!   initCode = ERC1167 header || implementation || footer
+//!              || abi.encode(salt, chainId, tokenContract, tokenId)
+//!   address  = CREATE2(registry, salt, keccak256(initCode))
+//! The salt appears in both the CREATE2 salt and the init code, so every
+//! mined salt hashes a fresh 183-byte init code.
+
+use crate::crypto::{create2_address, keccak256};
+
+/// Canonical ERC-6551 registry (same address on every chain).
+pub const ERC6551_REGISTRY: [u8; 20] = [
+    0x00, 0x00, 0x00, 0x00, 0x65, 0x51, 0xc1, 0x94, 0x87, 0x81, 0x46, 0x12, 0xe5, 0x8f, 0xe0, 0x68,
+    0x13, 0x77, 0x57, 0x58,
+];
+
+/// Creation code and proxy prefix up to the implementation address.
+const HEADER: [u8; 20] = [
+    0x3d, 0x60, 0xad, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d,
+    0x3d, 0x36, 0x3d, 0x73,
+];
+
+/// Proxy suffix after the implementation address.
+const FOOTER: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+/// Offset of the salt word in the init code.
+const SALT_OFFSET: usize = HEADER.len() + 20 + FOOTER.len();
+
+/// Length of the account init code.
+pub const INIT_CODE_LEN: usize = SALT_OFFSET + 4 * 32;
+
+/// A token-bound account of one NFT, deployed through an ERC-6551 registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBoundAccount {
+    registry: [u8; 20],
+    /// Init code with a zero salt word; the salt is written per nonce.
+    init_code: [u8; INIT_CODE_LEN],
+}
+
+impl TokenBoundAccount {
+    /// Token-bound account for `token_contract` / `token_id` on `chain_id`.
+    /// `chain_id` and `token_id` are uint256 words.
+    pub fn new(
+        registry: [u8; 20],
+        implementation: [u8; 20],
+        chain_id: [u8; 32],
+        token_contract: [u8; 20],
+        token_id: [u8; 32],
+    ) -> Self {
+        let mut init_code = [0u8; INIT_CODE_LEN];
+        init_code[..HEADER.len()].copy_from_slice(&HEADER);
+        init_code[HEADER.len()..HEADER.len() + 20].copy_from_slice(&implementation);
+        init_code[HEADER.len() + 20..SALT_OFFSET].copy_from_slice(&FOOTER);
+        init_code[SALT_OFFSET + 32..SALT_OFFSET + 64].copy_from_slice(&chain_id);
+        init_code[SALT_OFFSET + 76..SALT_OFFSET + 96].copy_from_slice(&token_contract);
+        init_code[SALT_OFFSET + 96..].copy_from_slice(&token_id);
+        Self {
+            registry,
+            init_code,
+        }
+    }
+
+    /// Registry address that executes CREATE2.
+    pub fn registry(&self) -> &[u8; 20] {
+        &self.registry
+    }
+
+    /// keccak256 of the init code deployed with `salt`.
+    pub fn init_code_hash(&self, salt: &[u8; 32]) -> [u8; 32] {
+        let mut init_code = self.init_code;
+        init_code[SALT_OFFSET..SALT_OFFSET + 32].copy_from_slice(salt);
+        keccak256(&init_code)
+    }
+
+    /// Account address for `salt` (as returned by the registry's `account`).
+    pub fn address(&self, salt: &[u8; 32]) -> [u8; 20] {
+        create2_address(&self.registry, salt, &self.init_code_hash(salt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_code_layout() {
+        let account = TokenBoundAccount::new(
+            ERC6551_REGISTRY,
+            [0x11; 20],
+            [0x22; 32],
+            [0x33; 20],
+            [0x44; 32],
+        );
+        let salt = [0x55u8; 32];
+        let mut expected = hex::decode("3d60ad80600a3d3981f3363d3d373d3d3d363d73").unwrap();
+        expected.extend_from_slice(&[0x11; 20]);
+        expected.extend_from_slice(&hex::decode("5af43d82803e903d91602b57fd5bf3").unwrap());
+        expected.extend_from_slice(&salt);
+        expected.extend_from_slice(&[0x22; 32]);
+        expected.extend_from_slice(&[0u8; 12]);
+        expected.extend_from_slice(&[0x33; 20]);
+        expected.extend_from_slice(&[0x44; 32]);
+        assert_eq!(expected.len(), INIT_CODE_LEN);
+        assert_eq!(account.init_code_hash(&salt), keccak256(&expected));
+        assert_eq!(
+            account.address(&salt),
+            create2_address(&ERC6551_REGISTRY, &salt, &keccak256(&expected))
+        );
+    }
+}
@@ -0,0 +1,692 @@
+//! Multi-lane Keccak-f1600 for hashing several salt nonces at once.
+//!
+//! A Safe attempt is two single-block Keccak-256 permutations over fixed-size
+//! preimages (64 bytes for the salt, 85 bytes for the CREATE2 address); a raw
+//! CREATE2 attempt is only the second one. Instead
+//! of going through `tiny_keccak` one nonce at a time, each of the 25 state
+//! lanes is a SIMD register holding that lane for N nonces, so a single
+//! permutation hashes N nonces:
+//!
+//! - `Scalar`: one nonce at a time via `tiny_keccak` (reference path)
+//! - `Avx2`:   4 lanes per permutation (256-bit registers)
+//! - `Avx512`: 8 lanes per permutation (512-bit registers)
+//!
+//! Preimage packing is specialised: the constant parts (initializer hash,
+//! `0xff || factory`, init code hash and padding) are packed into lanes once,
+//! and the salt produced by the first permutation is fed into the second one
+//! directly in lane form, without a round trip through bytes.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use super::account::SaltShape;
+use super::derivation::Derivation;
+
+/// Number of nonces hashed per call to [`BatchHasher::hash_batch`].
+///
+/// Every backend processes this many nonces per call (AVX2 in two halves,
+/// scalar one by one) so the worker loop is the same for all of them.
+pub const BATCH_LANES: usize = 8;
+
+/// Keccak backend used by the CPU workers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeccakBackend {
+    /// One nonce at a time through `tiny_keccak`.
+    Scalar,
+    /// 4 nonces per permutation using AVX2.
+    Avx2,
+    /// 8 nonces per permutation using AVX-512F.
+    Avx512,
+}
+
+impl KeccakBackend {
+    /// Picks the widest backend supported by the running CPU.
+    pub fn detect() -> Self {
+        if Self::Avx512.is_supported() {
+            Self::Avx512
+        } else if Self::Avx2.is_supported() {
+            Self::Avx2
+        } else {
+            Self::Scalar
+        }
+    }
+
+    /// Returns whether the running CPU can execute this backend.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            Self::Avx512 => is_x86_feature_detected!("avx512f"),
+            #[cfg(not(target_arch = "x86_64"))]
+            Self::Avx2 | Self::Avx512 => false,
+        }
+    }
+
+    /// Number of nonces hashed per Keccak permutation.
+    pub fn lanes(self) -> usize {
+        match self {
+            Self::Scalar => 1,
+            Self::Avx2 => 4,
+            Self::Avx512 => 8,
+        }
+    }
+}
+
+impl FromStr for KeccakBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(Self::detect()),
+            "scalar" | "none" => Ok(Self::Scalar),
+            "avx2" => Ok(Self::Avx2),
+            "avx512" | "avx-512" | "avx512f" => Ok(Self::Avx512),
+            _ => Err(format!("Unknown backend: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for KeccakBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Scalar => write!(f, "scalar"),
+            Self::Avx2 => write!(f, "avx2 (4 lanes)"),
+            Self::Avx512 => write!(f, "avx512 (8 lanes)"),
+        }
+    }
+}
+
+/// Address hasher for batches of [`BATCH_LANES`] mined nonces.
+///
+/// Safe derivations and CREATE2 salts that are either used unchanged or
+/// guarded as `keccak256(word || salt)` run on the selected SIMD backend;
+/// any other derivation (CREATE3, zkSync, ERC-6551, account factories with
+/// custom salts) is hashed one nonce at a time.
+#[derive(Debug, Clone)]
+pub struct BatchHasher {
+    backend: KeccakBackend,
+    derivation: Derivation,
+    /// Whether the derivation has a multi-lane path at all.
+    vectorized: bool,
+    /// Pre-packed lanes of the 64-byte salt preimage (Safe salt or one-word
+    /// CreateX guard).
+    salt_lanes: Option<SaltLanes>,
+    /// Pre-packed lanes of the 85-byte CREATE2 preimage.
+    create2_lanes: Create2Lanes,
+}
+
+impl BatchHasher {
+    /// Creates a hasher for the given derivation.
+    ///
+    /// # Panics
+    /// Panics if `backend` is not supported by the running CPU.
+    pub fn new(backend: KeccakBackend, derivation: Derivation) -> Self {
+        assert!(backend.is_supported(), "{} is not supported on this CPU", backend);
+        let (salt_lanes, vectorized) = match &derivation {
+            Derivation::Safe {
+                initializer_hash, ..
+            } => (Some(SaltLanes::new(initializer_hash)), true),
+            Derivation::Create2 { preset, .. } => match preset.guard_word() {
+                Some(word) => (Some(SaltLanes::new(&word)), true),
+                None => (None, preset.is_identity()),
+            },
+            // The nonce-1 CREATE after the proxy CREATE2, the two-block
+            // zkSync preimage and the per-salt ERC-6551 init code have no
+            // lane path.
+            Derivation::Create3 { .. } | Derivation::ZkSync { .. } | Derivation::TokenBound(_) => {
+                (None, false)
+            }
+            Derivation::Account(factory) => match factory.salt_shape() {
+                SaltShape::Identity => (None, true),
+                SaltShape::Prefixed(word) => (Some(SaltLanes::new(&word)), true),
+                SaltShape::Custom => (None, false),
+            },
+        };
+        // Derivations without a fixed init code hash are never vectorized.
+        let init_code_hash = derivation.init_code_hash().copied().unwrap_or_default();
+        let create2_lanes = Create2Lanes::new(derivation.deployer(), &init_code_hash);
+        Self {
+            backend,
+            vectorized,
+            derivation,
+            salt_lanes,
+            create2_lanes,
+        }
+    }
+
+    /// Returns the derivation being hashed.
+    pub fn derivation(&self) -> &Derivation {
+        &self.derivation
+    }
+
+    /// Returns the backend this hasher was created with.
+    pub fn backend(&self) -> KeccakBackend {
+        self.backend
+    }
+
+    /// Returns the backend batches actually run on (scalar if the
+    /// derivation has no multi-lane path).
+    pub fn effective_backend(&self) -> KeccakBackend {
+        if self.vectorized {
+            self.backend
+        } else {
+            KeccakBackend::Scalar
+        }
+    }
+
+    /// Computes the address for each nonce in `nonces`.
+    #[inline]
+    pub fn hash_batch(
+        &self,
+        nonces: &[[u8; 32]; BATCH_LANES],
+        out: &mut [[u8; 20]; BATCH_LANES],
+    ) {
+        match self.effective_backend() {
+            KeccakBackend::Scalar => {
+                for (nonce, addr) in nonces.iter().zip(out.iter_mut()) {
+                    *addr = self.derivation.address(nonce);
+                }
+            }
+            #[cfg(target_arch = "x86_64")]
+            KeccakBackend::Avx2 => {
+                // SAFETY: `new` asserted AVX2 support.
+                unsafe {
+                    self.hash_avx2(&nonces[..4], &mut out[..4]);
+                    self.hash_avx2(&nonces[4..], &mut out[4..]);
+                }
+            }
+            #[cfg(target_arch = "x86_64")]
+            KeccakBackend::Avx512 => {
+                // SAFETY: `new` asserted AVX-512F support.
+                unsafe { self.hash_avx512(nonces, out) }
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            KeccakBackend::Avx2 | KeccakBackend::Avx512 => unreachable!(),
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn hash_avx2(&self, nonces: &[[u8; 32]], out: &mut [[u8; 20]]) {
+        self.hash_lanes::<__m256i>(nonces, out)
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx512f")]
+    unsafe fn hash_avx512(&self, nonces: &[[u8; 32]], out: &mut [[u8; 20]]) {
+        self.hash_lanes::<__m512i>(nonces, out)
+    }
+
+    /// Hashes `L::WIDTH` nonces. Inlined into the `target_feature` wrappers
+    /// above so the lane operations compile down to single vector instructions.
+    #[inline(always)]
+    fn hash_lanes<L: Lane>(&self, nonces: &[[u8; 32]], out: &mut [[u8; 20]]) {
+        let salt = match &self.salt_lanes {
+            Some(salt_lanes) => salt_lanes.hash::<L>(nonces),
+            None => load_nonce_lanes::<L>(nonces),
+        };
+        let hash = self.create2_lanes.hash::<L>(&salt);
+
+        // Address = hash[12..32] = upper half of lane 1, then lanes 2 and 3.
+        let mut words = [[0u64; MAX_WIDTH]; 3];
+        for (w, lane) in words.iter_mut().zip(&hash[1..4]) {
+            lane.store(&mut w[..L::WIDTH]);
+        }
+        for (i, addr) in out.iter_mut().enumerate().take(L::WIDTH) {
+            addr[0..4].copy_from_slice(&words[0][i].to_le_bytes()[4..8]);
+            addr[4..12].copy_from_slice(&words[1][i].to_le_bytes());
+            addr[12..20].copy_from_slice(&words[2][i].to_le_bytes());
+        }
+    }
+}
+
+/// Widest lane type (AVX-512: 8 x u64).
+const MAX_WIDTH: usize = 8;
+
+/// Lane packing of `initializer_hash (32) || salt_nonce (32)`.
+///
+/// Lanes 0..4 hold the initializer hash, lanes 4..8 the nonce, lane 8 the
+/// `0x01` domain padding byte and lane 16 the final `0x80` rate padding bit.
+#[derive(Debug, Clone, Copy)]
+struct SaltLanes {
+    initializer: [u64; 4],
+}
+
+impl SaltLanes {
+    fn new(initializer_hash: &[u8; 32]) -> Self {
+        Self {
+            initializer: le_lanes(initializer_hash),
+        }
+    }
+
+    /// Returns the first four output lanes (the 32-byte salt) for each nonce.
+    #[inline(always)]
+    fn hash<L: Lane>(&self, nonces: &[[u8; 32]]) -> [L; 4] {
+        let mut st = [L::splat(0); 25];
+        let nonce_lanes = load_nonce_lanes::<L>(nonces);
+        for k in 0..4 {
+            st[k] = L::splat(self.initializer[k]);
+            st[4 + k] = nonce_lanes[k];
+        }
+        st[8] = L::splat(0x01);
+        st[16] = L::splat(0x80 << 56);
+        keccak_f(&mut st);
+        [st[0], st[1], st[2], st[3]]
+    }
+}
+
+/// Lane packing of `0xff || factory (20) || salt (32) || init_code_hash (32)`.
+///
+/// The salt occupies bytes 21..53, i.e. the top three bytes of lane 2,
+/// lanes 3..5 and the low five bytes of lane 6; every other lane is constant.
+#[derive(Debug, Clone, Copy)]
+struct Create2Lanes {
+    /// Lanes 0..11 of the padded preimage with the salt bytes zeroed.
+    fixed: [u64; 11],
+}
+
+impl Create2Lanes {
+    fn new(factory: &[u8; 20], init_code_hash: &[u8; 32]) -> Self {
+        let mut block = [0u8; 88];
+        block[0] = 0xff;
+        block[1..21].copy_from_slice(factory);
+        block[53..85].copy_from_slice(init_code_hash);
+        block[85] = 0x01;
+        let mut fixed = [0u64; 11];
+        for (k, lane) in fixed.iter_mut().enumerate() {
+            *lane = u64::from_le_bytes(block[k * 8..k * 8 + 8].try_into().unwrap());
+        }
+        Self { fixed }
+    }
+
+    /// Hashes the preimage for each salt (given as the 4 little-endian lanes
+    /// produced by [`SaltLanes::hash`]) and returns the full output state.
+    #[inline(always)]
+    fn hash<L: Lane>(&self, salt: &[L; 4]) -> [L; 25] {
+        let mut st = [L::splat(0); 25];
+        for (k, &lane) in self.fixed.iter().enumerate() {
+            st[k] = L::splat(lane);
+        }
+        // Shift the salt up by 5 bytes (40 bits) across lane boundaries:
+        // rotating a word left by 40 puts `w << 40` in its top 24 bits and
+        // `w >> 24` in its low 40 bits, which the masks then split apart.
+        let (high, low) = (L::splat(u64::MAX << 40), L::splat(u64::MAX >> 24));
+        let r = salt.map(|w| w.rotl::<40, 24>());
+        st[2] = st[2].xor(low.andnot(r[0]));
+        st[3] = high.andnot(r[0]).xor(low.andnot(r[1]));
+        st[4] = high.andnot(r[1]).xor(low.andnot(r[2]));
+        st[5] = high.andnot(r[2]).xor(low.andnot(r[3]));
+        st[6] = st[6].xor(high.andnot(r[3]));
+        st[16] = L::splat(0x80 << 56);
+        keccak_f(&mut st);
+        st
+    }
+}
+
+/// Loads the first `L::WIDTH` nonces as four little-endian lanes.
+#[inline(always)]
+fn load_nonce_lanes<L: Lane>(nonces: &[[u8; 32]]) -> [L; 4] {
+    let mut lanes = [L::splat(0); 4];
+    for (k, lane) in lanes.iter_mut().enumerate() {
+        let mut words = [0u64; MAX_WIDTH];
+        for (w, nonce) in words.iter_mut().zip(nonces).take(L::WIDTH) {
+            *w = u64::from_le_bytes(nonce[k * 8..k * 8 + 8].try_into().unwrap());
+        }
+        *lane = L::load(&words[..L::WIDTH]);
+    }
+    lanes
+}
+
+/// Splits 32 bytes into four little-endian Keccak lanes.
+fn le_lanes(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut lanes = [0u64; 4];
+    for (k, lane) in lanes.iter_mut().enumerate() {
+        *lane = u64::from_le_bytes(bytes[k * 8..k * 8 + 8].try_into().unwrap());
+    }
+    lanes
+}
+
+/// One Keccak lane for `WIDTH` independent states.
+///
+/// Implemented for `u64` (portable, one state) and for the AVX2 / AVX-512
+/// register types. The vector impls must only be used from functions compiled
+/// with the matching `target_feature`.
+trait Lane: Copy {
+    const WIDTH: usize;
+
+    fn splat(v: u64) -> Self;
+    /// Loads `WIDTH` words, one per state.
+    fn load(words: &[u64]) -> Self;
+    /// Stores `WIDTH` words, one per state.
+    fn store(self, words: &mut [u64]);
+    fn xor(self, rhs: Self) -> Self;
+    /// `!self & rhs`
+    fn andnot(self, rhs: Self) -> Self;
+    /// Rotates left by `L`; `R` must be `64 - L` (stable Rust cannot derive
+    /// one const argument from another).
+    fn rotl<const L: i32, const R: i32>(self) -> Self;
+}
+
+impl Lane for u64 {
+    const WIDTH: usize = 1;
+
+    #[inline(always)]
+    fn splat(v: u64) -> Self {
+        v
+    }
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        words[0]
+    }
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        words[0] = self;
+    }
+    #[inline(always)]
+    fn xor(self, rhs: Self) -> Self {
+        self ^ rhs
+    }
+    #[inline(always)]
+    fn andnot(self, rhs: Self) -> Self {
+        !self & rhs
+    }
+    #[inline(always)]
+    fn rotl<const L: i32, const R: i32>(self) -> Self {
+        self.rotate_left(L as u32)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Lane for __m256i {
+    const WIDTH: usize = 4;
+
+    #[inline(always)]
+    fn splat(v: u64) -> Self {
+        unsafe { _mm256_set1_epi64x(v as i64) }
+    }
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        assert!(words.len() >= 4);
+        unsafe { _mm256_loadu_si256(words.as_ptr() as *const __m256i) }
+    }
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        assert!(words.len() >= 4);
+        unsafe { _mm256_storeu_si256(words.as_mut_ptr() as *mut __m256i, self) }
+    }
+    #[inline(always)]
+    fn xor(self, rhs: Self) -> Self {
+        unsafe { _mm256_xor_si256(self, rhs) }
+    }
+    #[inline(always)]
+    fn andnot(self, rhs: Self) -> Self {
+        unsafe { _mm256_andnot_si256(self, rhs) }
+    }
+    #[inline(always)]
+    fn rotl<const L: i32, const R: i32>(self) -> Self {
+        unsafe { _mm256_or_si256(_mm256_slli_epi64::<L>(self), _mm256_srli_epi64::<R>(self)) }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl Lane for __m512i {
+    const WIDTH: usize = 8;
+
+    #[inline(always)]
+    fn splat(v: u64) -> Self {
+        unsafe { _mm512_set1_epi64(v as i64) }
+    }
+    #[inline(always)]
+    fn load(words: &[u64]) -> Self {
+        assert!(words.len() >= 8);
+        unsafe { _mm512_loadu_si512(words.as_ptr() as *const _) }
+    }
+    #[inline(always)]
+    fn store(self, words: &mut [u64]) {
+        assert!(words.len() >= 8);
+        unsafe { _mm512_storeu_si512(words.as_mut_ptr() as *mut _, self) }
+    }
+    #[inline(always)]
+    fn xor(self, rhs: Self) -> Self {
+        unsafe { _mm512_xor_si512(self, rhs) }
+    }
+    #[inline(always)]
+    fn andnot(self, rhs: Self) -> Self {
+        unsafe { _mm512_andnot_si512(self, rhs) }
+    }
+    #[inline(always)]
+    fn rotl<const L: i32, const R: i32>(self) -> Self {
+        unsafe { _mm512_rol_epi64::<L>(self) }
+    }
+}
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// XORs theta's column parity `d[x]` into every listed lane `x + 5y`.
+macro_rules! theta_apply {
+    ($a:ident, $d:ident, $($i:literal),* $(,)?) => {
+        $( $a[$i] = $a[$i].xor($d[$i % 5]); )*
+    };
+}
+
+/// Rho and pi for one round, unrolled so every rotation amount and lane
+/// index is a compile-time constant.
+macro_rules! rho_pi {
+    ($a:ident, $(($j:literal, $r:literal)),* $(,)?) => {{
+        let mut last = $a[1];
+        $(
+            let tmp = $a[$j];
+            $a[$j] = last.rotl::<$r, { 64 - $r }>();
+            last = tmp;
+        )*
+        let _ = last;
+    }};
+}
+
+/// Chi for the row starting at lane `$y`.
+macro_rules! chi_row {
+    ($a:ident, $y:literal) => {{
+        let row = [$a[$y], $a[$y + 1], $a[$y + 2], $a[$y + 3], $a[$y + 4]];
+        $a[$y] = row[0].xor(row[1].andnot(row[2]));
+        $a[$y + 1] = row[1].xor(row[2].andnot(row[3]));
+        $a[$y + 2] = row[2].xor(row[3].andnot(row[4]));
+        $a[$y + 3] = row[3].xor(row[4].andnot(row[0]));
+        $a[$y + 4] = row[4].xor(row[0].andnot(row[1]));
+    }};
+}
+
+/// Keccak-f[1600] over `L::WIDTH` independent states (lane `x + 5y`).
+///
+/// Every step is written out with constant lane indices so the whole state
+/// stays in registers, and every rotation count is a const generic that the
+/// vector impls pass to immediate-operand intrinsics (AVX2 `vpsllq`/`vpsrlq`,
+/// AVX-512 `vprolq`).
+#[inline(always)]
+fn keccak_f<L: Lane>(a: &mut [L; 25]) {
+    for &rc in ROUND_CONSTANTS.iter() {
+        // Theta
+        let c0 = a[0].xor(a[5]).xor(a[10]).xor(a[15]).xor(a[20]);
+        let c1 = a[1].xor(a[6]).xor(a[11]).xor(a[16]).xor(a[21]);
+        let c2 = a[2].xor(a[7]).xor(a[12]).xor(a[17]).xor(a[22]);
+        let c3 = a[3].xor(a[8]).xor(a[13]).xor(a[18]).xor(a[23]);
+        let c4 = a[4].xor(a[9]).xor(a[14]).xor(a[19]).xor(a[24]);
+        let d = [
+            c4.xor(c1.rotl::<1, 63>()),
+            c0.xor(c2.rotl::<1, 63>()),
+            c1.xor(c3.rotl::<1, 63>()),
+            c2.xor(c4.rotl::<1, 63>()),
+            c3.xor(c0.rotl::<1, 63>()),
+        ];
+        theta_apply!(a, d, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19,
+            20, 21, 22, 23, 24);
+
+        // Rho and pi: (destination lane, rotation) in visiting order
+        rho_pi!(
+            a,
+            (10, 1), (7, 3), (11, 6), (17, 10), (18, 15), (3, 21), (5, 28), (16, 36),
+            (8, 45), (21, 55), (24, 2), (4, 14), (15, 27), (23, 41), (19, 56), (13, 8),
+            (12, 25), (2, 43), (20, 62), (14, 18), (22, 39), (9, 61), (6, 20), (1, 44),
+        );
+
+        // Chi
+        chi_row!(a, 0);
+        chi_row!(a, 5);
+        chi_row!(a, 10);
+        chi_row!(a, 15);
+        chi_row!(a, 20);
+
+        // Iota
+        a[0] = a[0].xor(L::splat(rc));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::crypto::account::{KernelFactory, LightAccountFactory};
+    use crate::crypto::SaltPreset;
+
+    fn sample_nonces() -> [[u8; 32]; BATCH_LANES] {
+        let mut nonces = [[0u8; 32]; BATCH_LANES];
+        for (i, nonce) in nonces.iter_mut().enumerate() {
+            for (j, b) in nonce.iter_mut().enumerate() {
+                *b = (i * 37 + j * 11) as u8;
+            }
+        }
+        nonces
+    }
+
+    fn derivations() -> [Derivation; 8] {
+        [
+            Derivation::Safe {
+                factory: [0x4eu8; 20],
+                init_code_hash: [0x76u8; 32],
+                initializer_hash: [0xa1u8; 32],
+            },
+            Derivation::Create2 {
+                deployer: [0x11u8; 20],
+                init_code_hash: [0x22u8; 32],
+                preset: SaltPreset::Raw,
+            },
+            Derivation::Create2 {
+                deployer: [0x11u8; 20],
+                init_code_hash: [0x22u8; 32],
+                preset: SaltPreset::Immutable { caller: [0x33u8; 20] },
+            },
+            Derivation::Create2 {
+                deployer: [0x11u8; 20],
+                init_code_hash: [0x22u8; 32],
+                preset: SaltPreset::CreateX {
+                    caller: Some([0x33u8; 20]),
+                    chain_id: None,
+                },
+            },
+            Derivation::Create2 {
+                deployer: [0x11u8; 20],
+                init_code_hash: [0x22u8; 32],
+                preset: SaltPreset::CreateX {
+                    caller: None,
+                    chain_id: Some(8453),
+                },
+            },
+            Derivation::Create2 {
+                deployer: [0x11u8; 20],
+                init_code_hash: [0x22u8; 32],
+                preset: SaltPreset::CreateX {
+                    caller: Some([0x33u8; 20]),
+                    chain_id: Some(8453),
+                },
+            },
+            Derivation::Account(Arc::new(LightAccountFactory::new(
+                [0x11u8; 20],
+                [0x44u8; 20],
+                [0x33u8; 20],
+            ))),
+            Derivation::Account(Arc::new(KernelFactory::new(
+                [0x11u8; 20],
+                [0x44u8; 20],
+                &[0x55u8; 100],
+            ))),
+        ]
+    }
+
+    #[test]
+    fn test_portable_lanes_match_scalar() {
+        for derivation in derivations() {
+            let hasher = BatchHasher::new(KeccakBackend::Scalar, derivation.clone());
+            if !hasher.vectorized {
+                continue;
+            }
+
+            let nonces = sample_nonces();
+            let mut out = [[0u8; 20]; BATCH_LANES];
+            for (nonce, addr) in nonces.chunks(1).zip(out.chunks_mut(1)) {
+                hasher.hash_lanes::<u64>(nonce, addr);
+            }
+
+            for (nonce, addr) in nonces.iter().zip(out.iter()) {
+                assert_eq!(*addr, derivation.address(nonce));
+            }
+        }
+    }
+
+    #[test]
+    fn test_supported_backends_match_scalar() {
+        for backend in [KeccakBackend::Scalar, KeccakBackend::Avx2, KeccakBackend::Avx512] {
+            if !backend.is_supported() {
+                continue;
+            }
+            for derivation in derivations() {
+                let hasher = BatchHasher::new(backend, derivation.clone());
+                let nonces = sample_nonces();
+                let mut out = [[0u8; 20]; BATCH_LANES];
+                hasher.hash_batch(&nonces, &mut out);
+
+                for (nonce, addr) in nonces.iter().zip(out.iter()) {
+                    assert_eq!(*addr, derivation.address(nonce));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_backend_from_str() {
+        assert_eq!("scalar".parse::<KeccakBackend>(), Ok(KeccakBackend::Scalar));
+        assert_eq!("AVX2".parse::<KeccakBackend>(), Ok(KeccakBackend::Avx2));
+        assert_eq!("avx512".parse::<KeccakBackend>(), Ok(KeccakBackend::Avx512));
+        assert!(KeccakBackend::detect().is_supported());
+        assert!("sse".parse::<KeccakBackend>().is_err());
+    }
+}
@@ -3,10 +3,30 @@
 //! Safe proxy address (from SafeProxyFactory):
 //! - salt = keccak256(initializerHash || saltNonce)  [64 bytes -> 32 bytes]
 //! - address = keccak256(0xff || factory || salt || initCodeHash)[12..32]  [85 bytes -> 20 bytes]
+//!
+//! Generic CREATE2 mining skips the first step and varies the salt directly,
+//! optionally constrained by a factory salt preset (see [`salt`]). CREATE3
+//! mining goes through a fixed CREATE2 proxy and a nonce-1 CREATE (see [`create`]).
+//! ERC-4337 smart accounts plug their own salt and init code in via [`account`].
+//! ERC-6551 token-bound accounts embed the salt in the init code (see [`erc6551`]).
 
+pub mod account;
+pub mod create;
 pub mod create2;
+pub mod derivation;
+pub mod erc6551;
+pub mod keccak_simd;
+pub mod salt;
 
-pub use create2::{safe_address, safe_salt};
+pub use account::{AccountFactory, AccountKind};
+pub use create::{create3_address, create_address};
+pub use create2::{
+    clone_init_code_hash, create2_address, safe_address, safe_salt, zksync_create2_address,
+};
+pub use derivation::Derivation;
+pub use erc6551::TokenBoundAccount;
+pub use keccak_simd::{BatchHasher, KeccakBackend};
+pub use salt::SaltPreset;
 use tiny_keccak::{Hasher, Keccak};
 
 /// Keccak-256 of arbitrary bytes (output 32 bytes).
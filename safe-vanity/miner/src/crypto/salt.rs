@@ -0,0 +1,205 @@
+//! Salt layouts enforced by CREATE2 factories.
+//!
+//! Some factories constrain the salt they accept or transform it before
+//! calling CREATE2, so a plain salt search gives unusable results:
+//!
+//! - ImmutableCreate2Factory: the first 20 bytes must be `msg.sender`
+//!   (or zero); the salt is then used as-is.
+//! - CreateX: byte 0..20 is `msg.sender` (permissioned) or zero, byte 20 is
+//!   the cross-chain redeploy protection flag, and `_guard` hashes the salt
+//!   with the sender and/or chain id before CREATE2.
+//!
+//! A preset fixes the constrained prefix so only the remaining bytes vary,
+//! and reproduces the factory's salt transformation.
+
+use super::keccak256;
+
+/// ImmutableCreate2Factory (0age), same address on most EVM chains.
+pub const IMMUTABLE_CREATE2_FACTORY: [u8; 20] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xe8, 0xb4, 0x7b, 0x3e, 0x21, 0x30, 0x21, 0x3b, 0x80,
+    0x22, 0x12, 0x43, 0x94, 0x97,
+];
+
+/// CreateX factory, same address on most EVM chains.
+pub const CREATEX_FACTORY: [u8; 20] = [
+    0xba, 0x5e, 0xd0, 0x99, 0x63, 0x3d, 0x3b, 0x31, 0x3e, 0x4d, 0x5f, 0x7b, 0xdc, 0x13, 0x05,
+    0xd3, 0xc2, 0x8b, 0xa5, 0xed,
+];
+
+/// Salt layout and transformation applied by the deploying factory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaltPreset {
+    /// Any 32-byte salt, passed to CREATE2 unchanged.
+    #[default]
+    Raw,
+    /// ImmutableCreate2Factory: salt[0..20] = caller, used unchanged.
+    Immutable { caller: [u8; 20] },
+    /// CreateX: salt[0..20] = caller (permissioned) or zero, salt[20] = 0x01
+    /// when cross-chain redeploy protection is on, then `_guard` hashing.
+    CreateX {
+        caller: Option<[u8; 20]>,
+        chain_id: Option<u64>,
+    },
+}
+
+impl SaltPreset {
+    /// Factory address the preset is meant for, if it has a canonical one.
+    pub fn default_factory(&self) -> Option<[u8; 20]> {
+        match self {
+            SaltPreset::Raw => None,
+            SaltPreset::Immutable { .. } => Some(IMMUTABLE_CREATE2_FACTORY),
+            SaltPreset::CreateX { .. } => Some(CREATEX_FACTORY),
+        }
+    }
+
+    /// Number of leading salt bytes fixed by the preset.
+    pub fn fixed_len(&self) -> usize {
+        match self {
+            SaltPreset::Raw => 0,
+            SaltPreset::Immutable { .. } => 20,
+            SaltPreset::CreateX { .. } => 21,
+        }
+    }
+
+    /// Overwrites the fixed prefix of a mined salt.
+    #[inline]
+    pub fn apply_prefix(&self, salt: &mut [u8; 32]) {
+        match self {
+            SaltPreset::Raw => {}
+            SaltPreset::Immutable { caller } => salt[0..20].copy_from_slice(caller),
+            SaltPreset::CreateX { caller, chain_id } => {
+                salt[0..20].copy_from_slice(&caller.unwrap_or([0u8; 20]));
+                salt[20] = u8::from(chain_id.is_some());
+            }
+        }
+    }
+
+    /// Returns the salt the factory passes to CREATE2 (CreateX `_guard`).
+    #[inline]
+    pub fn guard(&self, salt: &[u8; 32]) -> [u8; 32] {
+        match self {
+            SaltPreset::Raw | SaltPreset::Immutable { .. } => *salt,
+            SaltPreset::CreateX { caller, chain_id } => match (caller, chain_id) {
+                // keccak256(abi.encode(msg.sender, block.chainid, salt))
+                (Some(caller), Some(chain_id)) => {
+                    let mut preimage = [0u8; 96];
+                    preimage[12..32].copy_from_slice(caller);
+                    preimage[56..64].copy_from_slice(&chain_id.to_be_bytes());
+                    preimage[64..96].copy_from_slice(salt);
+                    keccak256(&preimage)
+                }
+                // _efficientHash(bytes32(msg.sender) or bytes32(block.chainid), salt)
+                (Some(_), None) | (None, Some(_)) => {
+                    let mut preimage = [0u8; 64];
+                    preimage[0..32].copy_from_slice(&self.guard_word().expect("one-word guard"));
+                    preimage[32..64].copy_from_slice(salt);
+                    keccak256(&preimage)
+                }
+                // keccak256(abi.encode(salt))
+                (None, None) => keccak256(salt),
+            },
+        }
+    }
+
+    /// For guards of the form `keccak256(word || salt)`, returns `word`.
+    ///
+    /// These have the same shape as the Safe salt (`initializerHash || saltNonce`)
+    /// and can run on the multi-lane Keccak backends.
+    pub fn guard_word(&self) -> Option<[u8; 32]> {
+        let mut word = [0u8; 32];
+        match self {
+            SaltPreset::CreateX {
+                caller: Some(caller),
+                chain_id: None,
+            } => word[12..32].copy_from_slice(caller),
+            SaltPreset::CreateX {
+                caller: None,
+                chain_id: Some(chain_id),
+            } => word[24..32].copy_from_slice(&chain_id.to_be_bytes()),
+            _ => return None,
+        }
+        Some(word)
+    }
+
+    /// Returns whether CREATE2 receives the mined salt unchanged.
+    pub fn is_identity(&self) -> bool {
+        matches!(self, SaltPreset::Raw | SaltPreset::Immutable { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALLER: [u8; 20] = [0xaa; 20];
+
+    #[test]
+    fn test_immutable_prefix() {
+        let preset = SaltPreset::Immutable { caller: CALLER };
+        let mut salt = [0x55u8; 32];
+        preset.apply_prefix(&mut salt);
+        assert_eq!(salt[0..20], CALLER);
+        assert_eq!(salt[20..32], [0x55u8; 12]);
+        assert_eq!(preset.guard(&salt), salt);
+    }
+
+    #[test]
+    fn test_createx_prefix_and_flag() {
+        let mut salt = [0x55u8; 32];
+        SaltPreset::CreateX {
+            caller: Some(CALLER),
+            chain_id: Some(1),
+        }
+        .apply_prefix(&mut salt);
+        assert_eq!(salt[0..20], CALLER);
+        assert_eq!(salt[20], 0x01);
+
+        SaltPreset::CreateX {
+            caller: None,
+            chain_id: None,
+        }
+        .apply_prefix(&mut salt);
+        assert_eq!(salt[0..21], [0u8; 21]);
+        assert_eq!(salt[21..32], [0x55u8; 11]);
+    }
+
+    #[test]
+    fn test_createx_guard_variants() {
+        let salt = [0x55u8; 32];
+
+        let both = SaltPreset::CreateX {
+            caller: Some(CALLER),
+            chain_id: Some(10),
+        };
+        let mut encoded = vec![0u8; 12];
+        encoded.extend_from_slice(&CALLER);
+        encoded.extend_from_slice(&[0u8; 31]);
+        encoded.push(10);
+        encoded.extend_from_slice(&salt);
+        assert_eq!(both.guard(&salt), keccak256(&encoded));
+
+        let sender = SaltPreset::CreateX {
+            caller: Some(CALLER),
+            chain_id: None,
+        };
+        let mut encoded = vec![0u8; 12];
+        encoded.extend_from_slice(&CALLER);
+        encoded.extend_from_slice(&salt);
+        assert_eq!(sender.guard(&salt), keccak256(&encoded));
+
+        let chain = SaltPreset::CreateX {
+            caller: None,
+            chain_id: Some(10),
+        };
+        let mut encoded = vec![0u8; 31];
+        encoded.push(10);
+        encoded.extend_from_slice(&salt);
+        assert_eq!(chain.guard(&salt), keccak256(&encoded));
+
+        let neither = SaltPreset::CreateX {
+            caller: None,
+            chain_id: None,
+        };
+        assert_eq!(neither.guard(&salt), keccak256(&salt));
+    }
+}
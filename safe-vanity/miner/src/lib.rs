@@ -5,13 +5,25 @@
 //!
 //! Uses the same formula as SafeProxyFactory: salt = keccak256(initializerHash || saltNonce),
 //! then address = keccak256(0xff || factory || salt || initCodeHash)[12..32].
+//! In create2 mode the salt is mined directly, for any deployer and init code,
+//! optionally in the caller-protected layouts of ImmutableCreate2Factory or CreateX.
+//! In create3 mode the address depends only on the deployer and salt. In account
+//! mode, ERC-4337 account factories (SimpleAccount, LightAccount, Kernel,
+//! Coinbase Smart Wallet) are mined through the [`AccountFactory`] trait.
+//! Every result is re-derived by an independent [`Verifier`] before it is reported.
 
 pub mod config;
 pub mod crypto;
 pub mod matcher;
+pub mod verify;
 pub mod worker;
 
-pub use config::Config;
-pub use crypto::create2::{safe_address, safe_salt};
-pub use matcher::{Address, MatchResult, Pattern, PatternType};
+pub use config::{Chain, Config, Mode, SaltPresetKind};
+pub use crypto::create::{create3_address, create_address};
+pub use crypto::create2::{
+    clone_init_code_hash, create2_address, safe_address, safe_salt, zksync_create2_address,
+};
+pub use crypto::{AccountFactory, AccountKind, BatchHasher, Derivation, SaltPreset, TokenBoundAccount};
+pub use matcher::{Address, ChildTarget, HookFlags, MatchResult, Pattern, PatternType};
+pub use verify::{Verifier, VerifyError};
 pub use worker::{SafeVanityResult, WorkerPool};
@@ -3,13 +3,39 @@
 //! Mines saltNonce until the CREATE2-derived Safe proxy address matches the pattern.
 //! Use --factory, --init-code-hash, --initializer-hash from your Safe config
 //! (e.g. from Safe SDK getAddress flow).
+//!
+//! With `--mode create2`, mines the raw salt for any deployer:
+//!   safe_vanity -m create2 -p dead --deployer <addr> --init-code <file|hex>
+//!
+//! `--mode account --account simple|light|kernel|coinbase` mines the salt of an
+//! ERC-4337 smart account factory.
+//!
+//! `--mode hook --hooks beforeSwap,afterSwap` mines a CREATE2 salt for a
+//! Uniswap v4 hook whose low 14 address bits equal those permission flags.
+//!
+//! `--mode tba` mines the ERC-6551 registry salt of an NFT's token-bound
+//! account (`--implementation`, `--token-contract`, `--token-id`, `--chain-id`).
+//!
+//! `--mode clone --implementation <addr>` mines the CREATE2 salt of an EIP-1167
+//! clone (OpenZeppelin `Clones.cloneDeterministic`).
+//!
+//! `--mode create3` mines the salt of a CREATE3 deployment (no init code needed).
+//!
+//! `--chain zksync` switches safe and create2 mode to the zkSync Era CREATE2
+//! formula (`--singleton`, `--bytecode-hash`).
+//!
+//! `--child-nonce 1 [--parent-pattern <hex>]` matches the pattern against the
+//! contract the mined address deploys with CREATE (e.g. a Safe module via CreateCall).
+//!
+//! `--salt-preset immutable|createx` fixes the salt prefix required by
+//! ImmutableCreate2Factory / CreateX (`--caller`, `--chain-id`).
 
 use std::process;
 use std::time::Duration;
 
 use clap::Parser;
 
-use safe_vanity::{Config, Pattern, WorkerPool};
+use safe_vanity::{BatchHasher, Chain, Config, Mode, Verifier, WorkerPool};
 
 fn main() {
     let config = Config::parse();
@@ -19,19 +45,7 @@ fn main() {
         process::exit(1);
     }
 
-    let pattern = if let Some(ref suffix) = config.normalized_suffix() {
-        Pattern::new_prefix_and_suffix(
-            config.normalized_pattern(),
-            suffix.clone(),
-            config.case_sensitive,
-        )
-    } else {
-        Pattern::new(
-            config.normalized_pattern(),
-            config.effective_pattern_type(),
-            config.case_sensitive,
-        )
-    };
+    let pattern = config.pattern();
 
     println!("Safe Vanity Address Miner");
     println!("==========================");
@@ -40,22 +54,50 @@ fn main() {
     } else {
         format!("{} ({})", pattern.pattern(), pattern.pattern_type())
     };
-    println!("Pattern:    {}", pattern_display);
-    println!("Difficulty: {}", pattern.difficulty_description());
+    println!("Mode:       {}", config.mode);
+    if config.chain != Chain::Evm {
+        println!("Chain:      {}", config.chain);
+    }
+    if let Some(account) = config.account.filter(|_| config.mode == Mode::Account) {
+        println!("Account:    {}", account);
+    }
+    if let Some(flags) = pattern.hook_flags() {
+        println!("Hooks:      {}", flags);
+    }
+    if !pattern.pattern().is_empty() {
+        println!("Pattern:    {}", pattern_display);
+    }
+    let child = config.child_target();
+    if let Some(ref child) = child {
+        println!("Match:      {}", child);
+    }
+    match child {
+        Some(ref child) => println!("Difficulty: {}", child.difficulty_description(&pattern)),
+        None => println!("Difficulty: {}", pattern.difficulty_description()),
+    }
     println!("Workers:    {}", config.worker_count());
+    let derivation = config.derivation();
+    let effective = BatchHasher::new(config.backend, derivation.clone()).effective_backend();
+    if effective == config.backend {
+        println!("Backend:    {}", config.backend);
+    } else {
+        println!("Backend:    {} (no lane path here, using {})", config.backend, effective);
+    }
+    if let Some(preset) = config.salt_preset_description() {
+        println!("Salt:       {}", preset);
+    }
     println!("Target:     {} address(es)", config.count);
     println!();
 
-    let factory = config.factory_bytes();
-    let init_code_hash = config.init_code_hash_bytes();
-    let initializer_hash = config.initializer_hash_bytes();
+    // Every result is re-derived independently before it is reported
+    let verifier = Verifier::new(&config);
 
     let pool = WorkerPool::new(
         config.worker_count(),
         pattern,
-        factory,
-        init_code_hash,
-        initializer_hash,
+        config.backend,
+        derivation,
+        child,
     );
 
     let stop_flag = pool.stop_flag_clone();
@@ -72,8 +114,15 @@ fn main() {
     loop {
         match pool.wait_for_result(report_interval) {
             Some(result) => {
+                if let Err(e) = verifier.verify(&result) {
+                    eprintln!("FATAL: result failed independent verification, not reported");
+                    eprintln!("  {}", e);
+                    eprintln!("  This is a bug in address derivation or matching; please report it.");
+                    pool.stop();
+                    process::exit(2);
+                }
                 found += 1;
-                print_result(&result, found);
+                print_result(&result, found, config.mode);
                 if config.count > 0 && found >= config.count {
                     println!("\nTarget reached! Found {} address(es).", found);
                     break;
@@ -99,11 +148,24 @@ fn main() {
     pool.join();
 }
 
-fn print_result(result: &safe_vanity::SafeVanityResult, index: usize) {
+fn print_result(result: &safe_vanity::SafeVanityResult, index: usize, mode: Mode) {
     println!("=== Match #{} ===", index);
     println!("Address:      {}", result.address_checksum());
-    println!("Salt (hex):   0x{}", result.salt_nonce_hex());
-    println!("Salt (dec):   {}", result.salt_nonce_decimal());
+    if let Some(child) = result.child_address_checksum() {
+        println!("Child:        {}", child);
+    }
+    match mode {
+        Mode::Safe | Mode::Account => {
+            println!("Salt (hex):   0x{}", result.salt_nonce_hex());
+            println!("Salt (dec):   {}", result.salt_nonce_decimal());
+        }
+        Mode::Create2 | Mode::Create3 | Mode::Hook | Mode::Tba | Mode::Clone => {
+            println!("Salt:         0x{}", result.salt_nonce_hex());
+            if result.salt != result.salt_nonce {
+                println!("Guarded salt: 0x{}", result.salt_hex());
+            }
+        }
+    }
     println!("Worker:       {}", result.worker_id);
     println!();
 }
@@ -0,0 +1,105 @@
+//! Matching on a contract the mined address deploys with CREATE.
+//!
+//! A Safe that deploys a module through `CreateCall` (delegatecall, so the
+//! Safe itself executes CREATE) puts it at keccak256(rlp([safe, nonce])).
+//! Contract nonces start at 1 (EIP-161), so the first child is at nonce 1.
+
+use crate::crypto::create_address;
+
+use super::pattern::describe_difficulty;
+use super::{Address, Pattern};
+
+/// The pattern applies to the CREATE child at `nonce`; the mined address
+/// itself can be constrained by a second pattern.
+#[derive(Clone)]
+pub struct ChildTarget {
+    nonce: u64,
+    parent_pattern: Option<Pattern>,
+}
+
+impl ChildTarget {
+    pub fn new(nonce: u64, parent_pattern: Option<Pattern>) -> Self {
+        Self {
+            nonce,
+            parent_pattern,
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn parent_pattern(&self) -> Option<&Pattern> {
+        self.parent_pattern.as_ref()
+    }
+
+    /// Returns the child address if `parent` and its child match.
+    #[inline]
+    pub fn matches(&self, pattern: &Pattern, parent: &Address) -> Option<[u8; 20]> {
+        // The parent check needs no hashing; only derive the child when it passes.
+        if let Some(ref parent_pattern) = self.parent_pattern {
+            if !parent_pattern.matches(parent).is_match() {
+                return None;
+            }
+        }
+        let child = create_address(parent.as_bytes(), self.nonce);
+        pattern
+            .matches(&Address::from_bytes(child))
+            .is_match()
+            .then_some(child)
+    }
+
+    /// Expected attempts per match, including the parent pattern.
+    pub fn estimated_difficulty(&self, pattern: &Pattern) -> u64 {
+        let parent = self
+            .parent_pattern
+            .as_ref()
+            .map_or(1, Pattern::estimated_difficulty);
+        pattern.estimated_difficulty().saturating_mul(parent)
+    }
+
+    pub fn difficulty_description(&self, pattern: &Pattern) -> String {
+        describe_difficulty(self.estimated_difficulty(pattern))
+    }
+}
+
+impl std::fmt::Display for ChildTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CREATE child at nonce {}", self.nonce)?;
+        if let Some(ref parent_pattern) = self.parent_pattern {
+            write!(
+                f,
+                " + parent {} ({})",
+                parent_pattern.pattern(),
+                parent_pattern.pattern_type()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::PatternType;
+
+    #[test]
+    fn test_child_target() {
+        // nonce 1 child of this deployer is 0x343c43a3...
+        let bytes: [u8; 20] = hex::decode("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let parent = Address::from_bytes(bytes);
+        let pattern = Pattern::new("343c", PatternType::Prefix, false);
+
+        let child = ChildTarget::new(1, None);
+        let expected = hex::decode("343c43a37d37dff08ae8c4a11544c718abb4fcf8").unwrap();
+        assert_eq!(child.matches(&pattern, &parent).unwrap()[..], expected[..]);
+
+        let both = ChildTarget::new(1, Some(Pattern::new("6ac7", PatternType::Prefix, false)));
+        assert!(both.matches(&pattern, &parent).is_some());
+        let wrong_parent = ChildTarget::new(1, Some(Pattern::new("beef", PatternType::Prefix, false)));
+        assert!(wrong_parent.matches(&pattern, &parent).is_none());
+    }
+}
@@ -0,0 +1,132 @@
+//! Uniswap v4 hook permission flags.
+//!
+//! v4 `Hooks` reads a hook's permissions from the low 14 bits of its address:
+//! bit 13 is `beforeInitialize`, bit 0 `afterRemoveLiquidityReturnDelta`.
+//! A hook address is valid only if those bits equal exactly the callbacks the
+//! hook implements, so mining fixes all 14 bits.
+
+use std::str::FromStr;
+
+use super::Address;
+
+/// Bits of the address checked by `Hooks.validateHookPermissions`.
+pub const ALL_HOOK_MASK: u16 = (1 << 14) - 1;
+
+/// Callback names and their flag bits, as in v4-core `Hooks.sol`.
+const HOOK_FLAGS: [(&str, u16); 14] = [
+    ("beforeInitialize", 1 << 13),
+    ("afterInitialize", 1 << 12),
+    ("beforeAddLiquidity", 1 << 11),
+    ("afterAddLiquidity", 1 << 10),
+    ("beforeRemoveLiquidity", 1 << 9),
+    ("afterRemoveLiquidity", 1 << 8),
+    ("beforeSwap", 1 << 7),
+    ("afterSwap", 1 << 6),
+    ("beforeDonate", 1 << 5),
+    ("afterDonate", 1 << 4),
+    ("beforeSwapReturnDelta", 1 << 3),
+    ("afterSwapReturnDelta", 1 << 2),
+    ("afterAddLiquidityReturnDelta", 1 << 1),
+    ("afterRemoveLiquidityReturnDelta", 1 << 0),
+];
+
+/// Exact value required in the low 14 bits of a hook address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HookFlags(u16);
+
+impl HookFlags {
+    /// Builds flags from raw bits (must fit in [`ALL_HOOK_MASK`]).
+    pub fn from_bits(bits: u16) -> Option<Self> {
+        (bits & !ALL_HOOK_MASK == 0).then_some(Self(bits))
+    }
+
+    /// Returns the flag bits.
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns true if the low 14 bits of `address` equal these flags.
+    #[inline]
+    pub fn matches(&self, address: &Address) -> bool {
+        let bytes = address.as_bytes();
+        let low = u16::from_be_bytes([bytes[18], bytes[19]]);
+        low & ALL_HOOK_MASK == self.0
+    }
+
+    /// Names of the enabled callbacks.
+    pub fn names(&self) -> Vec<&'static str> {
+        HOOK_FLAGS
+            .iter()
+            .filter(|(_, bit)| self.0 & bit != 0)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
+impl FromStr for HookFlags {
+    type Err = String;
+
+    /// Parses a comma-separated list of callback names (case-insensitive,
+    /// `_`/`-` ignored), `none`, or raw bits as `0x...`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(hex_bits) = s.strip_prefix("0x") {
+            let bits = u16::from_str_radix(hex_bits, 16)
+                .map_err(|_| format!("Invalid hook flag bits: {}", s))?;
+            return Self::from_bits(bits)
+                .ok_or_else(|| format!("Hook flag bits {} exceed the 14-bit mask", s));
+        }
+        let normalize = |name: &str| name.replace(['_', '-'], "").to_lowercase();
+        let mut bits = 0;
+        for name in s.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            if normalize(name) == "none" {
+                continue;
+            }
+            let (_, bit) = HOOK_FLAGS
+                .iter()
+                .find(|(known, _)| normalize(known) == normalize(name))
+                .ok_or_else(|| format!("Unknown hook callback: {}", name))?;
+            bits |= bit;
+        }
+        Ok(Self(bits))
+    }
+}
+
+impl std::fmt::Display for HookFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = self.names();
+        if names.is_empty() {
+            write!(f, "none (0x0000)")
+        } else {
+            write!(f, "{} (0x{:04x})", names.join(", "), self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hook_flags() {
+        let flags: HookFlags = "beforeSwap,afterSwap".parse().unwrap();
+        assert_eq!(flags.bits(), 0x00c0);
+        let flags: HookFlags = "before_initialize, after-remove-liquidity-return-delta"
+            .parse()
+            .unwrap();
+        assert_eq!(flags.bits(), 0x2001);
+        assert_eq!("0x3fff".parse::<HookFlags>().unwrap().bits(), ALL_HOOK_MASK);
+        assert!("0x4000".parse::<HookFlags>().is_err());
+        assert!("beforeFlash".parse::<HookFlags>().is_err());
+    }
+
+    #[test]
+    fn test_hook_flags_match_exact_low_bits() {
+        let flags: HookFlags = "beforeSwap,afterSwap".parse().unwrap();
+        let mut bytes = [0xffu8; 20];
+        bytes[18] = 0xc0; // upper two bits are outside the mask
+        bytes[19] = 0xc0;
+        assert!(flags.matches(&Address::from_bytes(bytes)));
+        bytes[19] = 0xc1; // extra afterRemoveLiquidityReturnDelta bit
+        assert!(!flags.matches(&Address::from_bytes(bytes)));
+    }
+}
@@ -1,5 +1,10 @@
-//! Pattern matching for 20-byte addresses (hex nibbles).
+//! Pattern matching for 20-byte addresses (hex nibbles), optionally combined
+//! with Uniswap v4 hook flag bits or applied to a CREATE child.
 
+mod child;
+mod hook;
 mod pattern;
 
+pub use child::ChildTarget;
+pub use hook::{HookFlags, ALL_HOOK_MASK};
 pub use pattern::{Address, MatchResult, Pattern, PatternType};
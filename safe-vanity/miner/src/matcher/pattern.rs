@@ -2,6 +2,8 @@
 
 use std::str::FromStr;
 
+use super::HookFlags;
+
 /// A 20-byte address (e.g. Safe proxy address).
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Address(pub [u8; 20]);
@@ -122,6 +124,8 @@ pub struct Pattern {
     /// Pre-parsed nibble arrays for zero-allocation matching.
     pattern_nibbles: Vec<u8>,
     suffix_nibbles: Vec<u8>,
+    /// Exact Uniswap v4 hook flags required in the low 14 bits.
+    hook_flags: Option<HookFlags>,
 }
 
 /// Convert hex string to nibble array. Each char becomes one u8 (0..15).
@@ -183,6 +187,7 @@ impl Pattern {
             pattern_type,
             pattern_nibbles,
             suffix_nibbles: Vec::new(),
+            hook_flags: None,
         }
     }
 
@@ -202,6 +207,7 @@ impl Pattern {
             pattern_type: PatternType::PrefixAndSuffix,
             pattern_nibbles,
             suffix_nibbles,
+            hook_flags: None,
         }
     }
 
@@ -215,10 +221,25 @@ impl Pattern {
         self.pattern_type
     }
 
+    /// Additionally requires the low 14 bits to equal `flags` exactly.
+    pub fn with_hook_flags(mut self, flags: HookFlags) -> Self {
+        self.hook_flags = Some(flags);
+        self
+    }
+
+    pub fn hook_flags(&self) -> Option<HookFlags> {
+        self.hook_flags
+    }
+
     /// Zero-allocation pattern matching on raw address bytes.
     /// Converts address bytes to nibbles on the stack and compares directly.
     #[inline]
     pub fn matches(&self, address: &Address) -> MatchResult {
+        if let Some(flags) = self.hook_flags {
+            if !flags.matches(address) {
+                return MatchResult::NoMatch;
+            }
+        }
         let nibbles = addr_to_nibbles(address.as_bytes());
         let matched = match self.pattern_type {
             PatternType::Prefix => nibbles_start_with(&nibbles, &self.pattern_nibbles),
@@ -239,18 +260,27 @@ impl Pattern {
     pub fn estimated_difficulty(&self) -> u64 {
         let n = self.pattern.len()
             + self.suffix.as_ref().map_or(0, |s| s.len());
-        16u64.saturating_pow(n as u32)
+        let hook = if self.hook_flags.is_some() {
+            1u64 << 14
+        } else {
+            1
+        };
+        16u64.saturating_pow(n as u32).saturating_mul(hook)
     }
 
     pub fn difficulty_description(&self) -> String {
-        let d = self.estimated_difficulty();
-        match d {
-            0..=1_000 => "Very Easy (< 1 second)".into(),
-            1_001..=100_000 => "Easy (seconds)".into(),
-            100_001..=10_000_000 => "Medium (minutes)".into(),
-            10_000_001..=1_000_000_000 => "Hard (hours)".into(),
-            _ => "Very Hard (days or more)".into(),
-        }
+        describe_difficulty(self.estimated_difficulty())
+    }
+}
+
+/// Rough time bucket for an expected number of attempts.
+pub(crate) fn describe_difficulty(attempts: u64) -> String {
+    match attempts {
+        0..=1_000 => "Very Easy (< 1 second)".into(),
+        1_001..=100_000 => "Easy (seconds)".into(),
+        100_001..=10_000_000 => "Medium (minutes)".into(),
+        10_000_001..=1_000_000_000 => "Hard (hours)".into(),
+        _ => "Very Hard (days or more)".into(),
     }
 }
 
@@ -282,4 +312,14 @@ mod tests {
         let p = Pattern::new("cafe", PatternType::Contains, false);
         assert!(p.matches(&addr("0000000000000000cafe00000000000000000000")).is_match());
     }
+
+    #[test]
+    fn test_hook_flags_with_prefix() {
+        let flags: HookFlags = "beforeSwap".parse().unwrap();
+        let p = Pattern::new("00", PatternType::Prefix, false).with_hook_flags(flags);
+        assert!(p.matches(&addr("00ab000000000000000000000000000000000080")).is_match());
+        assert!(!p.matches(&addr("00ab0000000000000000000000000000000000c0")).is_match());
+        assert!(!p.matches(&addr("10ab000000000000000000000000000000000080")).is_match());
+        assert_eq!(p.estimated_difficulty(), 256 << 14);
+    }
 }
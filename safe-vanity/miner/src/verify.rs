@@ -0,0 +1,659 @@
+//! Independent re-derivation of every result before it is reported.
+//!
+//! The search hashes with tiny-keccak or the multi-lane Keccak backends and
+//! fills fixed preimage buffers. Here every hash is sha3's Keccak-256, and
+//! each preimage (Safe, account and CreateX salts, CREATE2, CREATE3, zkSync
+//! CREATE2, the ERC-6551 init code, CREATE RLP) is encoded again from the
+//! configuration. The pattern is checked on the hex string of the address.
+//! A bug in either path shows up as a mismatch instead of an unusable salt.
+
+use sha3::{Digest, Keccak256};
+
+use crate::config::{parse_hex, parse_uint256, Chain, Config, Mode};
+use crate::crypto::create::CREATE3_PROXY_INIT_CODE;
+use crate::crypto::{AccountKind, SaltPreset};
+use crate::matcher::PatternType;
+use crate::worker::SafeVanityResult;
+
+/// Re-derives results with an implementation independent of the search.
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    formula: Formula,
+    salt: SaltRule,
+    pattern: HexPattern,
+    /// CREATE nonce of the matched child and the pattern of its parent.
+    child: Option<(u64, Option<String>)>,
+}
+
+/// How the CREATE2 salt is obtained from the mined value.
+#[derive(Debug, Clone)]
+struct SaltRule {
+    /// Leading bytes of the mined value that the factory requires.
+    fixed: Vec<u8>,
+    /// The salt is keccak256(before || mined || after), or the mined value.
+    hashed: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+/// How the address is obtained from the salt.
+#[derive(Debug, Clone)]
+enum Formula {
+    Create2 {
+        deployer: [u8; 20],
+        init_code_hash: [u8; 32],
+    },
+    Create3 {
+        deployer: [u8; 20],
+    },
+    ZkSync {
+        deployer: [u8; 20],
+        bytecode_hash: [u8; 32],
+        constructor_input_hash: [u8; 32],
+    },
+    /// ERC-6551: the salt is also written into the init code at `salt_offset`.
+    TokenBound {
+        registry: [u8; 20],
+        init_code: Vec<u8>,
+        salt_offset: usize,
+    },
+}
+
+/// The pattern as lowercase hex strings.
+#[derive(Debug, Clone)]
+struct HexPattern {
+    pattern_type: PatternType,
+    pattern: String,
+    suffix: Option<String>,
+    hook_bits: Option<u16>,
+}
+
+impl Verifier {
+    /// Creates a verifier for results of a search configured by `config`
+    /// (after validation).
+    pub fn new(config: &Config) -> Self {
+        let pattern = config.pattern();
+        Self {
+            formula: formula(config),
+            salt: salt_rule(config),
+            pattern: HexPattern {
+                pattern_type: pattern.pattern_type(),
+                pattern: pattern.pattern().to_lowercase(),
+                suffix: pattern.suffix().map(str::to_lowercase),
+                hook_bits: pattern.hook_flags().map(|flags| flags.bits()),
+            },
+            child: config.child_nonce.map(|nonce| {
+                (
+                    nonce,
+                    config.parent_pattern.as_deref().map(str::to_lowercase),
+                )
+            }),
+        }
+    }
+
+    /// Checks that the mined value gives the reported salt and addresses,
+    /// and that the matched address really matches the pattern.
+    pub fn verify(&self, result: &SafeVanityResult) -> Result<(), VerifyError> {
+        let worker = result.worker_id;
+        let nonce = &result.salt_nonce;
+        let fixed = &self.salt.fixed;
+        compare(worker, "fixed salt prefix", &nonce[..fixed.len()], fixed)?;
+
+        let salt = match self.salt.hashed {
+            Some((ref before, ref after)) => keccak256(&[before, &nonce[..], after].concat()),
+            None => *nonce,
+        };
+        compare(worker, "salt", &result.salt, &salt)?;
+
+        let address = self.formula.address(&salt);
+        compare(worker, "address", &result.address, &address)?;
+
+        let child = self
+            .child
+            .as_ref()
+            .map(|(nonce, _)| create_address(&address, *nonce));
+        compare(
+            worker,
+            "child address",
+            result.child_address.as_ref().map_or(&[][..], |c| &c[..]),
+            child.as_ref().map_or(&[][..], |c| &c[..]),
+        )?;
+
+        if let Some((_, Some(ref parent))) = self.child {
+            check_match(
+                worker,
+                &address,
+                |hex| hex.starts_with(parent.as_str()),
+                parent,
+            )?;
+        }
+        let matched = child.unwrap_or(address);
+        if let Some(bits) = self.pattern.hook_bits {
+            let low = u16::from_be_bytes([matched[18], matched[19]]) & 0x3fff;
+            if low != bits {
+                return Err(VerifyError::NoMatch {
+                    worker,
+                    address: to_hex(&matched),
+                    pattern: format!("hook flags {:#06x}", bits),
+                });
+            }
+        }
+        let p = &self.pattern;
+        let description = match p.suffix {
+            Some(ref suffix) => format!("{}...{} ({})", p.pattern, suffix, p.pattern_type),
+            None => format!("{} ({})", p.pattern, p.pattern_type),
+        };
+        check_match(
+            worker,
+            &matched,
+            |hex| match p.pattern_type {
+                PatternType::Prefix => hex.starts_with(p.pattern.as_str()),
+                PatternType::Suffix => hex.ends_with(p.pattern.as_str()),
+                PatternType::Contains => hex.contains(p.pattern.as_str()),
+                PatternType::PrefixAndSuffix => {
+                    hex.starts_with(p.pattern.as_str())
+                        && hex.ends_with(p.suffix.as_deref().unwrap_or(""))
+                }
+            },
+            &description,
+        )
+    }
+}
+
+impl Formula {
+    fn address(&self, salt: &[u8; 32]) -> [u8; 20] {
+        match self {
+            Formula::Create2 {
+                deployer,
+                init_code_hash,
+            } => create2(deployer, salt, init_code_hash),
+            Formula::Create3 { deployer } => {
+                let proxy = create2(deployer, salt, &keccak256(&CREATE3_PROXY_INIT_CODE));
+                create_address(&proxy, 1)
+            }
+            Formula::ZkSync {
+                deployer,
+                bytecode_hash,
+                constructor_input_hash,
+            } => {
+                let preimage = [
+                    &keccak256(b"zksyncCreate2")[..],
+                    &address_word(deployer),
+                    salt,
+                    bytecode_hash,
+                    constructor_input_hash,
+                ]
+                .concat();
+                last_20(keccak256(&preimage))
+            }
+            Formula::TokenBound {
+                registry,
+                init_code,
+                salt_offset,
+            } => {
+                let mut init_code = init_code.clone();
+                init_code[*salt_offset..salt_offset + 32].copy_from_slice(salt);
+                create2(registry, salt, &keccak256(&init_code))
+            }
+        }
+    }
+}
+
+/// A result that failed independent verification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VerifyError {
+    #[error(
+        "worker {worker}: {what} mismatch: search reported {reported}, \
+         independent derivation gives {derived}"
+    )]
+    Mismatch {
+        worker: usize,
+        what: &'static str,
+        reported: String,
+        derived: String,
+    },
+    #[error("worker {worker}: {address} does not match {pattern}")]
+    NoMatch {
+        worker: usize,
+        address: String,
+        pattern: String,
+    },
+}
+
+fn formula(config: &Config) -> Formula {
+    let deployer = config.factory_bytes();
+    if config.chain == Chain::ZkSync {
+        let constructor_input = match config.mode {
+            Mode::Safe => address_word(&address(&config.singleton)).to_vec(),
+            _ => {
+                let input = config.constructor_input.as_deref().unwrap_or("");
+                hex::decode(input.strip_prefix("0x").unwrap_or(input)).expect("validated hex")
+            }
+        };
+        return Formula::ZkSync {
+            deployer,
+            bytecode_hash: config.bytecode_hash_bytes(),
+            constructor_input_hash: keccak256(&constructor_input),
+        };
+    }
+    match config.mode {
+        Mode::Create3 => Formula::Create3 { deployer },
+        Mode::Account => Formula::Create2 {
+            deployer,
+            init_code_hash: *config.account_factory().init_code_hash(),
+        },
+        Mode::Tba => {
+            let header = hex::decode("3d60ad80600a3d3981f3363d3d373d3d3d363d73").expect("hex");
+            let footer = hex::decode("5af43d82803e903d91602b57fd5bf3").expect("hex");
+            let chain_id = config.chain_id.expect("validated");
+            let token_id = parse_uint256(config.token_id.as_deref().expect("validated"));
+            let init_code = [
+                &header[..],
+                &address(&config.implementation),
+                &footer,
+                &[0u8; 32], // salt
+                &uint_word(chain_id),
+                &address_word(&address(&config.token_contract)),
+                &token_id.expect("validated"),
+            ]
+            .concat();
+            Formula::TokenBound {
+                registry: deployer,
+                init_code,
+                salt_offset: header.len() + 20 + footer.len(),
+            }
+        }
+        Mode::Safe | Mode::Create2 | Mode::Hook | Mode::Clone => Formula::Create2 {
+            deployer,
+            init_code_hash: config.init_code_hash_bytes(),
+        },
+    }
+}
+
+fn salt_rule(config: &Config) -> SaltRule {
+    let hashed = |before: Vec<u8>, after: Vec<u8>| SaltRule {
+        fixed: Vec::new(),
+        hashed: Some((before, after)),
+    };
+    let raw = SaltRule {
+        fixed: Vec::new(),
+        hashed: None,
+    };
+    match config.mode {
+        // keccak256(abi.encodePacked(keccak256(initializer), saltNonce))
+        Mode::Safe => hashed(config.initializer_hash_bytes().to_vec(), Vec::new()),
+        Mode::Account => match config.account.expect("validated") {
+            AccountKind::Simple => raw,
+            // keccak256(abi.encode(owner, salt))
+            AccountKind::Light => hashed(
+                address_word(&address_at(&config.owner[0])).to_vec(),
+                Vec::new(),
+            ),
+            // keccak256(abi.encodePacked(data, salt))
+            AccountKind::Kernel => {
+                let data = config.init_data.as_deref().expect("validated");
+                let data =
+                    hex::decode(data.strip_prefix("0x").unwrap_or(data)).expect("validated hex");
+                hashed(data, Vec::new())
+            }
+            // keccak256(abi.encode(bytes[] owners, uint256 nonce))
+            AccountKind::Coinbase => {
+                let owners: Vec<Vec<u8>> = config
+                    .owner
+                    .iter()
+                    .map(|owner| match parse_hex::<20>(owner) {
+                        Some(address) => address_word(&address).to_vec(),
+                        None => parse_hex::<64>(owner).expect("validated hex").to_vec(),
+                    })
+                    .collect();
+                hashed(uint_word(0x40).to_vec(), encode_bytes_array(&owners))
+            }
+        },
+        Mode::Tba => raw,
+        Mode::Create2 | Mode::Create3 | Mode::Hook | Mode::Clone => match config.salt_preset() {
+            SaltPreset::Raw => raw,
+            SaltPreset::Immutable { caller } => SaltRule {
+                fixed: caller.to_vec(),
+                hashed: None,
+            },
+            // CreateX `_guard`
+            SaltPreset::CreateX { caller, chain_id } => {
+                let mut fixed = caller.unwrap_or_default().to_vec();
+                fixed.push(u8::from(chain_id.is_some()));
+                let mut before = Vec::new();
+                if let Some(caller) = caller {
+                    before.extend_from_slice(&address_word(&caller));
+                }
+                if let Some(chain_id) = chain_id {
+                    before.extend_from_slice(&uint_word(chain_id));
+                }
+                SaltRule {
+                    fixed,
+                    hashed: Some((before, Vec::new())),
+                }
+            }
+        },
+    }
+}
+
+/// abi.encode of a `bytes[]` as the tail after a head word: length, element
+/// offsets, then each element length-prefixed and zero-padded to 32 bytes.
+fn encode_bytes_array(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut offsets = Vec::new();
+    let mut elements = Vec::new();
+    for item in items {
+        offsets.extend_from_slice(&uint_word((32 * items.len() + elements.len()) as u64));
+        elements.extend_from_slice(&uint_word(item.len() as u64));
+        elements.extend_from_slice(item);
+        while elements.len() % 32 != 0 {
+            elements.push(0);
+        }
+    }
+    [&uint_word(items.len() as u64)[..], &offsets, &elements].concat()
+}
+
+fn compare(
+    worker: usize,
+    what: &'static str,
+    reported: &[u8],
+    derived: &[u8],
+) -> Result<(), VerifyError> {
+    if reported == derived {
+        return Ok(());
+    }
+    let show = |bytes: &[u8]| {
+        if bytes.is_empty() {
+            "none".to_string()
+        } else {
+            format!("0x{}", hex::encode(bytes))
+        }
+    };
+    Err(VerifyError::Mismatch {
+        worker,
+        what,
+        reported: show(reported),
+        derived: show(derived),
+    })
+}
+
+fn check_match(
+    worker: usize,
+    address: &[u8; 20],
+    matches: impl Fn(&str) -> bool,
+    pattern: &str,
+) -> Result<(), VerifyError> {
+    let hex = hex::encode(address);
+    if matches(&hex) {
+        return Ok(());
+    }
+    Err(VerifyError::NoMatch {
+        worker,
+        address: to_hex(address),
+        pattern: pattern.to_string(),
+    })
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+fn last_20(hash: [u8; 32]) -> [u8; 20] {
+    hash[12..].try_into().expect("20 bytes")
+}
+
+/// keccak256(0xff || deployer || salt || init_code_hash)[12..].
+fn create2(deployer: &[u8; 20], salt: &[u8; 32], init_code_hash: &[u8; 32]) -> [u8; 20] {
+    last_20(keccak256(
+        &[&[0xff][..], deployer, salt, init_code_hash].concat(),
+    ))
+}
+
+/// keccak256(rlp([deployer, nonce]))[12..].
+fn create_address(deployer: &[u8; 20], nonce: u64) -> [u8; 20] {
+    let mut items = rlp_bytes(deployer);
+    let nonce_bytes = nonce.to_be_bytes();
+    let start = nonce_bytes.iter().position(|&b| b != 0).unwrap_or(8);
+    items.extend(rlp_bytes(&nonce_bytes[start..]));
+
+    let mut rlp = vec![0xc0 + items.len() as u8];
+    rlp.extend(items);
+    last_20(keccak256(&rlp))
+}
+
+/// RLP of a short byte string (under 56 bytes).
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    match bytes {
+        [b] if *b < 0x80 => vec![*b],
+        _ => {
+            let mut out = vec![0x80 + bytes.len() as u8];
+            out.extend_from_slice(bytes);
+            out
+        }
+    }
+}
+
+fn uint_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+fn address_word(address: &[u8; 20]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address);
+    word
+}
+
+/// A validated address option of the configuration.
+fn address(value: &Option<String>) -> [u8; 20] {
+    address_at(value.as_deref().expect("validated"))
+}
+
+fn address_at(value: &str) -> [u8; 20] {
+    parse_hex(value).expect("validated hex")
+}
+
+fn to_hex(address: &[u8; 20]) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::matcher::Address;
+
+    fn parse(args: &[&str]) -> Config {
+        let args = std::iter::once("safe_vanity").chain(args.iter().copied());
+        let config = Config::try_parse_from(args).unwrap();
+        config.validate().unwrap();
+        config
+    }
+
+    /// Mines a result the way a worker does.
+    fn mine(config: &Config) -> SafeVanityResult {
+        let derivation = config.derivation();
+        let pattern = config.pattern();
+        let child = config.child_target();
+        for i in 0u32.. {
+            let mut nonce = [0x5au8; 32];
+            nonce[28..].copy_from_slice(&i.to_be_bytes());
+            derivation.apply_prefix(&mut nonce);
+            let address = Address::from_bytes(derivation.address(&nonce));
+            let matched = match child {
+                None => pattern.matches(&address).is_match().then_some(None),
+                Some(ref child) => child.matches(&pattern, &address).map(Some),
+            };
+            if let Some(child_address) = matched {
+                return SafeVanityResult {
+                    salt_nonce: nonce,
+                    salt: derivation.salt(&nonce),
+                    address: *address.as_bytes(),
+                    child_address,
+                    worker_id: 1,
+                };
+            }
+        }
+        unreachable!()
+    }
+
+    const DEPLOYER: &str = "0x0000000000000000000000000000000000001111";
+    const CALLER: &str = "0x00000000000000000000000000000000deadbeef";
+
+    #[test]
+    fn test_verify_agrees_with_search_path() {
+        let hash = "11".repeat(32);
+        let owners = format!("{},{}", "33".repeat(20), "44".repeat(64));
+        let configs: Vec<Vec<&str>> = vec![
+            vec!["--factory", DEPLOYER, "--init-code-hash", &hash, "--initializer-hash", &hash],
+            vec!["-m", "create2", "--deployer", DEPLOYER, "--init-code", "0xdeadbeef"],
+            vec![
+                "-m", "create2",
+                "--init-code", "0xdeadbeef",
+                "--salt-preset", "immutable",
+                "--caller", CALLER,
+            ],
+            vec![
+                "-m", "create2",
+                "--init-code", "0xdeadbeef",
+                "--salt-preset", "createx",
+                "--caller", CALLER,
+                "--chain-id", "10",
+            ],
+            vec!["-m", "create3", "--salt-preset", "createx", "--chain-id", "10"],
+            vec!["-m", "create3", "--salt-preset", "createx"],
+            vec!["-m", "clone", "--deployer", DEPLOYER, "--implementation", CALLER],
+            vec![
+                "-m", "account",
+                "--account", "light",
+                "--factory", DEPLOYER,
+                "--implementation", CALLER,
+                "--owner", CALLER,
+            ],
+            vec![
+                "-m", "account",
+                "--account", "kernel",
+                "--factory", DEPLOYER,
+                "--implementation", CALLER,
+                "--init-data", "0xc0ffee",
+            ],
+            vec![
+                "-m", "account",
+                "--account", "coinbase",
+                "--factory", DEPLOYER,
+                "--implementation", CALLER,
+                "--owner", &owners,
+            ],
+            vec![
+                "-m", "account",
+                "--account", "simple",
+                "--factory", DEPLOYER,
+                "--implementation", CALLER,
+                "--owner", CALLER,
+                "--init-code", "0x6080",
+            ],
+            vec![
+                "-m", "tba",
+                "--implementation", CALLER,
+                "--token-contract", DEPLOYER,
+                "--token-id", "300",
+                "--chain-id", "1",
+            ],
+            vec!["--chain", "zksync", "--singleton", CALLER, "--initializer-hash", &hash],
+            vec![
+                "-m", "create2",
+                "--chain", "zksync",
+                "--deployer", DEPLOYER,
+                "--bytecode-hash", &hash,
+                "--constructor-input", "0xbeef",
+            ],
+            vec![
+                "-m", "create2",
+                "--deployer", DEPLOYER,
+                "--init-code", "0xdeadbeef",
+                "--child-nonce", "1",
+                "--parent-pattern", "a",
+            ],
+        ];
+        for args in configs {
+            let mut args = args;
+            args.extend(["-p", "ab", "-t", "contains"]);
+            let config = parse(&args);
+            let result = mine(&config);
+            assert_eq!(Verifier::new(&config).verify(&result), Ok(()), "{:?}", args);
+        }
+
+        let config = parse(&[
+            "-m", "hook",
+            "--deployer", DEPLOYER,
+            "--init-code-hash", &hash,
+            "--hooks", "beforeSwap",
+        ]);
+        assert_eq!(Verifier::new(&config).verify(&mine(&config)), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_results() {
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "ab",
+            "-t", "contains",
+            "--init-code", "0xdeadbeef",
+            "--salt-preset", "createx",
+            "--caller", CALLER,
+        ]);
+        let verifier = Verifier::new(&config);
+        let result = mine(&config);
+
+        let mut wrong = result.clone();
+        wrong.address[0] ^= 1;
+        assert!(matches!(
+            verifier.verify(&wrong),
+            Err(VerifyError::Mismatch { what: "address", .. })
+        ));
+
+        let mut wrong = result.clone();
+        wrong.salt_nonce[0] ^= 1;
+        assert!(matches!(
+            verifier.verify(&wrong),
+            Err(VerifyError::Mismatch { what: "fixed salt prefix", .. })
+        ));
+
+        let mut wrong = result.clone();
+        wrong.salt = wrong.salt_nonce;
+        assert!(matches!(
+            verifier.verify(&wrong),
+            Err(VerifyError::Mismatch { what: "salt", .. })
+        ));
+
+        let mut wrong = result.clone();
+        wrong.child_address = Some([0u8; 20]);
+        assert!(matches!(
+            verifier.verify(&wrong),
+            Err(VerifyError::Mismatch { what: "child address", .. })
+        ));
+
+        // A correctly derived address that does not match the pattern
+        let config = parse(&[
+            "-m", "create2",
+            "-p", "ab",
+            "-t", "contains",
+            "--deployer", DEPLOYER,
+            "--init-code", "0xdeadbeef",
+        ]);
+        let mut nonce = [0u8; 32];
+        while hex::encode(config.derivation().address(&nonce)).contains("ab") {
+            nonce[31] += 1;
+        }
+        let result = SafeVanityResult {
+            salt_nonce: nonce,
+            salt: nonce,
+            address: config.derivation().address(&nonce),
+            child_address: None,
+            worker_id: 2,
+        };
+        assert!(matches!(
+            Verifier::new(&config).verify(&result),
+            Err(VerifyError::NoMatch { worker: 2, .. })
+        ));
+    }
+}
@@ -6,8 +6,8 @@ use std::sync::Arc;
 use crossbeam_channel::Sender;
 use rand::RngCore;
 
-use crate::crypto::create2::{safe_address, safe_salt};
-use crate::matcher::{Address, Pattern};
+use crate::crypto::keccak_simd::{BatchHasher, BATCH_LANES};
+use crate::matcher::{Address, ChildTarget, Pattern};
 
 use super::SafeVanityResult;
 
@@ -32,9 +32,8 @@ impl WorkerStats {
 pub struct CpuWorker {
     id: usize,
     pattern: Pattern,
-    factory: [u8; 20],
-    init_code_hash: [u8; 32],
-    initializer_hash: [u8; 32],
+    child: Option<ChildTarget>,
+    hasher: BatchHasher,
     result_tx: Sender<SafeVanityResult>,
     stop_flag: Arc<AtomicBool>,
     stats: Arc<WorkerStats>,
@@ -44,9 +43,8 @@ impl CpuWorker {
     pub fn new(
         id: usize,
         pattern: Pattern,
-        factory: [u8; 20],
-        init_code_hash: [u8; 32],
-        initializer_hash: [u8; 32],
+        child: Option<ChildTarget>,
+        hasher: BatchHasher,
         result_tx: Sender<SafeVanityResult>,
         stop_flag: Arc<AtomicBool>,
         stats: Arc<WorkerStats>,
@@ -54,9 +52,8 @@ impl CpuWorker {
         Self {
             id,
             pattern,
-            factory,
-            init_code_hash,
-            initializer_hash,
+            child,
+            hasher,
             result_tx,
             stop_flag,
             stats,
@@ -64,7 +61,8 @@ impl CpuWorker {
     }
 
     pub fn run(&self) {
-        const BATCH_SIZE: u64 = 1000;
+        // Multiple of BATCH_LANES so every hasher call is a full batch.
+        const BATCH_SIZE: u64 = 1024;
 
         // Each worker starts from a random nonce and increments sequentially.
         // This avoids per-iteration RNG overhead while ensuring workers explore
@@ -72,28 +70,44 @@ impl CpuWorker {
         let mut salt_nonce = [0u8; 32];
         rand::thread_rng().fill_bytes(&mut salt_nonce);
 
+        let mut nonces = [[0u8; 32]; BATCH_LANES];
+        let mut addrs = [[0u8; 20]; BATCH_LANES];
+
         loop {
             if self.stop_flag.load(Ordering::Relaxed) {
                 break;
             }
 
-            for _ in 0..BATCH_SIZE {
-                let salt = safe_salt(&self.initializer_hash, &salt_nonce);
-                let addr = safe_address(&self.factory, &self.init_code_hash, &salt);
-                let address = Address::from_bytes(addr);
-
-                if self.pattern.matches(&address).is_match() {
-                    self.stats.matches_found.fetch_add(1, Ordering::Relaxed);
-                    let result = SafeVanityResult {
-                        salt_nonce,
-                        address: addr,
-                        worker_id: self.id,
-                    };
-                    let _ = self.result_tx.send(result);
+            for _ in 0..BATCH_SIZE / BATCH_LANES as u64 {
+                for nonce in nonces.iter_mut() {
+                    *nonce = salt_nonce;
+                    self.hasher.derivation().apply_prefix(nonce);
+                    // Increment nonce as a 256-bit big-endian counter
+                    increment_nonce(&mut salt_nonce);
                 }
+                self.hasher.hash_batch(&nonces, &mut addrs);
+
+                for (nonce, addr) in nonces.iter().zip(addrs.iter()) {
+                    let address = Address::from_bytes(*addr);
 
-                // Increment nonce as a 256-bit big-endian counter
-                increment_nonce(&mut salt_nonce);
+                    // With a child target the pattern applies to the CREATE
+                    // child derived from the mined address.
+                    let matched = match self.child {
+                        None => self.pattern.matches(&address).is_match().then_some(None),
+                        Some(ref child) => child.matches(&self.pattern, &address).map(Some),
+                    };
+                    if let Some(child_address) = matched {
+                        self.stats.matches_found.fetch_add(1, Ordering::Relaxed);
+                        let result = SafeVanityResult {
+                            salt_nonce: *nonce,
+                            salt: self.hasher.derivation().salt(nonce),
+                            address: *addr,
+                            child_address,
+                            worker_id: self.id,
+                        };
+                        let _ = self.result_tx.send(result);
+                    }
+                }
             }
 
             self.stats.salts_tried.fetch_add(BATCH_SIZE, Ordering::Relaxed);
@@ -7,17 +7,23 @@ use std::time::{Duration, Instant};
 
 use crossbeam_channel::{bounded, Receiver};
 
-use crate::matcher::Pattern;
+use crate::crypto::{BatchHasher, Derivation, KeccakBackend};
+use crate::matcher::{ChildTarget, Pattern};
 
 use super::cpu::{CpuWorker, WorkerStats};
 
 /// Result of a successful Safe vanity match.
 #[derive(Debug, Clone)]
 pub struct SafeVanityResult {
-    /// The salt nonce (32 bytes) to use with SafeProxyFactory.createProxyWithNonce.
+    /// The mined value (32 bytes): the saltNonce for SafeProxyFactory.createProxyWithNonce,
+    /// or the raw salt in create2 mode.
     pub salt_nonce: [u8; 32],
+    /// The CREATE2 salt derived from `salt_nonce` (equal to it in create2 mode).
+    pub salt: [u8; 32],
     /// The predicted Safe proxy address (20 bytes).
     pub address: [u8; 20],
+    /// The contract `address` deploys with CREATE, when matching a child.
+    pub child_address: Option<[u8; 20]>,
     /// Worker ID that found it.
     pub worker_id: usize,
 }
@@ -33,10 +39,21 @@ impl SafeVanityResult {
         bytes_to_decimal(&self.salt_nonce)
     }
 
+    /// CREATE2 salt as hex (no 0x).
+    pub fn salt_hex(&self) -> String {
+        hex::encode(self.salt)
+    }
+
     /// Address as checksummed hex (0x...).
     pub fn address_checksum(&self) -> String {
         crate::matcher::Address::from_bytes(self.address).to_checksum()
     }
+
+    /// Child contract address as checksummed hex (0x...), when matching a child.
+    pub fn child_address_checksum(&self) -> Option<String> {
+        self.child_address
+            .map(|child| crate::matcher::Address::from_bytes(child).to_checksum())
+    }
 }
 
 /// Convert a big-endian byte array to decimal string without bigint crate.
@@ -83,9 +100,9 @@ impl WorkerPool {
     pub fn new(
         num_workers: usize,
         pattern: Pattern,
-        factory: [u8; 20],
-        init_code_hash: [u8; 32],
-        initializer_hash: [u8; 32],
+        backend: KeccakBackend,
+        derivation: Derivation,
+        child: Option<ChildTarget>,
     ) -> Self {
         let (result_tx, result_rx) = bounded(100);
         let stop_flag = Arc::new(AtomicBool::new(false));
@@ -94,23 +111,16 @@ impl WorkerPool {
         let handles = (0..num_workers)
             .map(|id| {
                 let pattern = pattern.clone();
+                let child = child.clone();
                 let result_tx = result_tx.clone();
                 let stop_flag = stop_flag.clone();
                 let stats = stats.clone();
+                let hasher = BatchHasher::new(backend, derivation.clone());
 
                 thread::Builder::new()
                     .name(format!("safe-vanity-worker-{}", id))
                     .spawn(move || {
-                        let worker = CpuWorker::new(
-                            id,
-                            pattern,
-                            factory,
-                            init_code_hash,
-                            initializer_hash,
-                            result_tx,
-                            stop_flag,
-                            stats,
-                        );
+                        let worker = CpuWorker::new(id, pattern, child, hasher, result_tx, stop_flag, stats);
                         worker.run();
                     })
                     .expect("spawn worker")